@@ -0,0 +1,40 @@
+//! Compares the packed `Vec3x4` area kernel against the 4x unrolled scalar kernel.
+//! Run with `cargo bench --bench area`.
+
+mod common;
+
+use common::grid;
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use vestra_physics::engine::state::PhysicsState;
+use vestra_physics::systems::constraints::area::AreaConstraint;
+
+/// Builds an `n x n` grid of quads (two triangles each) and slightly distorts it.
+fn grid_state(n: usize) -> PhysicsState {
+    let (positions, indices, uvs) = grid(n, 0.01, 0.0);
+    let mut state = PhysicsState::new(&positions, &indices, &uvs);
+    for (i, p) in state.positions.iter_mut().enumerate() {
+        p.y = ((i * 7919) % 13) as f32 * 0.001;
+    }
+    state
+}
+
+fn bench_area(c: &mut Criterion) {
+    let base = grid_state(64);
+    let constraint = AreaConstraint::new(&base);
+    let dt = 1.0 / 480.0;
+
+    let mut group = c.benchmark_group("area_constraint");
+    group.bench_function("unrolled", |b| {
+        let mut state = base.clone();
+        b.iter(|| constraint.solve_unrolled(black_box(&mut state), 2e-4, 1.0, dt));
+    });
+    group.bench_function("simd", |b| {
+        let mut state = base.clone();
+        b.iter(|| constraint.solve_simd(black_box(&mut state), 2e-4, 1.0, dt));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_area);
+criterion_main!(benches);
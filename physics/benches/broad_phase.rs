@@ -0,0 +1,113 @@
+//! Broad phase cost (uniform grid vs BVH) for a dense cloth draped over a large, finely tessellated collider.
+//! With the `parallel` feature, `grid_double_query` times the previous count-then-requery
+//! gather as a baseline for the single-query one in `perform_broad_phase`.
+//! Run with `cargo bench --bench broad_phase`.
+
+mod common;
+
+use common::grid;
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use vestra_physics::collision::{CollisionResolver, MeshCollider};
+use vestra_physics::engine::config::PhysicsConfig;
+use vestra_physics::engine::state::PhysicsState;
+#[cfg(feature = "parallel")]
+use {rayon::prelude::*, rustc_hash::FxHashSet};
+
+/// The pre-single-query parallel gather: one pass counts every particle's candidates,
+/// a prefix sum sizes the flat buffer, and a second pass re-runs every query to
+/// scatter into it. Same search radius and filtering as `perform_broad_phase`.
+#[cfg(feature = "parallel")]
+fn double_query_broad_phase(
+    state: &PhysicsState,
+    collider: &MeshCollider,
+    margin: f32,
+    counts: &mut [usize],
+    offsets: &mut [usize],
+    indices: &mut Vec<usize>,
+) {
+    let lookup = &collider.lookup;
+    let radius = |i: usize| {
+        let (pos, prev) = (state.positions[i].truncate(), state.prev_positions[i].truncate());
+        if state.inv_mass[i] == 0.0 || (!lookup.contains(pos) && !lookup.contains(prev)) {
+            return None;
+        }
+        Some((pos, margin + pos.distance(prev)))
+    };
+
+    counts.par_iter_mut().enumerate().for_each(|(i, count)| {
+        *count = radius(i).map_or(0, |(pos, r)| {
+            let (mut buffer, mut dedup) = (Vec::with_capacity(32), FxHashSet::default());
+            lookup.query(pos, r, &mut buffer, &mut dedup);
+            buffer.len()
+        });
+    });
+
+    let mut total = 0;
+    for (offset, &count) in offsets.iter_mut().zip(counts.iter()) {
+        *offset = total;
+        total += count;
+    }
+    indices.resize(total, 0);
+
+    let mut slices = Vec::with_capacity(counts.len());
+    let mut rest = indices.as_mut_slice();
+    for &count in counts.iter() {
+        let (head, tail) = rest.split_at_mut(count);
+        slices.push(head);
+        rest = tail;
+    }
+    slices.into_par_iter().enumerate().for_each(|(i, out)| {
+        if out.is_empty() {
+            return;
+        }
+        let (pos, r) = radius(i).unwrap();
+        let (mut buffer, mut dedup) = (Vec::with_capacity(out.len()), FxHashSet::default());
+        lookup.query(pos, r, &mut buffer, &mut dedup);
+        out.copy_from_slice(&buffer);
+    });
+}
+
+fn bench_broad_phase(c: &mut Criterion) {
+    let (body_pos, body_idx, _) = grid(150, 0.01, 0.0);
+    let mut collider = MeshCollider::new(body_pos, Vec::new(), body_idx, 0, 0.0);
+
+    let (cloth_pos, cloth_idx, cloth_uvs) = grid(64, 0.02, 0.01);
+    let mut state = PhysicsState::new(&cloth_pos, &cloth_idx, &cloth_uvs);
+    // Give every particle a little velocity so the swept search radius is realistic
+    for (i, p) in state.prev_positions.iter_mut().enumerate() {
+        p.y += ((i * 7919) % 13) as f32 * 0.001;
+    }
+
+    let config = PhysicsConfig::default();
+    let mut resolver = CollisionResolver::new(state.count);
+    let mut group = c.benchmark_group("broad_phase");
+    group.bench_function("grid", |b| {
+        b.iter(|| resolver.broad_phase(black_box(&state), &mut collider, &config, 1));
+    });
+    #[cfg(feature = "parallel")]
+    {
+        let (mut counts, mut offsets) = (vec![0; state.count], vec![0; state.count]);
+        let mut indices = Vec::new();
+        group.bench_function("grid_double_query", |b| {
+            b.iter(|| {
+                double_query_broad_phase(
+                    black_box(&state),
+                    &collider,
+                    config.broad_phase_margin,
+                    &mut counts,
+                    &mut offsets,
+                    &mut indices,
+                )
+            });
+        });
+    }
+    collider.use_bvh(true);
+    group.bench_function("bvh", |b| {
+        b.iter(|| resolver.broad_phase(black_box(&state), &mut collider, &config, 1));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_broad_phase);
+criterion_main!(benches);
@@ -0,0 +1,24 @@
+//! Mesh builders shared by the benches.
+
+/// `n x n` quads (two triangles each) with `spacing`, lying in the XZ plane at height `y`.
+/// Returns `(positions, indices, uvs)`.
+pub fn grid(n: usize, spacing: f32, y: f32) -> (Vec<f32>, Vec<u32>, Vec<f32>) {
+    let mut positions = Vec::with_capacity((n + 1) * (n + 1) * 3);
+    let mut uvs = Vec::with_capacity((n + 1) * (n + 1) * 2);
+    for z in 0..=n {
+        for x in 0..=n {
+            positions.extend_from_slice(&[x as f32 * spacing, y, z as f32 * spacing]);
+            uvs.extend_from_slice(&[x as f32 / n as f32, z as f32 / n as f32]);
+        }
+    }
+
+    let mut indices = Vec::with_capacity(n * n * 6);
+    let row = (n + 1) as u32;
+    for z in 0..n as u32 {
+        for x in 0..n as u32 {
+            let i = z * row + x;
+            indices.extend_from_slice(&[i, i + row, i + 1, i + 1, i + row, i + row + 1]);
+        }
+    }
+    (positions, indices, uvs)
+}
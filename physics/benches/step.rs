@@ -0,0 +1,31 @@
+//! Full `Simulation::step` cost through the headless `NativeEngine`: a cloth draped over
+//! a flat body, with and without self-collision. Run with `cargo bench --bench step`.
+
+mod common;
+
+use common::grid;
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use vestra_physics::engine::{NativeEngine, PhysicsConfig};
+
+/// A 48 x 48 cloth settled for a few frames on a larger body grid.
+fn draped(self_collision: bool) -> NativeEngine {
+    let (body_pos, body_idx, _) = grid(60, 0.02, 0.0);
+    let (cloth_pos, cloth_idx, cloth_uvs) = grid(48, 0.02, 0.02);
+    let config = PhysicsConfig { self_collision_enabled: self_collision, ..Default::default() };
+    let mut engine = NativeEngine::new(cloth_pos, cloth_idx, cloth_uvs, body_pos, body_idx, config).unwrap();
+    engine.run(10, 1.0 / 60.0);
+    engine
+}
+
+fn bench_step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("step");
+    for (name, self_collision) in [("cloth", false), ("cloth_self_collision", true)] {
+        let mut engine = draped(self_collision);
+        group.bench_function(name, |b| b.iter(|| engine.step(black_box(1.0 / 60.0))));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_step);
+criterion_main!(benches);
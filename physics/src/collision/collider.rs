@@ -1,8 +1,10 @@
 // physics/src/collision/collider.rs
 use super::geometry::Triangle;
 use super::preprocessing;
-use super::spatial::StaticSpatialHash;
+use super::spatial::static_grid::BOUNDS_PADDING;
+use super::spatial::{StaticSpatialHash, TriangleBvh};
 use glam::Vec3;
+use rustc_hash::FxHashSet;
 
 /// Represents a static collider mesh (e.g., the Mannequin).
 /// Uses a Spatial Hash for efficient broad-phase collision detection.
@@ -13,32 +15,153 @@ pub struct MeshCollider {
     pub normals: Vec<Vec3>,
     pub indices: Vec<u32>,
     pub triangles: Vec<Triangle>,
-    pub spatial_hash: StaticSpatialHash,
+    /// Broad-phase structure over `triangles` (uniform grid by default, see `use_bvh`).
+    pub lookup: TriangleLookup,
+    /// Cell size (meters) of the uniform grid, see `set_cell_size`.
+    pub cell_size: f32,
     // Config for updates
     pub smoothing_iterations: usize,
+    pub smoothing_lambda: f32,
     pub inflation_amount: f32,
+    /// Angle-weighted (instead of area-weighted) vertex normals, see `set_angle_weighted_normals`.
+    pub angle_weighted_normals: bool,
+    /// Per-triangle friction multiplier (e.g. < 1 for a silk lining, > 1 for bare skin).
+    /// Empty = 1.0 everywhere.
+    pub friction: Vec<f32>,
 }
 
+/// Grid cell size (meters) used when the mesh has no usable edges to derive one from.
+const GRID_CELL_SIZE: f32 = 0.1;
+
+/// Derived grid cells span this many mean edge lengths, so a cell holds a handful of
+/// triangles: fine enough for a hand, coarse enough that a torso query stays a few cells.
+const AUTO_CELL_EDGE_FACTOR: f32 = 2.0;
+/// Smallest grid cell size, derived or set by hand: finer cells make a query walk many
+/// cells and allocate a huge grid for a full body.
+pub const MIN_CELL_SIZE: f32 = 0.02;
+/// Largest derived cell size; above it every cell holds most of the mesh.
+const MAX_AUTO_CELL_SIZE: f32 = 0.5;
+
+/// Grid cell size derived from the mean triangle edge length of `triangles`.
+pub fn auto_cell_size(triangles: &[Triangle]) -> f32 {
+    if triangles.is_empty() {
+        return GRID_CELL_SIZE;
+    }
+    let total: f32 = triangles
+        .iter()
+        .map(|t| t.v0.distance(t.v1) + t.v1.distance(t.v2) + t.v2.distance(t.v0))
+        .sum();
+    let mean_edge = total / (3 * triangles.len()) as f32;
+    if !mean_edge.is_finite() || mean_edge <= 0.0 {
+        return GRID_CELL_SIZE;
+    }
+    (mean_edge * AUTO_CELL_EDGE_FACTOR).clamp(MIN_CELL_SIZE, MAX_AUTO_CELL_SIZE)
+}
+
+/// Acceleration structure the broad phase uses to find triangles near a point.
+pub enum TriangleLookup {
+    /// Uniform grid: O(1) cell lookups, best for compact meshes with even triangle sizes.
+    Grid(StaticSpatialHash),
+    /// AABB tree: no memory cap and tight candidate lists for elongated meshes
+    /// (a full-body avatar) or very non-uniform triangle sizes.
+    Bvh(TriangleBvh),
+}
+
+impl TriangleLookup {
+    /// Builds a lookup over `triangles`; `cell_size` only applies to the grid.
+    fn build(triangles: &[Triangle], use_bvh: bool, cell_size: f32) -> Self {
+        let bounds: Vec<(Vec3, Vec3)> = triangles.iter().map(|t| t.aabb()).collect();
+        if use_bvh {
+            return TriangleLookup::Bvh(TriangleBvh::new(&bounds));
+        }
+
+        let mut min_bound = Vec3::splat(f32::MAX);
+        let mut max_bound = Vec3::splat(f32::MIN);
+        for &(min, max) in &bounds {
+            min_bound = min_bound.min(min);
+            max_bound = max_bound.max(max);
+        }
+
+        let mut grid = StaticSpatialHash::new(min_bound, max_bound, cell_size);
+        for (i, &(min, max)) in bounds.iter().enumerate() {
+            grid.insert_aabb(i, min, max);
+        }
+        TriangleLookup::Grid(grid)
+    }
+
+    /// Fast AABB check to skip particles far outside the mesh.
+    pub fn contains(&self, p: Vec3) -> bool {
+        match self {
+            TriangleLookup::Grid(grid) => grid.contains(p),
+            // Same padding as the grid bounds
+            TriangleLookup::Bvh(bvh) => bvh.contains(p, BOUNDS_PADDING),
+        }
+    }
+
+    /// `(cells, entries)` of the uniform grid; `(0, 0)` for the BVH.
+    pub fn grid_stats(&self) -> (usize, usize) {
+        match self {
+            TriangleLookup::Grid(grid) => (grid.cell_count(), grid.entry_count()),
+            TriangleLookup::Bvh(_) => (0, 0),
+        }
+    }
+
+    /// Retrieves the triangles near `p` (within `radius`) into `buffer`.
+    pub fn query(
+        &self,
+        p: Vec3,
+        radius: f32,
+        buffer: &mut Vec<usize>,
+        dedup_set: &mut FxHashSet<usize>,
+    ) {
+        match self {
+            TriangleLookup::Grid(grid) => grid.query(p, radius, buffer, dedup_set),
+            TriangleLookup::Bvh(bvh) => bvh.query(p, radius, buffer),
+        }
+    }
+}
+
+/// Laplacian smoothing factor used by `MeshCollider::new`.
+pub const DEFAULT_SMOOTHING_LAMBDA: f32 = 0.5;
+
 impl MeshCollider {
     pub fn new(
+        raw_vertices: Vec<f32>,
+        raw_normals: Vec<f32>,
+        indices: Vec<u32>,
+        smoothing: usize,
+        inflation: f32,
+    ) -> Self {
+        Self::with_smoothing(
+            raw_vertices,
+            raw_normals,
+            indices,
+            smoothing,
+            DEFAULT_SMOOTHING_LAMBDA,
+            inflation,
+        )
+    }
+
+    /// Same as `new`, with an explicit Laplacian smoothing factor per iteration.
+    /// Low `smoothing_lambda` (or `smoothing` = 0) keeps sharp features like fingers;
+    /// higher values clean up noisy scans.
+    pub fn with_smoothing(
         raw_vertices: Vec<f32>,
         _raw_normals: Vec<f32>,
         indices: Vec<u32>,
         smoothing: usize,
+        smoothing_lambda: f32,
         inflation: f32,
     ) -> Self {
         // Pass config to processor
-        let processed = preprocessing::process_mesh(&raw_vertices, &indices, smoothing, inflation);
-
-        let mut min_bound = Vec3::splat(f32::MAX);
-        let mut max_bound = Vec3::splat(f32::MIN);
-
-        for &v in &processed.vertices {
-            min_bound = min_bound.min(v);
-            max_bound = max_bound.max(v);
-        }
+        let processed = preprocessing::process_mesh(
+            &raw_vertices,
+            &indices,
+            smoothing,
+            smoothing_lambda,
+            inflation,
+        );
 
-        let mut spatial_hash = StaticSpatialHash::new(min_bound, max_bound, 0.1);
         let mut triangles = Vec::new();
 
         let num_triangles = indices.len() / 3;
@@ -51,21 +174,23 @@ impl MeshCollider {
             let v1 = processed.vertices[idx1];
             let v2 = processed.vertices[idx2];
 
-            let tri = Triangle::new(v0, v1, v2, i);
-            let (min, max) = tri.aabb();
-
-            triangles.push(tri);
-            spatial_hash.insert_aabb(i, min, max);
+            triangles.push(Triangle::new(v0, v1, v2, i));
         }
+        let cell_size = auto_cell_size(&triangles);
+        let lookup = TriangleLookup::build(&triangles, false, cell_size);
 
         MeshCollider {
             vertices: processed.vertices,
             normals: processed.normals,
             indices,
             triangles,
-            spatial_hash,
+            lookup,
+            cell_size,
             smoothing_iterations: smoothing,
+            smoothing_lambda,
             inflation_amount: inflation,
+            angle_weighted_normals: false,
+            friction: Vec::new(),
         }
     }
 
@@ -73,18 +198,62 @@ impl MeshCollider {
     /// Reprocesses the mesh (smoothing/inflation) and rebuilds the spatial hash.
     pub fn update(&mut self, raw_vertices: &[f32]) {
         // 1. Reprocess Mesh (this handles smoothing and inflation)
-        let processed = preprocessing::process_mesh(
+        let processed = preprocessing::process_mesh_weighted(
             raw_vertices,
             &self.indices,
             self.smoothing_iterations,
+            self.smoothing_lambda,
             self.inflation_amount,
+            self.angle_weighted_normals,
         );
 
         self.vertices = processed.vertices;
         self.normals = processed.normals;
 
-        // 2. Clear Spatial Hash
-        self.spatial_hash.clear();
+        self.rebuild_triangles();
+    }
+
+    /// Changes how far the collider is pushed out along its smoothed normals (the visible
+    /// cloth-body gap is `contact_thickness` + inflation). Normals are computed before
+    /// inflation, so the offset is swapped in place without the raw mesh.
+    pub fn set_inflation(&mut self, inflation: f32) {
+        let delta = inflation - self.inflation_amount;
+        for (v, &n) in self.vertices.iter_mut().zip(&self.normals) {
+            *v += n * delta;
+        }
+        self.inflation_amount = inflation;
+
+        self.rebuild_triangles();
+    }
+
+    /// Switches the smooth collider normals between area-weighted (default) and
+    /// angle-weighted accumulation. Angle weighting keeps normals from leaning towards
+    /// the large faces of unevenly tessellated scans, so contact response points the
+    /// right way. The inflation is re-applied along the new normals.
+    pub fn set_angle_weighted_normals(&mut self, enabled: bool) {
+        if enabled == self.angle_weighted_normals {
+            return;
+        }
+        self.angle_weighted_normals = enabled;
+
+        // Normals are computed on the uninflated surface, so step back onto it first
+        for (v, &n) in self.vertices.iter_mut().zip(&self.normals) {
+            *v -= n * self.inflation_amount;
+        }
+        self.normals = preprocessing::vertex_normals(&self.vertices, &self.indices, enabled);
+        for (v, &n) in self.vertices.iter_mut().zip(&self.normals) {
+            *v += n * self.inflation_amount;
+        }
+
+        self.rebuild_triangles();
+    }
+
+    /// Refreshes triangle geometry from `vertices` and re-inserts them into the spatial hash.
+    fn rebuild_triangles(&mut self) {
+        // 2. Clear Spatial Hash (the grid keeps its original bounds)
+        if let TriangleLookup::Grid(grid) = &mut self.lookup {
+            grid.clear();
+        }
 
         // 3. Update Triangles & Re-insert into Hash
         let num_triangles = self.indices.len() / 3;
@@ -102,7 +271,141 @@ impl MeshCollider {
             let (min, max) = self.triangles[i].aabb();
 
             // Insert into Hash
-            self.spatial_hash.insert_aabb(i, min, max);
+            if let TriangleLookup::Grid(grid) = &mut self.lookup {
+                grid.insert_aabb(i, min, max);
+            }
+        }
+
+        // The tree is rebuilt from scratch (O(n log n), same order as the grid re-insert)
+        if matches!(self.lookup, TriangleLookup::Bvh(_)) {
+            self.lookup = TriangleLookup::build(&self.triangles, true, self.cell_size);
+        }
+    }
+
+    /// Switches the broad-phase structure between the uniform grid (default) and a BVH.
+    /// Prefer the BVH for tall/thin bodies or meshes mixing tiny and huge triangles.
+    pub fn use_bvh(&mut self, enabled: bool) {
+        if enabled != matches!(self.lookup, TriangleLookup::Bvh(_)) {
+            self.lookup = TriangleLookup::build(&self.triangles, enabled, self.cell_size);
+        }
+    }
+
+    /// The uniform grid, or `None` while the BVH is active.
+    #[deprecated(note = "use `lookup`, which may also hold a BVH")]
+    pub fn spatial_hash(&self) -> Option<&StaticSpatialHash> {
+        match &self.lookup {
+            TriangleLookup::Grid(grid) => Some(grid),
+            TriangleLookup::Bvh(_) => None,
+        }
+    }
+
+    /// Sets the grid cell size in meters; 0.0 derives it from the mean triangle edge
+    /// length (the default). Too coarse and every query returns huge candidate lists,
+    /// too fine and a query walks many cells. Sizes below `MIN_CELL_SIZE` are raised to it.
+    /// Rebuilds the grid if the size changed.
+    pub fn set_cell_size(&mut self, cell_size: f32) {
+        let cell_size = if cell_size > 0.0 {
+            cell_size.max(MIN_CELL_SIZE)
+        } else {
+            auto_cell_size(&self.triangles)
+        };
+        if cell_size == self.cell_size {
+            return;
+        }
+        self.cell_size = cell_size;
+        if matches!(self.lookup, TriangleLookup::Grid(_)) {
+            self.lookup = TriangleLookup::build(&self.triangles, false, cell_size);
+        }
+    }
+
+    /// Sets the per-triangle friction multipliers (one finite value >= 0 per triangle);
+    /// an empty map restores uniform friction.
+    pub fn set_friction(&mut self, friction: Vec<f32>) -> Result<(), String> {
+        if !friction.is_empty() && friction.len() != self.triangles.len() {
+            return Err(format!(
+                "collider_friction: expected {} values (1 per triangle), got {}",
+                self.triangles.len(),
+                friction.len()
+            ));
+        }
+        if let Some(k) = friction.iter().position(|f| !f.is_finite() || *f < 0.0) {
+            return Err(format!("collider_friction: value at index {} must be finite and >= 0", k));
+        }
+        self.friction = friction;
+        Ok(())
+    }
+
+    /// Friction multiplier of triangle `t` (1.0 without a friction map).
+    #[inline]
+    pub fn friction_at(&self, t: usize) -> f32 {
+        self.friction.get(t).copied().unwrap_or(1.0)
+    }
+
+    /// Processed (smoothed + inflated) vertices as a flat `[x, y, z, ...]` buffer:
+    /// the surface the physics actually collides against, not the raw input mesh.
+    pub fn flat_vertices(&self) -> Vec<f32> {
+        self.vertices.iter().flat_map(|v| v.to_array()).collect()
+    }
+
+    /// Normals of the processed surface as a flat `[x, y, z, ...]` buffer.
+    pub fn flat_normals(&self) -> Vec<f32> {
+        self.normals.iter().flat_map(|n| n.to_array()).collect()
+    }
+
+    /// True if the segment `p1 -> p2` crosses the collider surface. Long segments are
+    /// marched in lookup-cell-sized pieces, so each query touches a few cells instead of
+    /// one box around the whole segment (used for wind shadow rays). Candidates are tested
+    /// against the whole segment, so a hit on a piece boundary is not missed.
+    /// `candidates` and `dedup` are scratch buffers, reused across calls by ray batches.
+    pub fn segment_hits(
+        &self,
+        p1: Vec3,
+        p2: Vec3,
+        candidates: &mut Vec<usize>,
+        dedup: &mut FxHashSet<usize>,
+    ) -> bool {
+        // Pieces no longer than the bounds padding: a piece crossing the surface then has
+        // its center inside the padded bounds and is not skipped by `contains`
+        let piece = self.cell_size.min(BOUNDS_PADDING);
+        let pieces = (p1.distance(p2) / piece).ceil().max(1.0) as usize;
+
+        for k in 0..pieces {
+            let a = p1.lerp(p2, k as f32 / pieces as f32);
+            let b = p1.lerp(p2, (k + 1) as f32 / pieces as f32);
+            let center = (a + b) * 0.5;
+            if !self.lookup.contains(center) {
+                continue;
+            }
+            self.lookup.query(center, a.distance(b) * 0.5 + 1e-4, candidates, dedup);
+            if candidates
+                .iter()
+                .any(|&tri_idx| self.triangles[tri_idx].intersect_segment(p1, p2).is_some())
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Sweeps the segment `p1 -> p2` against the collider and returns the first hit as
+    /// `(point, normal, t)`, with the normal facing back towards `p1`.
+    /// Used to stop kinematically driven particles (e.g. a fast grab) tunnelling through.
+    pub fn sweep_segment(&self, p1: Vec3, p2: Vec3) -> Option<(Vec3, Vec3, f32)> {
+        let center = (p1 + p2) * 0.5;
+        let radius = p1.distance(p2) * 0.5 + 1e-4;
+
+        let mut candidates = Vec::new();
+        let mut dedup = FxHashSet::default();
+        self.lookup.query(center, radius, &mut candidates, &mut dedup);
+
+        let mut first_hit: Option<(Vec3, Vec3, f32)> = None;
+        for tri_idx in candidates {
+            if let Some((point, normal, t)) = self.triangles[tri_idx].intersect_segment(p1, p2)
+                && first_hit.is_none_or(|(_, _, best_t)| t < best_t)
+            {
+                first_hit = Some((point, normal, t));
+            }
         }
+        first_hit
     }
 }
@@ -1,5 +1,6 @@
 // physics/src/collision/exclusion.rs
 
+use crate::engine::topology::MeshTopology;
 use rustc_hash::FxHashSet;
 
 /// Precomputed topology exclusion masks for O(1) neighbor filtering.
@@ -16,27 +17,17 @@ pub struct TopologyExclusion {
 }
 
 impl TopologyExclusion {
-    /// Builds exclusion masks from mesh topology.
+    /// Builds exclusion masks from a raw triangle index buffer.
     /// `ring_depth` controls how many hops of neighbors to exclude.
     /// - 1 = immediate edge neighbors only
     /// - 2 = neighbors of neighbors (recommended for stability)
     pub fn new(indices: &[u32], particle_count: usize, ring_depth: usize) -> Self {
-        // Build adjacency list
-        let mut adjacency: Vec<FxHashSet<u32>> = vec![FxHashSet::default(); particle_count];
-        let num_triangles = indices.len() / 3;
-
-        for t in 0..num_triangles {
-            let i0 = indices[t * 3] as usize;
-            let i1 = indices[t * 3 + 1] as usize;
-            let i2 = indices[t * 3 + 2] as usize;
+        Self::from_topology(&MeshTopology::new(indices, particle_count), ring_depth)
+    }
 
-            adjacency[i0].insert(i1 as u32);
-            adjacency[i0].insert(i2 as u32);
-            adjacency[i1].insert(i0 as u32);
-            adjacency[i1].insert(i2 as u32);
-            adjacency[i2].insert(i0 as u32);
-            adjacency[i2].insert(i1 as u32);
-        }
+    /// Builds exclusion masks from a prebuilt (shared) mesh topology.
+    pub fn from_topology(topology: &MeshTopology, ring_depth: usize) -> Self {
+        let particle_count = topology.vertex_count();
 
         // BFS to find N-ring neighbors
         let mut masks = vec![0u64; particle_count];
@@ -50,7 +41,7 @@ impl TopologyExclusion {
             for _ring in 0..ring_depth {
                 let mut next_frontier = Vec::new();
                 for &node in &frontier {
-                    for &neighbor in &adjacency[node as usize] {
+                    for &neighbor in topology.neighbors(node as usize) {
                         if visited.insert(neighbor) {
                             next_frontier.push(neighbor);
                         }
@@ -16,6 +16,11 @@ impl Triangle {
         Self { v0, v1, v2, index }
     }
 
+    /// Unit face normal (authored winding); zero for a degenerate triangle.
+    pub fn normal(&self) -> Vec3 {
+        (self.v1 - self.v0).cross(self.v2 - self.v0).normalize_or_zero()
+    }
+
     /// Computes the Axis-Aligned Bounding Box (AABB) of the triangle.
     pub fn aabb(&self) -> (Vec3, Vec3) {
         let min = self.v0.min(self.v1).min(self.v2);
@@ -24,7 +29,9 @@ impl Triangle {
     }
 
     /// Computes the squared distance from a point `p` to the triangle's AABB.
-    /// Returns 0.0 if the point is inside the AABB.
+    /// Returns 0.0 if the point is inside the AABB (boundary included).
+    /// Never exceeds the squared distance to the triangle itself, so it is a safe
+    /// conservative test for skipping `closest_point` in the narrow phase.
     pub fn aabb_dist_sq(&self, p: Vec3) -> f32 {
         let (min, max) = self.aabb();
 
@@ -111,7 +118,7 @@ impl Triangle {
         let s = p1 - self.v0;
         let u = f * s.dot(h);
 
-        if u < 0.0 || u > 1.0 {
+        if !(0.0..=1.0).contains(&u) {
             return None;
         }
 
@@ -6,9 +6,11 @@ pub mod resolver;
 pub mod self_collision;
 pub mod exclusion;
 pub mod preprocessing;
+pub mod sdf;
 
 pub use collider::MeshCollider;
 pub use resolver::CollisionResolver;
+pub use sdf::SdfCollider;
 // TopologyExclusion is used internally by SelfCollision
 
 // FIX: Suppress warning since we aren't using this in the main loop yet
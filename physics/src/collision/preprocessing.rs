@@ -1,4 +1,5 @@
 // physics/src/collision/preprocessing.rs
+use crate::utils::normals::corner_normals;
 use glam::Vec3;
 
 /// A mesh that has been pre-processed for physics collision (smoothed, inflated, etc).
@@ -10,14 +11,30 @@ pub struct ProcessedMesh {
 /// Prepares a raw mesh for use as a collider.
 /// Steps:
 /// 1. Converts flat float buffers to Vec3.
-/// 2. Performs Laplacian Smoothing to remove sharp edges that snag cloth.
+/// 2. Performs Laplacian Smoothing to remove sharp edges that snag cloth
+///    (`smoothing_iterations` passes, each moving a vertex `smoothing_lambda` of the way
+///    to its neighbor average; 0 iterations skips it).
 /// 3. Computes Face Normals (flat shading style for accurate collisions).
 /// 4. Inflates the mesh to create a "safety buffer".
 pub fn process_mesh(
     raw_vertices: &[f32],
     indices: &[u32],
     smoothing_iterations: usize,
+    smoothing_lambda: f32,
     inflation_amount: f32
+) -> ProcessedMesh {
+    process_mesh_weighted(raw_vertices, indices, smoothing_iterations, smoothing_lambda, inflation_amount, false)
+}
+
+/// Same as `process_mesh`, with angle-weighted vertex normals when `angle_weighted` is set
+/// (see `utils::normals::corner_normals`).
+pub fn process_mesh_weighted(
+    raw_vertices: &[f32],
+    indices: &[u32],
+    smoothing_iterations: usize,
+    smoothing_lambda: f32,
+    inflation_amount: f32,
+    angle_weighted: bool,
 ) -> ProcessedMesh {
     let num_verts = raw_vertices.len() / 3;
     let mut vertices = Vec::with_capacity(num_verts);
@@ -46,7 +63,7 @@ pub fn process_mesh(
         }
 
         // 3. Laplacian Smoothing
-        let lambda = 0.5;
+        let lambda = smoothing_lambda;
         for _ in 0..smoothing_iterations {
             let old_verts = vertices.clone();
             for i in 0..num_verts {
@@ -61,29 +78,7 @@ pub fn process_mesh(
     }
 
     // 4. Compute Normals
-    let mut normals = vec![Vec3::ZERO; num_verts];
-    let num_triangles = indices.len() / 3;
-    for i in 0..num_triangles {
-        let idx0 = indices[i * 3] as usize;
-        let idx1 = indices[i * 3 + 1] as usize;
-        let idx2 = indices[i * 3 + 2] as usize;
-
-        let v0 = vertices[idx0];
-        let v1 = vertices[idx1];
-        let v2 = vertices[idx2];
-
-        let edge1 = v1 - v0;
-        let edge2 = v2 - v0;
-        let face_normal = edge1.cross(edge2);
-
-        normals[idx0] += face_normal;
-        normals[idx1] += face_normal;
-        normals[idx2] += face_normal;
-    }
-
-    for n in &mut normals {
-        *n = n.normalize_or_zero();
-    }
+    let normals = vertex_normals(&vertices, indices, angle_weighted);
 
     // 5. Inflation
     if inflation_amount.abs() > 1e-6 {
@@ -95,7 +90,28 @@ pub fn process_mesh(
     ProcessedMesh { vertices, normals }
 }
 
-fn add_neighbor(adj: &mut Vec<Vec<usize>>, a: usize, b: usize) {
+fn add_neighbor(adj: &mut [Vec<usize>], a: usize, b: usize) {
     if !adj[a].contains(&b) { adj[a].push(b); }
     if !adj[b].contains(&a) { adj[b].push(a); }
-}
\ No newline at end of file
+}
+
+/// Unit vertex normals of `vertices` (zero for vertices without a valid face).
+pub fn vertex_normals(vertices: &[Vec3], indices: &[u32], angle_weighted: bool) -> Vec<Vec3> {
+    let mut normals = vec![Vec3::ZERO; vertices.len()];
+    let num_triangles = indices.len() / 3;
+    for i in 0..num_triangles {
+        let idx0 = indices[i * 3] as usize;
+        let idx1 = indices[i * 3 + 1] as usize;
+        let idx2 = indices[i * 3 + 2] as usize;
+
+        let [n0, n1, n2] = corner_normals(vertices[idx0], vertices[idx1], vertices[idx2], angle_weighted);
+        normals[idx0] += n0;
+        normals[idx1] += n1;
+        normals[idx2] += n2;
+    }
+
+    for n in &mut normals {
+        *n = n.normalize_or_zero();
+    }
+    normals
+}
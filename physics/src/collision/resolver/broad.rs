@@ -1,116 +1,105 @@
 // physics/src/collision/resolver/broad.rs
-use super::CollisionResolver;
+use super::{CANDIDATES_PER_PARTICLE, CollisionResolver};
 use crate::collision::collider::MeshCollider;
+use crate::engine::config::PhysicsConfig;
 use crate::engine::state::PhysicsState;
+use crate::utils::profiler::{ProfileCounter, Profiler};
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
+use glam::Vec3;
 use rustc_hash::FxHashSet;
+#[cfg(feature = "parallel")]
+use smallvec::SmallVec;
+
+/// Particles queried per parallel batch before their candidates are capped and copied.
+#[cfg(feature = "parallel")]
+const PARALLEL_CHUNK: usize = 4096;
 
 /// Executes the Broad Phase of collision detection.
 /// Queries the Spatial Hash to find potential collision candidates (triangles close to particles).
 /// Populates the `candidate_indices` buffer in the resolver.
 ///
-/// OPTIMIZATION: Uses Rayon for parallel spatial hash queries.
+/// OPTIMIZATION: Uses Rayon for parallel spatial hash queries (a single query per particle).
 pub fn perform_broad_phase(
     resolver: &mut CollisionResolver,
     state: &PhysicsState,
     collider: &mut MeshCollider,
+    config: &PhysicsConfig,
+    substeps: usize,
 ) {
+    let margin = config.broad_phase_margin;
+    let limit = if config.max_candidates > 0 {
+        config.max_candidates
+    } else {
+        state.count * CANDIDATES_PER_PARTICLE
+    };
+    // Particles whose candidates did not fit under `limit`
+    let mut dropped = 0u64;
+
     // 1. Reset counters
     // resolver.candidate_indices.clear(); // We rewrite, so no clear needed if we resize strictly
     // Actually we need to ensure capacity.
 
     #[cfg(feature = "parallel")]
     {
-        // Step 1: Compute candidate counts in parallel
-        // We use a chunk-based approach or zip with indices
-        resolver
-            .candidate_counts
-            .par_iter_mut()
-            .enumerate()
-            .take(state.count)
-            .for_each(|(i, count_ref)| {
-                if state.inv_mass[i] == 0.0 {
-                    *count_ref = 0;
-                    return;
-                }
-
-                let pos = state.positions[i].truncate();
-                let prev = state.prev_positions[i].truncate();
-
-                // Optimized check: only query if moving or near mesh
-                if !collider.spatial_hash.contains(pos) && !collider.spatial_hash.contains(prev) {
-                    *count_ref = 0;
-                    return;
-                }
-
-                let search_radius = 0.02 + pos.distance(prev);
-
-                // Thread-local scratch buffers
-                // We waste some allocation here but SmallVec/stack might be too small for complex collision
-                let mut local_buffer = Vec::with_capacity(32);
-                let mut local_dedup = FxHashSet::default();
-
-                // Query (Read-only on spatial hash)
-                collider.spatial_hash.query(
-                    pos,
-                    search_radius,
-                    &mut local_buffer,
-                    &mut local_dedup,
-                );
-
-                *count_ref = local_buffer.len();
-            });
-
-        // Step 2: Prefix Sum (Scan) to compute offsets
-        // Serial is fast enough for 5000 particles
-        let mut current_offset = 0;
-        for i in 0..state.count {
-            resolver.candidate_offsets[i] = current_offset;
-            current_offset += resolver.candidate_counts[i];
-        }
-
-        // Resize indices buffer to fit all results
-        // Safety: We write to disjoint regions
-        resolver.candidate_indices.resize(current_offset, 0);
-
-        // Safety: We write to disjoint regions.
-        // Convert pointer to usize to allow capturing in closure (usize is Send+Sync)
-        let indices_ptr_addr = resolver.candidate_indices.as_mut_ptr() as usize;
-
-        // Capture read-only slices/references to avoid capturing &mut resolver/collider
-        let counts = &resolver.candidate_counts;
-        let offsets = &resolver.candidate_offsets;
-        let spatial_hash = &collider.spatial_hash;
-
-        // Step 3: Parallel Write (Scatter)
-        // We re-query to write directly to the target index.
-        // Double-querying is often faster than dynamic allocation + merge in parallel.
-        (0..state.count).into_par_iter().for_each(move |i| {
-            let count = counts[i];
-            if count == 0 {
-                return;
-            }
-
-            let pos = state.positions[i].truncate();
-            let prev = state.prev_positions[i].truncate();
-            let search_radius = 0.02 + pos.distance(prev);
-
-            let mut local_buffer = Vec::with_capacity(count);
-            let mut local_dedup = FxHashSet::default();
-
-            spatial_hash.query(pos, search_radius, &mut local_buffer, &mut local_dedup);
-
-            let offset = offsets[i];
-            let ptr = indices_ptr_addr as *mut usize;
-
-            // Unsafe write to pre-calculated disjoint offsets
-            for (j, &val) in local_buffer.iter().enumerate() {
-                unsafe {
-                    *ptr.add(offset + j) = val;
+        let lookup = &collider.lookup;
+        resolver.candidate_indices.clear();
+        for chunk_start in (0..state.count).step_by(PARALLEL_CHUNK) {
+            let chunk = chunk_start..(chunk_start + PARALLEL_CHUNK).min(state.count);
+            // HEAP GUARD: a particle with more candidates than the room left at the start
+            // of its chunk can never fit, so it is not copied out of the scratch buffer
+            let room = limit - resolver.candidate_indices.len();
+
+            // Step 1: Query every particle of the chunk once in parallel.
+            // Each rayon worker reuses its own scratch buffers (`map_init`), and the results
+            // land in per-particle SmallVecs, so only particles with many candidates allocate.
+            let per_particle: Vec<Option<SmallVec<[usize; 32]>>> = chunk
+                .clone()
+                .into_par_iter()
+                .map_init(
+                    || (Vec::with_capacity(64), FxHashSet::default()),
+                    |(buffer, dedup), i| {
+                        if state.inv_mass[i] == 0.0 {
+                            return Some(SmallVec::new());
+                        }
+
+                        let pos = state.positions[i].truncate();
+                        let prev = state.prev_positions[i].truncate();
+
+                        // Optimized check: only query if moving or near mesh
+                        if !lookup.contains(pos) && !lookup.contains(prev) {
+                            return Some(SmallVec::new());
+                        }
+
+                        let search_radius = search_radius(pos, prev, margin, substeps);
+                        lookup.query(pos, search_radius, buffer, dedup);
+                        if buffer.len() > room {
+                            return None;
+                        }
+                        Some(SmallVec::from_slice(buffer))
+                    },
+                )
+                .collect();
+
+            // Step 2: Serial prefix sum + copy into the flat candidate buffer.
+            // Replaces the old count-then-requery scatter, which ran every query twice.
+            // The cap is applied in particle order, exactly like the serial path, so both
+            // builds drop the same particles.
+            for (i, candidates) in chunk.zip(&per_particle) {
+                let start_idx = resolver.candidate_indices.len();
+                match candidates {
+                    Some(candidates) if start_idx + candidates.len() <= limit => {
+                        resolver.candidate_indices.extend_from_slice(candidates);
+                        resolver.candidate_offsets[i] = start_idx;
+                        resolver.candidate_counts[i] = candidates.len();
+                    }
+                    _ => {
+                        resolver.candidate_counts[i] = 0;
+                        dropped += 1;
+                    }
                 }
             }
-        });
+        }
     }
 
     #[cfg(not(feature = "parallel"))]
@@ -126,22 +115,22 @@ pub fn perform_broad_phase(
 
             let pos = state.positions[i].truncate();
             let prev = state.prev_positions[i].truncate();
-            let search_radius = 0.02 + pos.distance(prev);
+            let search_radius = search_radius(pos, prev, margin, substeps);
 
-            if !collider.spatial_hash.contains(pos) && !collider.spatial_hash.contains(prev) {
+            if !collider.lookup.contains(pos) && !collider.lookup.contains(prev) {
                 resolver.candidate_counts[i] = 0;
                 continue;
             }
 
             collider
-                .spatial_hash
+                .lookup
                 .query(pos, search_radius, &mut resolver.query_buffer, &mut dedup);
 
             let start_idx = resolver.candidate_indices.len();
             let query_len = resolver.query_buffer.len();
 
             // HEAP GUARD: Cap candidates
-            if start_idx + query_len < resolver.candidate_indices.capacity() {
+            if start_idx + query_len <= limit {
                 resolver
                     .candidate_indices
                     .extend_from_slice(&resolver.query_buffer);
@@ -149,7 +138,18 @@ pub fn perform_broad_phase(
                 resolver.candidate_counts[i] = query_len;
             } else {
                 resolver.candidate_counts[i] = 0;
+                dropped += 1;
             }
         }
     }
+
+    Profiler::set_counter(ProfileCounter::DroppedCandidates, dropped);
+}
+
+/// Query radius covering the particle's motion over the whole frame.
+/// The candidates are gathered once per frame but used by every substep, so the
+/// last substep's displacement (`pos - prev`) is extrapolated over all `substeps`.
+#[inline]
+fn search_radius(pos: Vec3, prev: Vec3, margin: f32, substeps: usize) -> f32 {
+    margin + pos.distance(prev) * substeps.max(1) as f32
 }
@@ -13,12 +13,30 @@ pub struct Contact {
     pub particle_index: usize,
     pub normal: Vec3,
     pub surface_point: Vec3,
+    /// Speed (m/s) at which the particle was moving into the surface when the contact
+    /// was found; 0 for a particle at rest or moving away.
+    pub approach_speed: f32,
+    /// Collider triangle the contact was found on (`None` for SDF contacts).
+    pub triangle: Option<usize>,
+    /// Friction multiplier of that triangle (`MeshCollider::friction_at`), applied to both
+    /// `static_friction` and `dynamic_friction`.
+    pub friction: f32,
 }
 
+/// Default broad-phase candidate budget per particle (see `PhysicsConfig::max_candidates`).
+pub const CANDIDATES_PER_PARTICLE: usize = 100;
+
 /// Manages collision detection and resolution.
 /// Stores contact constraints and solver buffers to avoid per-frame allocations.
 pub struct CollisionResolver {
     pub(crate) contacts: Vec<Contact>,
+    /// 1 for every particle with a contact in `contacts`, else 0 (see `update_contact_mask`).
+    pub(crate) contact_mask: Vec<u8>,
+
+    /// When true (default, e.g. a body), cloth always resolves to the positive normal side
+    /// and anything behind the surface is pushed back out. When false (e.g. a flag pole),
+    /// contacts resolve to whichever side the particle is on.
+    pub one_sided: bool,
 
     // Spatial Hashing Buffers
     #[allow(dead_code)]
@@ -30,9 +48,11 @@ pub struct CollisionResolver {
 
 impl CollisionResolver {
     pub fn new(particle_count: usize) -> Self {
-        let estimated_candidates = particle_count * 100;
+        let estimated_candidates = particle_count * CANDIDATES_PER_PARTICLE;
         Self {
             contacts: Vec::with_capacity(particle_count),
+            contact_mask: vec![0; particle_count],
+            one_sided: true,
             query_buffer: Vec::with_capacity(256),
             candidate_indices: Vec::with_capacity(estimated_candidates),
             candidate_offsets: vec![0; particle_count],
@@ -40,8 +60,57 @@ impl CollisionResolver {
         }
     }
 
-    pub fn broad_phase(&mut self, state: &PhysicsState, collider: &mut MeshCollider) {
-        broad::perform_broad_phase(self, state, collider);
+    /// Contacts found by the most recent narrow phase (plus any SDF contacts),
+    /// ordered by particle index within each collider.
+    pub fn contacts(&self) -> &[Contact] {
+        &self.contacts
+    }
+
+    /// Contact with the highest `approach_speed`, as `(particle, speed)`, ties going to the
+    /// lowest particle index. `None` when nothing is moving into the surface.
+    pub fn strongest_impact(&self) -> Option<(usize, f32)> {
+        let mut strongest: Option<(usize, f32)> = None;
+        for c in &self.contacts {
+            if c.approach_speed > 0.0
+                && strongest.is_none_or(|(i, speed)| {
+                    c.approach_speed > speed || (c.approach_speed == speed && c.particle_index < i)
+                })
+            {
+                strongest = Some((c.particle_index, c.approach_speed));
+            }
+        }
+        strongest
+    }
+
+    /// Per-particle contact flags (1 = touching the body), refreshed by `update_contact_mask`.
+    pub fn contact_mask(&self) -> &[u8] {
+        &self.contact_mask
+    }
+
+    /// Rasterizes `contacts` into `contact_mask`. Call once all colliders have added their contacts.
+    pub fn update_contact_mask(&mut self) {
+        self.contact_mask.fill(0);
+        for c in &self.contacts {
+            self.contact_mask[c.particle_index] = 1;
+        }
+    }
+
+    /// Candidate triangles found for `particle` by the most recent broad phase.
+    pub fn candidates(&self, particle: usize) -> &[usize] {
+        let offset = self.candidate_offsets[particle];
+        &self.candidate_indices[offset..offset + self.candidate_counts[particle]]
+    }
+
+    /// Gathers candidate triangles once per frame. `substeps` is the number of substeps
+    /// the candidates must stay valid for.
+    pub fn broad_phase(
+        &mut self,
+        state: &PhysicsState,
+        collider: &mut MeshCollider,
+        config: &PhysicsConfig,
+        substeps: usize,
+    ) {
+        broad::perform_broad_phase(self, state, collider, config, substeps);
     }
 
     pub fn narrow_phase(
@@ -60,14 +129,24 @@ impl CollisionResolver {
             let i = contact.particle_index;
             let pos_v4 = state.positions[i];
             let pos = pos_v4.truncate();
-            let normal = contact.normal;
             let surface_point = contact.surface_point;
 
+            // Two-sided: flip to the side the particle started the substep on, so a
+            // particle crossing mid-step is still pushed back rather than through
+            let normal = if !self.one_sided
+                && (state.prev_positions[i].truncate() - surface_point).dot(contact.normal) < 0.0
+            {
+                -contact.normal
+            } else {
+                contact.normal
+            };
+
             let vec = pos - surface_point;
             let projection = vec.dot(normal);
 
-            if projection < config.contact_thickness {
-                let penetration = config.contact_thickness - projection;
+            let contact_distance = config.body_contact_distance();
+            if projection < contact_distance {
+                let penetration = contact_distance - projection;
                 let stiffness = if projection < 0.0 {
                     1.0
                 } else {
@@ -75,7 +154,11 @@ impl CollisionResolver {
                 };
 
                 // FIX: No omega multiplication here.
-                let correction = normal * (penetration * stiffness);
+                let mut depth = penetration * stiffness;
+                if config.max_collision_correction > 0.0 {
+                    depth = depth.min(config.max_collision_correction);
+                }
+                let correction = normal * depth;
                 state.positions[i] += Vec4::from((correction, 0.0));
 
                 let prev_v4 = state.prev_positions[i];
@@ -90,10 +173,10 @@ impl CollisionResolver {
 
                 let mut friction_factor = 0.0;
                 if vt_len > 1e-9 {
-                    if vt_len < penetration * config.static_friction {
+                    if vt_len < penetration * config.static_friction * contact.friction {
                         friction_factor = 1.0;
                     } else {
-                        let max_slide = penetration * config.dynamic_friction;
+                        let max_slide = penetration * config.dynamic_friction * contact.friction;
                         friction_factor = max_slide / vt_len;
                         if friction_factor > 1.0 {
                             friction_factor = 1.0;
@@ -22,7 +22,7 @@ pub fn perform_narrow_phase(
 
     let max_v_per_step = config.contact_thickness * 0.9;
     let max_v = max_v_per_step / dt;
-    let discrete_radius = 0.05;
+    let discrete_radius = config.discrete_contact_radius(collider.inflation_amount);
 
     // Parallelize logic:
     // We cannot write to `state.prev_positions` and `resolver.contacts` concurrently easily.
@@ -57,7 +57,7 @@ pub fn perform_narrow_phase(
                 let pos = pos_v4.truncate();
                 let prev = prev_v4.truncate();
 
-                let mut best_contact: Option<(Vec3, Vec3, f32)> = None;
+                let mut best_contact: Option<(Vec3, Vec3, f32, usize)> = None;
                 let mut min_metric = f32::MAX;
                 let mut is_continuous = false;
 
@@ -66,17 +66,17 @@ pub fn perform_narrow_phase(
                     let tri = &collider.triangles[tri_idx];
 
                     // 1. Continuous Check
-                    if let Some((hit_point, hit_normal, t)) = tri.intersect_segment(prev, pos) {
-                        if t < min_metric {
-                            let normal = if hit_normal.dot(pos - prev) < 0.0 {
-                                hit_normal
-                            } else {
-                                -hit_normal
-                            };
-                            best_contact = Some((hit_point, normal, t));
-                            min_metric = t;
-                            is_continuous = true;
-                        }
+                    if let Some((hit_point, hit_normal, t)) = tri.intersect_segment(prev, pos)
+                        && t < min_metric
+                    {
+                        let normal = if hit_normal.dot(pos - prev) < 0.0 {
+                            hit_normal
+                        } else {
+                            -hit_normal
+                        };
+                        best_contact = Some((hit_point, normal, t, tri_idx));
+                        min_metric = t;
+                        is_continuous = true;
                     }
 
                     // 2. Discrete Check
@@ -86,22 +86,14 @@ pub fn perform_narrow_phase(
                             let (closest, _bary) = tri.closest_point(pos);
                             let dist_sq = closest.distance_squared(pos);
 
-                            if dist_sq < discrete_radius * discrete_radius {
-                                if dist_sq < min_metric {
-                                    let idx0 = collider.indices[tri_idx * 3] as usize;
-                                    let idx1 = collider.indices[tri_idx * 3 + 1] as usize;
-                                    let idx2 = collider.indices[tri_idx * 3 + 2] as usize;
-                                    let n0 = collider.normals[idx0];
-                                    let n1 = collider.normals[idx1];
-                                    let n2 = collider.normals[idx2];
-
-                                    let (_, bary) = tri.closest_point(pos);
-                                    let smooth_normal =
-                                        (n0 * bary[0] + n1 * bary[1] + n2 * bary[2]).normalize();
-
-                                    best_contact = Some((closest, smooth_normal, dist_sq));
-                                    min_metric = dist_sq;
-                                }
+                            if dist_sq < discrete_radius * discrete_radius
+                                && dist_sq < min_metric
+                            {
+                                let (_, bary) = tri.closest_point(pos);
+                                let smooth_normal = smooth_normal(collider, tri_idx, bary);
+
+                                best_contact = Some((closest, smooth_normal, dist_sq, tri_idx));
+                                min_metric = dist_sq;
                             }
                         }
                     }
@@ -110,7 +102,7 @@ pub fn perform_narrow_phase(
                 let mut correction = None;
                 let mut contact = None;
 
-                if let Some((surface_point, normal, _metric)) = best_contact {
+                if let Some((surface_point, normal, _metric, tri_idx)) = best_contact {
                     // Velocity Clamping (Airbag)
                     let velocity = (pos - prev) / dt;
                     let v_normal = velocity.dot(normal);
@@ -132,6 +124,9 @@ pub fn perform_narrow_phase(
                         particle_index: i,
                         normal,
                         surface_point,
+                        approach_speed: (-v_normal).max(0.0),
+                        triangle: Some(tri_idx),
+                        friction: collider.friction_at(tri_idx),
                     });
                 }
 
@@ -170,7 +165,7 @@ pub fn perform_narrow_phase(
             let pos = pos_v4.truncate();
             let prev = prev_v4.truncate();
 
-            let mut best_contact: Option<(Vec3, Vec3, f32)> = None;
+            let mut best_contact: Option<(Vec3, Vec3, f32, usize)> = None;
             let mut min_metric = f32::MAX;
             let mut is_continuous = false;
 
@@ -179,17 +174,17 @@ pub fn perform_narrow_phase(
                 let tri = &collider.triangles[tri_idx];
 
                 // 1. Continuous Check
-                if let Some((hit_point, hit_normal, t)) = tri.intersect_segment(prev, pos) {
-                    if t < min_metric {
-                        let normal = if hit_normal.dot(pos - prev) < 0.0 {
-                            hit_normal
-                        } else {
-                            -hit_normal
-                        };
-                        best_contact = Some((hit_point, normal, t));
-                        min_metric = t;
-                        is_continuous = true;
-                    }
+                if let Some((hit_point, hit_normal, t)) = tri.intersect_segment(prev, pos)
+                    && t < min_metric
+                {
+                    let normal = if hit_normal.dot(pos - prev) < 0.0 {
+                        hit_normal
+                    } else {
+                        -hit_normal
+                    };
+                    best_contact = Some((hit_point, normal, t, tri_idx));
+                    min_metric = t;
+                    is_continuous = true;
                 }
 
                 // 2. Discrete Check
@@ -200,28 +195,20 @@ pub fn perform_narrow_phase(
                         let (closest, _bary) = tri.closest_point(pos);
                         let dist_sq = closest.distance_squared(pos);
 
-                        if dist_sq < discrete_radius * discrete_radius {
-                            if dist_sq < min_metric {
-                                let idx0 = collider.indices[tri_idx * 3] as usize;
-                                let idx1 = collider.indices[tri_idx * 3 + 1] as usize;
-                                let idx2 = collider.indices[tri_idx * 3 + 2] as usize;
-                                let n0 = collider.normals[idx0];
-                                let n1 = collider.normals[idx1];
-                                let n2 = collider.normals[idx2];
+                        if dist_sq < discrete_radius * discrete_radius
+                            && dist_sq < min_metric
+                        {
+                            let (_, bary) = tri.closest_point(pos);
+                            let smooth_normal = smooth_normal(collider, tri_idx, bary);
 
-                                let (_, bary) = tri.closest_point(pos);
-                                let smooth_normal =
-                                    (n0 * bary[0] + n1 * bary[1] + n2 * bary[2]).normalize();
-
-                                best_contact = Some((closest, smooth_normal, dist_sq));
-                                min_metric = dist_sq;
-                            }
+                            best_contact = Some((closest, smooth_normal, dist_sq, tri_idx));
+                            min_metric = dist_sq;
                         }
                     }
                 }
             }
 
-            if let Some((surface_point, normal, _metric)) = best_contact {
+            if let Some((surface_point, normal, _metric, tri_idx)) = best_contact {
                 // Velocity Clamping (Airbag)
                 let velocity = (pos - prev) / dt;
                 let v_normal = velocity.dot(normal);
@@ -240,8 +227,29 @@ pub fn perform_narrow_phase(
                     particle_index: i,
                     normal,
                     surface_point,
+                    approach_speed: (-v_normal).max(0.0),
+                    triangle: Some(tri_idx),
+                    friction: collider.friction_at(tri_idx),
                 });
             }
         }
     }
 }
+
+/// Collider normal at `bary` on triangle `tri_idx`, interpolated from the vertex normals.
+/// Falls back to the face normal where the vertex normals cancel out (e.g. the two sides
+/// of a thin flap), which would otherwise normalize to NaN and poison the corrections.
+#[inline]
+fn smooth_normal(collider: &MeshCollider, tri_idx: usize, bary: [f32; 3]) -> Vec3 {
+    let n0 = collider.normals[collider.indices[tri_idx * 3] as usize];
+    let n1 = collider.normals[collider.indices[tri_idx * 3 + 1] as usize];
+    let n2 = collider.normals[collider.indices[tri_idx * 3 + 2] as usize];
+
+    let n = n0 * bary[0] + n1 * bary[1] + n2 * bary[2];
+    let len_sq = n.length_squared();
+    if len_sq > 1e-12 {
+        n / len_sq.sqrt()
+    } else {
+        collider.triangles[tri_idx].normal()
+    }
+}
@@ -0,0 +1,188 @@
+// physics/src/collision/sdf.rs
+use super::resolver::Contact;
+use crate::engine::config::PhysicsConfig;
+use crate::engine::state::PhysicsState;
+use glam::Vec3;
+
+/// A collider backed by a precomputed Signed Distance Field.
+/// The field is sampled on a regular 3D grid spanning `bounds_min..bounds_max`
+/// (negative inside the body, positive outside).
+///
+/// OPTIMIZATION: Queries are O(1) (one trilinear lookup per particle) regardless of
+/// the body's triangle count, trading memory for speed on detailed avatars.
+pub struct SdfCollider {
+    /// Distance samples, X-major: `data[x + y * nx + z * nx * ny]`.
+    pub data: Vec<f32>,
+    pub dims: [usize; 3],
+    pub bounds_min: Vec3,
+    pub bounds_max: Vec3,
+    /// Spacing between grid samples along each axis.
+    cell_size: Vec3,
+}
+
+impl SdfCollider {
+    /// Panics on an invalid grid; see `try_new` for untrusted input.
+    pub fn new(data: Vec<f32>, dims: [usize; 3], bounds_min: Vec3, bounds_max: Vec3) -> Self {
+        Self::try_new(data, dims, bounds_min, bounds_max).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Same as `new`, but reports an invalid grid (fewer than 2 samples per axis,
+    /// `data` length not matching `dims`, empty or non-finite bounds) as an error.
+    pub fn try_new(data: Vec<f32>, dims: [usize; 3], bounds_min: Vec3, bounds_max: Vec3) -> Result<Self, String> {
+        if dims.iter().any(|&d| d < 2) {
+            return Err("SDF grid needs at least 2 samples per axis".to_string());
+        }
+        let expected = dims[0].checked_mul(dims[1]).and_then(|n| n.checked_mul(dims[2]));
+        if expected != Some(data.len()) {
+            return Err(format!(
+                "SDF data length {} does not match grid dimensions {}x{}x{}",
+                data.len(),
+                dims[0],
+                dims[1],
+                dims[2]
+            ));
+        }
+        if !bounds_min.is_finite() || !bounds_max.is_finite() || bounds_min.cmpge(bounds_max).any() {
+            return Err("SDF bounds must be finite with min < max on every axis".to_string());
+        }
+
+        let steps = Vec3::new(
+            (dims[0] - 1) as f32,
+            (dims[1] - 1) as f32,
+            (dims[2] - 1) as f32,
+        );
+        let cell_size = (bounds_max - bounds_min) / steps;
+
+        Ok(Self {
+            data,
+            dims,
+            bounds_min,
+            bounds_max,
+            cell_size,
+        })
+    }
+
+    #[inline(always)]
+    fn at(&self, x: usize, y: usize, z: usize) -> f32 {
+        self.data[x + y * self.dims[0] + z * self.dims[0] * self.dims[1]]
+    }
+
+    /// Samples the field at `p` using trilinear interpolation.
+    /// Returns the signed distance and its analytic gradient (un-normalized),
+    /// or `None` if `p` lies outside the grid bounds.
+    pub fn sample(&self, p: Vec3) -> Option<(f32, Vec3)> {
+        if p.cmplt(self.bounds_min).any() || p.cmpgt(self.bounds_max).any() {
+            return None;
+        }
+
+        let local = (p - self.bounds_min) / self.cell_size;
+
+        // Clamp the base cell so points on the max face still have a valid +1 neighbour
+        let x0 = (local.x as usize).min(self.dims[0] - 2);
+        let y0 = (local.y as usize).min(self.dims[1] - 2);
+        let z0 = (local.z as usize).min(self.dims[2] - 2);
+
+        let tx = local.x - x0 as f32;
+        let ty = local.y - y0 as f32;
+        let tz = local.z - z0 as f32;
+
+        let c000 = self.at(x0, y0, z0);
+        let c100 = self.at(x0 + 1, y0, z0);
+        let c010 = self.at(x0, y0 + 1, z0);
+        let c110 = self.at(x0 + 1, y0 + 1, z0);
+        let c001 = self.at(x0, y0, z0 + 1);
+        let c101 = self.at(x0 + 1, y0, z0 + 1);
+        let c011 = self.at(x0, y0 + 1, z0 + 1);
+        let c111 = self.at(x0 + 1, y0 + 1, z0 + 1);
+
+        // Interpolate along X
+        let c00 = c000 + (c100 - c000) * tx;
+        let c10 = c010 + (c110 - c010) * tx;
+        let c01 = c001 + (c101 - c001) * tx;
+        let c11 = c011 + (c111 - c011) * tx;
+
+        // Interpolate along Y
+        let c0 = c00 + (c10 - c00) * ty;
+        let c1 = c01 + (c11 - c01) * ty;
+
+        // Interpolate along Z
+        let distance = c0 + (c1 - c0) * tz;
+
+        // Analytic derivatives of the trilinear interpolant (in grid units)
+        let dx0 = (c100 - c000) + ((c110 - c010) - (c100 - c000)) * ty;
+        let dx1 = (c101 - c001) + ((c111 - c011) - (c101 - c001)) * ty;
+        let dx = dx0 + (dx1 - dx0) * tz;
+
+        let dy0 = c10 - c00;
+        let dy1 = c11 - c01;
+        let dy = dy0 + (dy1 - dy0) * tz;
+
+        let dz = c1 - c0;
+
+        let gradient = Vec3::new(dx, dy, dz) / self.cell_size;
+
+        Some((distance, gradient))
+    }
+
+    /// Generates contacts for every particle within `search_radius` of the zero level set.
+    /// `contacts` may already hold the mesh contacts (at most one per particle, ordered by
+    /// particle index); a particle touching both colliders keeps only the deeper contact,
+    /// other SDF contacts are appended.
+    /// `dt` is the substep length, used to turn the last displacement into an approach speed.
+    pub fn collect_contacts(
+        &self,
+        state: &PhysicsState,
+        config: &PhysicsConfig,
+        dt: f32,
+        contacts: &mut Vec<Contact>,
+    ) {
+        let search_radius = config.body_contact_distance() * 2.0;
+        let existing = contacts.len();
+        let mut cursor = 0;
+
+        for i in 0..state.count {
+            if state.inv_mass[i] == 0.0 {
+                continue;
+            }
+
+            let pos = state.positions[i].truncate();
+            let Some((distance, gradient)) = self.sample(pos) else {
+                continue;
+            };
+
+            if distance > search_radius {
+                continue;
+            }
+
+            // Degenerate gradient (e.g. at the medial axis) gives no usable direction
+            let grad_len_sq = gradient.length_squared();
+            if grad_len_sq < 1e-12 {
+                continue;
+            }
+
+            let normal = gradient / grad_len_sq.sqrt();
+            let v_normal = (pos - state.prev_positions[i].truncate()).dot(normal) / dt;
+
+            let contact = Contact {
+                particle_index: i,
+                normal,
+                surface_point: pos - normal * distance,
+                approach_speed: (-v_normal).max(0.0),
+                triangle: None,
+                friction: 1.0,
+            };
+
+            while cursor < existing && contacts[cursor].particle_index < i {
+                cursor += 1;
+            }
+            if cursor < existing && contacts[cursor].particle_index == i {
+                let mesh = &contacts[cursor];
+                if distance < (pos - mesh.surface_point).dot(mesh.normal) {
+                    contacts[cursor] = contact;
+                }
+            } else {
+                contacts.push(contact);
+            }
+        }
+    }
+}
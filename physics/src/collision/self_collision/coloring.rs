@@ -47,18 +47,18 @@ impl SelfCollision {
 
             // Check neighbors of particle i
             for &c_idx in &adj[offset[i]..offset[i + 1]] {
-                if let Some(c) = pair_colors[c_idx] {
-                    if c < 64 {
-                        used_colors |= 1u64 << c;
-                    }
+                if let Some(c) = pair_colors[c_idx]
+                    && c < 64
+                {
+                    used_colors |= 1u64 << c;
                 }
             }
             // Check neighbors of particle j
             for &c_idx in &adj[offset[j]..offset[j + 1]] {
-                if let Some(c) = pair_colors[c_idx] {
-                    if c < 64 {
-                        used_colors |= 1u64 << c;
-                    }
+                if let Some(c) = pair_colors[c_idx]
+                    && c < 64
+                {
+                    used_colors |= 1u64 << c;
                 }
             }
 
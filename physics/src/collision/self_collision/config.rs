@@ -2,17 +2,65 @@
 
 //! Configuration and data types for self-collision detection.
 
+use glam::Vec3;
+
+/// Number of distinct self-collision layers (one bit each in a layer mask).
+pub const MAX_LAYERS: usize = 32;
+
+/// Which primitives self-collision tests against each other.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelfCollisionMode {
+    /// Particle vs. particle spheres (fast, but a vertex can slip through
+    /// the middle of a large triangle on coarse meshes).
+    #[default]
+    PointPoint,
+    /// Particle vs. nearby cloth triangles (catches thin-region pass-through).
+    VertexTriangle,
+}
+
+/// Axis-aligned box (world space, meters), e.g. the skirt band of a dress.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min: min.min(max), max: min.max(max) }
+    }
+
+    #[inline(always)]
+    pub fn contains(&self, p: Vec3) -> bool {
+        p.cmpge(self.min).all() && p.cmple(self.max).all()
+    }
+
+    #[inline(always)]
+    pub fn overlaps(&self, min: Vec3, max: Vec3) -> bool {
+        max.cmpge(self.min).all() && min.cmple(self.max).all()
+    }
+}
+
 /// Configuration for self-collision behavior.
 /// Allows runtime tuning of quality vs. performance trade-off.
+#[derive(Clone, Copy)]
 pub struct SelfCollisionConfig {
     /// Minimum separation distance between particles (default: 0.005 = 5mm)
     pub thickness: f32,
     /// Repulsion strength (0.0 - 1.0, default: 0.5)
     pub stiffness: f32,
-    /// Solve every N substeps (1 = every substep, 2 = every other, etc.)
+    /// Solve every N substeps (1 = every substep, 2 = every other, etc.; 0 acts as 1).
+    /// Read each substep, so it can be changed while the simulation runs.
     pub frequency: u8,
     /// Maximum collision pairs to process per frame (performance cap)
     pub max_pairs: usize,
+    /// Primitive pairing used for detection/resolution
+    pub mode: SelfCollisionMode,
+    /// Bit `b` of `layer_masks[a]` is set if layer `a` collides with layer `b` (default: all)
+    pub layer_masks: [u32; MAX_LAYERS],
+    /// Only particles inside this box take part in self-collision (`None` = whole mesh).
+    /// Particles outside are left out of the spatial hash, so they cost nothing.
+    pub region: Option<Aabb>,
 }
 
 impl Default for SelfCollisionConfig {
@@ -22,13 +70,47 @@ impl Default for SelfCollisionConfig {
             stiffness: 0.5,
             frequency: 2, // Every other substep
             max_pairs: 10000,
+            mode: SelfCollisionMode::PointPoint,
+            layer_masks: [u32::MAX; MAX_LAYERS],
+            region: None,
         }
     }
 }
 
+impl SelfCollisionConfig {
+    /// Enables or disables collision between layers `a` and `b` (symmetric).
+    /// Layers at or above `MAX_LAYERS` are treated as the last layer.
+    pub fn set_layers_interact(&mut self, a: u8, b: u8, interact: bool) {
+        let a = (a as usize).min(MAX_LAYERS - 1);
+        let b = (b as usize).min(MAX_LAYERS - 1);
+        if interact {
+            self.layer_masks[a] |= 1 << b;
+            self.layer_masks[b] |= 1 << a;
+        } else {
+            self.layer_masks[a] &= !(1 << b);
+            self.layer_masks[b] &= !(1 << a);
+        }
+    }
+
+    /// Returns true if particles on layers `a` and `b` should collide.
+    #[inline(always)]
+    pub fn layers_interact(&self, a: u8, b: u8) -> bool {
+        let a = (a as usize).min(MAX_LAYERS - 1);
+        let b = (b as usize).min(MAX_LAYERS - 1);
+        self.layer_masks[a] & (1 << b) != 0
+    }
+}
+
 /// A collision pair between two particles.
 #[derive(Clone, Copy)]
 pub(crate) struct CollisionPair {
     pub i: u32,
     pub j: u32,
 }
+
+/// A particle within `thickness` of a (non-adjacent) cloth triangle.
+#[derive(Clone, Copy)]
+pub(crate) struct VertexTrianglePair {
+    pub p: u32,
+    pub t: u32,
+}
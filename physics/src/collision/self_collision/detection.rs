@@ -10,82 +10,103 @@ use rayon::prelude::*;
 use rustc_hash::FxHashSet;
 
 impl SelfCollision {
+    /// Layer + topology filter for a candidate pair. Topology exclusion only applies
+    /// within a layer, so separate garments sharing vertices still collide.
+    #[inline(always)]
+    pub(crate) fn should_test(&self, state: &PhysicsState, i: usize, j: usize) -> bool {
+        let (li, lj) = (state.layers[i], state.layers[j]);
+        if !self.config.layers_interact(li, lj) {
+            return false;
+        }
+        li != lj || !self.exclusion.should_exclude(i, j)
+    }
+
+    /// Syncs the particle hash with current positions (Serial - O(N), but only
+    /// particles that crossed a cell boundary touch the grids).
+    /// Particles outside `config.region` are kept out of the hash.
+    pub(crate) fn update_hash(&mut self, state: &PhysicsState) {
+        for i in 0..state.count {
+            let p = state.positions[i].truncate();
+            if self.config.region.is_none_or(|region| region.contains(p)) {
+                self.hash.update_point(i as u32, p);
+            } else {
+                self.hash.remove_point(i as u32);
+            }
+        }
+    }
+
     /// Phase 1: Detect all collision pairs (read-only on positions).
     /// Returns true if there are pairs to resolve.
     pub(crate) fn detect_pairs(&mut self, state: &PhysicsState) -> bool {
         self.collision_pairs.clear();
 
-        // 1. Rebuild hash with current positions (Serial - fast O(N))
-        self.hash.clear();
-        for i in 0..state.count {
-            self.hash
-                .insert_point(i as u32, state.positions[i].truncate());
-        }
+        // 1. Update hash with current positions
+        self.update_hash(state);
 
         let thickness = self.config.thickness;
         let thickness_sq = thickness * thickness;
         let max_pairs = self.config.max_pairs;
 
-        // 2. Detect pairs (Parallel Query)
+        // 2. Detect pairs (Parallel Query), each with its squared distance
         #[cfg(feature = "parallel")]
-        {
-            let results: Vec<CollisionPair> = (0..state.count)
-                .into_par_iter()
-                .map(|i| {
-                    let p_i = state.positions[i].truncate();
-
-                    // Thread-local scratch buffers
-                    let mut query_buffer = Vec::with_capacity(32);
-                    let mut dedup_set = FxHashSet::default();
-
-                    self.hash
-                        .query(p_i, thickness, &mut query_buffer, &mut dedup_set);
-
-                    let mut local_pairs = Vec::new();
-
-                    for &j in query_buffer.iter() {
-                        let j = j as usize;
-
-                        // Only process once per pair (i < j)
-                        if i >= j {
-                            continue;
-                        }
-                        if self.exclusion.should_exclude(i, j) {
-                            continue;
-                        }
-
-                        let p_j = state.positions[j].truncate();
-                        let delta = p_i - p_j;
-                        let dist_sq = delta.length_squared();
-
-                        if dist_sq < thickness_sq && dist_sq > 1e-9 {
-                            local_pairs.push(CollisionPair {
+        let results: Vec<(f32, CollisionPair)> = (0..state.count)
+            .into_par_iter()
+            .map(|i| {
+                let p_i = state.positions[i].truncate();
+                if !self.config.region.is_none_or(|region| region.contains(p_i)) {
+                    return Vec::new();
+                }
+
+                // Thread-local scratch buffers
+                let mut query_buffer = Vec::with_capacity(32);
+                let mut dedup_set = FxHashSet::default();
+
+                self.hash
+                    .query(p_i, thickness, &mut query_buffer, &mut dedup_set);
+
+                let mut local_pairs = Vec::new();
+
+                for &j in query_buffer.iter() {
+                    let j = j as usize;
+
+                    // Only process once per pair (i < j)
+                    if i >= j {
+                        continue;
+                    }
+                    if !self.should_test(state, i, j) {
+                        continue;
+                    }
+
+                    let p_j = state.positions[j].truncate();
+                    let delta = p_i - p_j;
+                    let dist_sq = delta.length_squared();
+
+                    if dist_sq < thickness_sq && dist_sq > 1e-9 {
+                        local_pairs.push((
+                            dist_sq,
+                            CollisionPair {
                                 i: i as u32,
                                 j: j as u32,
-                            });
-                        }
+                            },
+                        ));
                     }
-                    local_pairs
-                })
-                .flatten() // Flatten thread results
-                .collect();
-
-            // Cap results if needed (though hard to cap strictly during parallel)
-            if results.len() > max_pairs {
-                self.collision_pairs
-                    .extend_from_slice(&results[..max_pairs]);
-            } else {
-                self.collision_pairs = results;
-            }
-        }
+                }
+                local_pairs
+            })
+            .flatten() // Flatten thread results
+            .collect();
 
         #[cfg(not(feature = "parallel"))]
-        {
+        let results: Vec<(f32, CollisionPair)> = {
             // Re-use member buffer for serial
             let mut dedup_set = FxHashSet::default();
+            let mut results = Vec::new();
 
             for i in 0..state.count {
                 let p_i = state.positions[i].truncate();
+                if !self.config.region.is_none_or(|region| region.contains(p_i)) {
+                    continue;
+                }
                 // We can temporarily borrow query_buffer if we are careful, but the struct has it.
                 // But wait, the function takes &mut self, so we can't borrow self.hash (immutable) and self.query_buffer (mutable) easily?
                 // self.hash.query takes &self.
@@ -103,7 +124,7 @@ impl SelfCollision {
                     if i >= j {
                         continue;
                     }
-                    if self.exclusion.should_exclude(i, j) {
+                    if !self.should_test(state, i, j) {
                         continue;
                     }
 
@@ -112,24 +133,46 @@ impl SelfCollision {
                     let dist_sq = delta.length_squared();
 
                     if dist_sq < thickness_sq && dist_sq > 1e-9 {
-                        self.collision_pairs.push(CollisionPair {
-                            i: i as u32,
-                            j: j as u32,
-                        });
-
-                        // Cap pairs for performance
-                        if self.collision_pairs.len() >= max_pairs {
-                            break;
-                        }
+                        results.push((dist_sq, CollisionPair { i: i as u32, j: j as u32 }));
                     }
                 }
-
-                if self.collision_pairs.len() >= max_pairs {
-                    break;
-                }
             }
-        }
+            results
+        };
+
+        // 3. Cap pairs for performance, keeping the deepest penetrations
+        self.collision_pairs = keep_deepest(results, max_pairs, |p| (p.i, p.j));
+
+        // A pair seen twice would be resolved twice per pass (double stiffness, popping)
+        debug_assert!(
+            self.collision_pairs
+                .windows(2)
+                .all(|w| (w[0].i, w[0].j) < (w[1].i, w[1].j)),
+            "Self-collision pairs must be sorted and unique"
+        );
 
         !self.collision_pairs.is_empty()
     }
 }
+
+/// Caps `pairs` (each tagged with its squared distance) at `max_pairs`, keeping the
+/// closest (most overlapping) ones so the worst penetrations are always resolved,
+/// wherever they are on the mesh. Ties break on `key`, and the kept pairs are returned
+/// sorted by `key`, so the result never depends on thread scheduling.
+pub(super) fn keep_deepest<T, K: Ord>(
+    mut pairs: Vec<(f32, T)>,
+    max_pairs: usize,
+    key: impl Fn(&T) -> K,
+) -> Vec<T> {
+    if pairs.len() > max_pairs {
+        if max_pairs == 0 {
+            return Vec::new();
+        }
+        pairs.select_nth_unstable_by(max_pairs - 1, |a, b| {
+            a.0.total_cmp(&b.0).then_with(|| key(&a.1).cmp(&key(&b.1)))
+        });
+        pairs.truncate(max_pairs);
+    }
+    pairs.sort_unstable_by_key(|(_, p)| key(p));
+    pairs.into_iter().map(|(_, p)| p).collect()
+}
@@ -10,14 +10,21 @@ mod coloring;
 mod config;
 mod detection;
 mod resolution;
+mod vertex_triangle;
 
-use config::CollisionPair;
-pub use config::SelfCollisionConfig;
+use config::{CollisionPair, VertexTrianglePair};
+pub use config::{Aabb, MAX_LAYERS, SelfCollisionConfig, SelfCollisionMode};
 
 use super::exclusion::TopologyExclusion;
 use super::spatial::HierarchicalSpatialHash;
 use crate::engine::state::PhysicsState;
-use crate::utils::profiler::{ProfileCategory, Profiler};
+use crate::utils::profiler::{ProfileCategory, ProfileCounter, Profiler};
+use glam::Vec3;
+use rustc_hash::FxHashSet;
+
+/// Radius doublings (starting at one hash cell) `nearest_particle` tries before giving up.
+/// The last step scans 17^3 fine cells; farther queries are cheaper as a linear scan.
+const NEAREST_SEARCH_STEPS: usize = 4;
 
 /// Handles cloth-on-cloth collision detection and resolution.
 /// Uses hierarchical spatial hashing with Morton codes for efficient broad-phase,
@@ -30,6 +37,8 @@ pub struct SelfCollision {
     pub config: SelfCollisionConfig,
     /// Detected collision pairs (phase 1 output)
     pub(crate) collision_pairs: Vec<CollisionPair>,
+    /// Detected vertex-triangle pairs (`SelfCollisionMode::VertexTriangle` only)
+    pub(crate) vt_pairs: Vec<VertexTrianglePair>,
     /// Batch offsets for graph-colored pairs
     pub(crate) batch_offsets: Vec<usize>,
     /// Particle count for coloring
@@ -40,7 +49,7 @@ impl SelfCollision {
     /// Creates a new self-collision system from mesh topology.
     pub fn new(state: &PhysicsState, config: SelfCollisionConfig) -> Self {
         // Build topology exclusion with 2-ring depth (recommended for stability)
-        let exclusion = TopologyExclusion::new(&state.indices, state.count, 2);
+        let exclusion = TopologyExclusion::from_topology(&state.topology, 2);
 
         // Initialize hierarchical hash with collision radius
         let hash = HierarchicalSpatialHash::new(config.thickness);
@@ -50,11 +59,67 @@ impl SelfCollision {
             exclusion,
             config,
             collision_pairs: Vec::with_capacity(1000),
+            vt_pairs: Vec::new(),
             batch_offsets: Vec::new(),
             particle_count: state.count,
         }
     }
 
+    /// Changes the separation distance at runtime.
+    /// The hash cell size is derived from the thickness, so the hash is re-gridded too.
+    pub fn set_thickness(&mut self, thickness: f32) {
+        self.config.thickness = thickness;
+        self.hash.set_collision_radius(thickness);
+    }
+
+    /// Number of particle pairs found by the last detection pass.
+    pub fn pair_count(&self) -> usize {
+        self.collision_pairs.len()
+    }
+
+    /// Particle pairs `(i, j)` found by the last detection pass, each emitted once
+    /// with `i < j`, in resolution (color batch) order.
+    pub fn pairs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.collision_pairs.iter().map(|p| (p.i as usize, p.j as usize))
+    }
+
+    /// Current hash cell size (tracks `config.thickness`).
+    pub fn cell_size(&self) -> f32 {
+        self.hash.fine_cell_size()
+    }
+
+    /// Closest particle to `p`, searched in the hash with a growing radius.
+    /// The hash holds the positions of the last detection pass; distances are measured on
+    /// the current positions, with one extra cell of reach for particles that moved since.
+    /// Returns `None` if the hash is empty or nothing is within reach.
+    pub fn nearest_particle(&self, state: &PhysicsState, p: Vec3) -> Option<usize> {
+        let mut buffer = Vec::new();
+        let mut dedup_set = FxHashSet::default();
+        let closest = |candidates: &[u32]| {
+            candidates
+                .iter()
+                .map(|&i| (i as usize, state.positions[i as usize].truncate().distance(p)))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+        };
+
+        let cell = self.cell_size();
+        let mut radius = cell;
+        for _ in 0..NEAREST_SEARCH_STEPS {
+            // Box queries: the coarse early-out of `query` would reject points off the cloth
+            let reach = Vec3::splat(radius);
+            self.hash.query_aabb(p - reach, p + reach, &mut buffer, &mut dedup_set);
+            if let Some((_, distance)) = closest(&buffer)
+                && distance <= radius
+            {
+                let reach = Vec3::splat(distance + cell);
+                self.hash.query_aabb(p - reach, p + reach, &mut buffer, &mut dedup_set);
+                return closest(&buffer).map(|(i, _)| i);
+            }
+            radius *= 2.0;
+        }
+        None
+    }
+
     /// Detects and resolves self-collisions using three-phase approach:
     /// 1. Detect pairs (read-only broad-phase)
     /// 2. Color pairs for parallel-safe batching
@@ -62,10 +127,16 @@ impl SelfCollision {
     ///
     /// PROFILING: Each phase is measured individually.
     pub fn solve(&mut self, state: &mut PhysicsState) {
+        if self.config.mode == SelfCollisionMode::VertexTriangle {
+            self.solve_vertex_triangle(state);
+            return;
+        }
+
         Profiler::start(ProfileCategory::SelfCollisionDetect);
         let has_pairs = self.detect_pairs(state);
         Profiler::end(ProfileCategory::SelfCollisionDetect);
 
+        let mut batch_count = 0;
         if has_pairs {
             Profiler::start(ProfileCategory::SelfCollisionColor);
             self.color_pairs();
@@ -74,6 +145,11 @@ impl SelfCollision {
             Profiler::start(ProfileCategory::SelfCollisionResolve);
             self.resolve_batched(state);
             Profiler::end(ProfileCategory::SelfCollisionResolve);
+
+            batch_count = self.batch_offsets.len().saturating_sub(1);
         }
+
+        Profiler::set_counter(ProfileCounter::CollisionPairs, self.collision_pairs.len() as u64);
+        Profiler::set_counter(ProfileCounter::SelfCollisionBatches, batch_count as u64);
     }
 }
@@ -0,0 +1,188 @@
+// physics/src/collision/self_collision/vertex_triangle.rs
+
+//! Vertex-triangle self-collision (`SelfCollisionMode::VertexTriangle`).
+//! Tests each particle against nearby cloth triangles so a vertex cannot slip
+//! through the interior of a large triangle between its corners.
+
+use super::SelfCollision;
+use super::config::VertexTrianglePair;
+use super::detection::keep_deepest;
+use crate::collision::geometry::Triangle;
+use crate::engine::state::PhysicsState;
+use crate::utils::profiler::{ProfileCategory, ProfileCounter, Profiler};
+use glam::{Vec3, Vec4};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use rustc_hash::FxHashSet;
+
+impl SelfCollision {
+    /// Detects and resolves vertex-triangle contacts.
+    /// Resolution is serial (Gauss-Seidel): each pair touches four particles,
+    /// so the pair coloring used by the point-point path does not apply.
+    pub(crate) fn solve_vertex_triangle(&mut self, state: &mut PhysicsState) {
+        Profiler::start(ProfileCategory::SelfCollisionDetect);
+        self.detect_vertex_triangle(state);
+        Profiler::end(ProfileCategory::SelfCollisionDetect);
+
+        Profiler::start(ProfileCategory::SelfCollisionResolve);
+        self.resolve_vertex_triangle(state);
+        Profiler::end(ProfileCategory::SelfCollisionResolve);
+
+        Profiler::set_counter(ProfileCounter::CollisionPairs, self.vt_pairs.len() as u64);
+        Profiler::set_counter(ProfileCounter::SelfCollisionBatches, 0);
+    }
+
+    /// Collects (particle, triangle) pairs closer than `thickness`, skipping triangles
+    /// within the particle's topology exclusion. Sorted by `(p, t)` before capping.
+    pub(crate) fn detect_vertex_triangle(&mut self, state: &PhysicsState) {
+        self.update_hash(state);
+
+        let thickness = self.config.thickness;
+        let thickness_sq = thickness * thickness;
+        let num_triangles = state.indices.len() / 3;
+
+        let find = |t: usize,
+                    query_buffer: &mut Vec<u32>,
+                    dedup_set: &mut FxHashSet<u32>,
+                    out: &mut Vec<(f32, VertexTrianglePair)>| {
+            let ia = state.indices[t * 3] as usize;
+            let ib = state.indices[t * 3 + 1] as usize;
+            let ic = state.indices[t * 3 + 2] as usize;
+            if ia == ib || ib == ic || ia == ic {
+                return;
+            }
+
+            let tri = Triangle::new(
+                state.positions[ia].truncate(),
+                state.positions[ib].truncate(),
+                state.positions[ic].truncate(),
+                t,
+            );
+
+            // Candidates: particles in the fine cells under the triangle's bounds + thickness
+            let (min, max) = tri.aabb();
+            if self.config.region.is_some_and(|region| {
+                !region.overlaps(min - Vec3::splat(thickness), max + Vec3::splat(thickness))
+            }) {
+                return;
+            }
+            self.hash.query_aabb(
+                min - Vec3::splat(thickness),
+                max + Vec3::splat(thickness),
+                query_buffer,
+                dedup_set,
+            );
+
+            for &p in query_buffer.iter() {
+                let pi = p as usize;
+                if pi == ia || pi == ib || pi == ic {
+                    continue;
+                }
+                if !self.should_test(state, pi, ia)
+                    || !self.should_test(state, pi, ib)
+                    || !self.should_test(state, pi, ic)
+                {
+                    continue;
+                }
+
+                let pos = state.positions[pi].truncate();
+                if tri.aabb_dist_sq(pos) >= thickness_sq {
+                    continue;
+                }
+                let (closest, _bary) = tri.closest_point(pos);
+                let dist_sq = closest.distance_squared(pos);
+                if dist_sq < thickness_sq && dist_sq > 1e-9 {
+                    out.push((dist_sq, VertexTrianglePair { p, t: t as u32 }));
+                }
+            }
+        };
+
+        #[cfg(feature = "parallel")]
+        let pairs: Vec<(f32, VertexTrianglePair)> = (0..num_triangles)
+            .into_par_iter()
+            .map(|t| {
+                let mut query_buffer = Vec::with_capacity(32);
+                let mut dedup_set = FxHashSet::default();
+                let mut local = Vec::new();
+                find(t, &mut query_buffer, &mut dedup_set, &mut local);
+                local
+            })
+            .flatten()
+            .collect();
+
+        #[cfg(not(feature = "parallel"))]
+        let pairs: Vec<(f32, VertexTrianglePair)> = {
+            let mut query_buffer = Vec::with_capacity(32);
+            let mut dedup_set = FxHashSet::default();
+            let mut out = Vec::new();
+            for t in 0..num_triangles {
+                find(t, &mut query_buffer, &mut dedup_set, &mut out);
+            }
+            out
+        };
+
+        // Cap at the deepest penetrations, same policy as the vertex-vertex pairs
+        self.vt_pairs = keep_deepest(pairs, self.config.max_pairs, |pair| (pair.p, pair.t));
+    }
+
+    /// Pushes each particle out of its triangle along the closest-point direction,
+    /// distributing the counter-correction to the corners by barycentric weight.
+    pub(crate) fn resolve_vertex_triangle(&self, state: &mut PhysicsState) {
+        let stiffness = self.config.stiffness;
+        let thickness = self.config.thickness;
+
+        for pair in &self.vt_pairs {
+            let p = pair.p as usize;
+            let t = pair.t as usize;
+            let corners = [
+                state.indices[t * 3] as usize,
+                state.indices[t * 3 + 1] as usize,
+                state.indices[t * 3 + 2] as usize,
+            ];
+
+            let tri = Triangle::new(
+                state.positions[corners[0]].truncate(),
+                state.positions[corners[1]].truncate(),
+                state.positions[corners[2]].truncate(),
+                t,
+            );
+            let pos = state.positions[p].truncate();
+            let (closest, bary) = tri.closest_point(pos);
+
+            let delta = pos - closest;
+            let dist = delta.length();
+            if dist < 1e-9 {
+                continue;
+            }
+
+            let overlap = thickness - dist;
+            if overlap <= 0.0 {
+                continue;
+            }
+
+            let normal = delta / dist;
+
+            // Generalized inverse mass: w_p + sum(b_k^2 * w_k)
+            let w_p = state.inv_mass[p];
+            let mut w_sum = w_p;
+            for k in 0..3 {
+                w_sum += bary[k] * bary[k] * state.inv_mass[corners[k]];
+            }
+            if w_sum <= 0.0 {
+                continue;
+            }
+
+            let s = overlap * stiffness / w_sum;
+
+            if w_p > 0.0 {
+                state.positions[p] += Vec4::from((normal * (s * w_p), 0.0));
+            }
+            for k in 0..3 {
+                let w_k = state.inv_mass[corners[k]];
+                if w_k > 0.0 {
+                    state.positions[corners[k]] -= Vec4::from((normal * (s * w_k * bary[k]), 0.0));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// physics/src/collision/spatial/bvh.rs
+
+use glam::Vec3;
+
+/// Triangles per leaf. Small leaves keep candidate lists tight; the tree stays shallow
+/// enough that traversal is cheaper than testing the extra triangles.
+const LEAF_SIZE: usize = 4;
+
+/// A node of the tree. Interior nodes store their left child at `self + 1` and the
+/// right child at `right`; leaves (`count > 0`) own `order[first..first + count]`.
+#[derive(Clone, Copy)]
+struct BvhNode {
+    min: Vec3,
+    max: Vec3,
+    right: usize,
+    first: usize,
+    count: usize,
+}
+
+/// Bounding Volume Hierarchy (AABB tree) over the collider triangles.
+/// Alternative to `StaticSpatialHash` for elongated meshes (a full-body avatar) or very
+/// non-uniform triangle sizes, where a uniform grid wastes memory and over-reports candidates.
+/// Built top-down with a median split on the longest axis of the triangle centroids.
+pub struct TriangleBvh {
+    nodes: Vec<BvhNode>,
+    /// Triangle ids, reordered so every leaf owns a contiguous range.
+    order: Vec<usize>,
+    /// Triangle AABBs in `order` (leaf entries are tested individually).
+    bounds: Vec<(Vec3, Vec3)>,
+}
+
+impl TriangleBvh {
+    /// Builds the tree from per-triangle AABBs (`bounds[i]` belongs to triangle `i`).
+    pub fn new(bounds: &[(Vec3, Vec3)]) -> Self {
+        let mut bvh = Self {
+            nodes: Vec::with_capacity(bounds.len().max(1) * 2 / LEAF_SIZE + 1),
+            order: (0..bounds.len()).collect(),
+            bounds: Vec::new(),
+        };
+        if !bounds.is_empty() {
+            bvh.build(bounds, 0, bounds.len());
+        }
+        bvh.bounds = bvh.order.iter().map(|&id| bounds[id]).collect();
+        bvh
+    }
+
+    /// Recursively builds the subtree over `order[start..end]` and returns its node index.
+    fn build(&mut self, bounds: &[(Vec3, Vec3)], start: usize, end: usize) -> usize {
+        let mut min = Vec3::splat(f32::MAX);
+        let mut max = Vec3::splat(f32::MIN);
+        let mut centroid_min = Vec3::splat(f32::MAX);
+        let mut centroid_max = Vec3::splat(f32::MIN);
+        for &id in &self.order[start..end] {
+            let (lo, hi) = bounds[id];
+            min = min.min(lo);
+            max = max.max(hi);
+            let c = (lo + hi) * 0.5;
+            centroid_min = centroid_min.min(c);
+            centroid_max = centroid_max.max(c);
+        }
+
+        let node = self.nodes.len();
+        self.nodes.push(BvhNode {
+            min,
+            max,
+            right: 0,
+            first: start,
+            count: end - start,
+        });
+        if end - start <= LEAF_SIZE {
+            return node;
+        }
+
+        // Median split along the axis with the widest centroid spread
+        let extent = centroid_max - centroid_min;
+        let axis = if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        };
+        let mid = (start + end) / 2;
+        let centroid = |id: usize| {
+            let (lo, hi) = bounds[id];
+            (lo[axis] + hi[axis]) * 0.5
+        };
+        self.order[start..end]
+            .select_nth_unstable_by(mid - start, |&a, &b| centroid(a).total_cmp(&centroid(b)));
+
+        self.build(bounds, start, mid);
+        let right = self.build(bounds, mid, end);
+        self.nodes[node].right = right;
+        self.nodes[node].count = 0;
+        node
+    }
+
+    /// Fast AABB check to skip particles far outside the mesh (root bounds grown by `margin`).
+    pub fn contains(&self, p: Vec3, margin: f32) -> bool {
+        self.nodes.first().is_some_and(|root| {
+            p.cmpge(root.min - Vec3::splat(margin)).all()
+                && p.cmple(root.max + Vec3::splat(margin)).all()
+        })
+    }
+
+    /// Retrieves all triangles whose AABB overlaps the query box `p ± radius`.
+    /// Same contract as `StaticSpatialHash::query`; the tree never reports a triangle
+    /// twice, so no dedup set is needed.
+    pub fn query(&self, p: Vec3, radius: f32, buffer: &mut Vec<usize>) {
+        buffer.clear();
+        if self.nodes.is_empty() {
+            return;
+        }
+
+        let min = p - Vec3::splat(radius);
+        let max = p + Vec3::splat(radius);
+
+        let mut stack = [0usize; 64];
+        let mut top = 1;
+        while top > 0 {
+            top -= 1;
+            let index = stack[top];
+            let node = &self.nodes[index];
+            if node.max.cmplt(min).any() || node.min.cmpgt(max).any() {
+                continue;
+            }
+
+            if node.count > 0 {
+                for k in node.first..node.first + node.count {
+                    let (lo, hi) = self.bounds[k];
+                    if hi.cmpge(min).all() && lo.cmple(max).all() {
+                        buffer.push(self.order[k]);
+                    }
+                }
+            } else {
+                // Median splits keep the depth at ~log2(n / LEAF_SIZE), far below the stack size
+                stack[top] = node.right;
+                stack[top + 1] = index + 1;
+                top += 2;
+            }
+        }
+    }
+}
@@ -34,8 +34,15 @@ pub struct HierarchicalSpatialHash {
     coarse_cell_size: f32,
     fine_grid: FxHashMap<u64, SmallVec<[u32; 8]>>,
     coarse_grid: FxHashMap<u64, SmallVec<[u32; 16]>>,
+    /// (fine_key, coarse_key) each point was last inserted under, indexed by id.
+    /// `UNTRACKED` marks ids that are not currently in the hash.
+    point_keys: Vec<(u64, u64)>,
+    /// Fine cell coordinates matching `point_keys`, so an unmoved point can skip Morton encoding.
+    point_cells: Vec<(i32, i32, i32)>,
 }
 
+const UNTRACKED: (u64, u64) = (u64::MAX, u64::MAX);
+
 impl HierarchicalSpatialHash {
     pub fn new(collision_radius: f32) -> Self {
         let fine_cell_size = collision_radius * 2.0;
@@ -46,17 +53,37 @@ impl HierarchicalSpatialHash {
             coarse_cell_size,
             fine_grid: FxHashMap::default(),
             coarse_grid: FxHashMap::default(),
+            point_keys: Vec::new(),
+            point_cells: Vec::new(),
         }
     }
 
+    /// Changes the collision radius (and both cell sizes).
+    /// Every cell key changes, so the grids are emptied; points are re-inserted
+    /// on their next `update_point`.
+    pub fn set_collision_radius(&mut self, collision_radius: f32) {
+        self.fine_cell_size = collision_radius * 2.0;
+        self.coarse_cell_size = self.fine_cell_size * 4.0;
+        self.fine_grid.clear();
+        self.coarse_grid.clear();
+        self.point_keys.fill(UNTRACKED);
+    }
+
+    /// Current fine cell size (twice the collision radius).
+    #[inline]
+    pub fn fine_cell_size(&self) -> f32 {
+        self.fine_cell_size
+    }
+
     /// Clears all cells without deallocating memory (reuses capacity).
     pub fn clear(&mut self) {
-        for (_, cell) in self.fine_grid.iter_mut() {
+        for cell in self.fine_grid.values_mut() {
             cell.clear();
         }
-        for (_, cell) in self.coarse_grid.iter_mut() {
+        for cell in self.coarse_grid.values_mut() {
             cell.clear();
         }
+        self.point_keys.fill(UNTRACKED);
     }
 
     #[inline]
@@ -77,23 +104,102 @@ impl HierarchicalSpatialHash {
         )
     }
 
+    #[inline]
+    fn cell_keys(&self, p: Vec3) -> (u64, u64) {
+        let fine_cell = self.get_fine_cell(p);
+        let coarse_cell = self.get_coarse_cell(p);
+        (
+            morton_encode(fine_cell.0, fine_cell.1, fine_cell.2),
+            morton_encode(coarse_cell.0, coarse_cell.1, coarse_cell.2),
+        )
+    }
+
     /// Inserts a particle into both fine and coarse grids.
     pub fn insert_point(&mut self, id: u32, p: Vec3) {
-        let fine_cell = self.get_fine_cell(p);
-        let fine_key = morton_encode(fine_cell.0, fine_cell.1, fine_cell.2);
+        let (fine_key, coarse_key) = self.cell_keys(p);
         self.fine_grid.entry(fine_key).or_default().push(id);
+        self.coarse_grid.entry(coarse_key).or_default().push(id);
 
-        let coarse_cell = self.get_coarse_cell(p);
+        let idx = id as usize;
+        if idx >= self.point_keys.len() {
+            self.point_keys.resize(idx + 1, UNTRACKED);
+            self.point_cells.resize(idx + 1, (0, 0, 0));
+        }
+        self.point_keys[idx] = (fine_key, coarse_key);
+        self.point_cells[idx] = self.get_fine_cell(p);
+    }
+
+    /// Moves a particle to its new cells, touching the grids only when a cell changes.
+    /// Points that stay inside their fine cell return before any Morton encoding.
+    /// The old cells are taken from the keys recorded at the last insert/update, so the
+    /// caller does not need to know where the point was when it was last hashed.
+    /// Points not yet in the hash are inserted.
+    pub fn update_point(&mut self, id: u32, new_p: Vec3) {
+        let idx = id as usize;
+        let old_keys = self.point_keys.get(idx).copied().unwrap_or(UNTRACKED);
+        if old_keys == UNTRACKED {
+            self.insert_point(id, new_p);
+            return;
+        }
+
+        // Fast path: still in the same fine cell (and therefore the same coarse cell),
+        // so nothing to re-encode or move. Most particles of resting cloth take this.
+        let fine_cell = self.get_fine_cell(new_p);
+        if fine_cell == self.point_cells[idx] {
+            return;
+        }
+
+        let coarse_cell = self.get_coarse_cell(new_p);
+        let fine_key = morton_encode(fine_cell.0, fine_cell.1, fine_cell.2);
         let coarse_key = morton_encode(coarse_cell.0, coarse_cell.1, coarse_cell.2);
-        self.coarse_grid.entry(coarse_key).or_default().push(id);
+
+        if fine_key != old_keys.0 {
+            if let Some(cell) = self.fine_grid.get_mut(&old_keys.0)
+                && let Some(pos) = cell.iter().position(|&x| x == id)
+            {
+                cell.swap_remove(pos);
+            }
+            self.fine_grid.entry(fine_key).or_default().push(id);
+        }
+
+        if coarse_key != old_keys.1 {
+            if let Some(cell) = self.coarse_grid.get_mut(&old_keys.1)
+                && let Some(pos) = cell.iter().position(|&x| x == id)
+            {
+                cell.swap_remove(pos);
+            }
+            self.coarse_grid.entry(coarse_key).or_default().push(id);
+        }
+
+        self.point_keys[idx] = (fine_key, coarse_key);
+        self.point_cells[idx] = fine_cell;
+    }
+
+    /// Takes a particle out of the hash (no-op if it is not in it).
+    pub fn remove_point(&mut self, id: u32) {
+        let idx = id as usize;
+        let old_keys = self.point_keys.get(idx).copied().unwrap_or(UNTRACKED);
+        if old_keys == UNTRACKED {
+            return;
+        }
+
+        if let Some(cell) = self.fine_grid.get_mut(&old_keys.0)
+            && let Some(pos) = cell.iter().position(|&x| x == id)
+        {
+            cell.swap_remove(pos);
+        }
+        if let Some(cell) = self.coarse_grid.get_mut(&old_keys.1)
+            && let Some(pos) = cell.iter().position(|&x| x == id)
+        {
+            cell.swap_remove(pos);
+        }
+        self.point_keys[idx] = UNTRACKED;
     }
 
     /// Queries particles within radius using hierarchical refinement.
     /// 1. Check coarse grid for early exit
     /// 2. Refine to fine grid for actual candidates
-    /// Queries particles within radius using hierarchical refinement.
-    /// 1. Check coarse grid for early exit
-    /// 2. Refine to fine grid for actual candidates
+    ///
     /// Thread-safe version: accepts external buffers.
     pub fn query(
         &self,
@@ -138,9 +244,30 @@ impl HierarchicalSpatialHash {
         }
 
         // Fine grid query
-        let min = p - Vec3::splat(radius);
-        let max = p + Vec3::splat(radius);
+        self.gather_fine(p - Vec3::splat(radius), p + Vec3::splat(radius), buffer, dedup_set);
+    }
 
+    /// Queries all particles in fine cells overlapping the box `[min, max]`.
+    /// No coarse early-exit, so suited to boxes larger than a coarse cell (e.g. triangle bounds).
+    pub fn query_aabb(
+        &self,
+        min: Vec3,
+        max: Vec3,
+        buffer: &mut Vec<u32>,
+        dedup_set: &mut rustc_hash::FxHashSet<u32>,
+    ) {
+        buffer.clear();
+        dedup_set.clear();
+        self.gather_fine(min, max, buffer, dedup_set);
+    }
+
+    fn gather_fine(
+        &self,
+        min: Vec3,
+        max: Vec3,
+        buffer: &mut Vec<u32>,
+        dedup_set: &mut rustc_hash::FxHashSet<u32>,
+    ) {
         let (min_x, min_y, min_z) = self.get_fine_cell(min);
         let (max_x, max_y, max_z) = self.get_fine_cell(max);
 
@@ -160,6 +287,11 @@ impl HierarchicalSpatialHash {
         }
     }
 
+    /// Occupied `(fine, coarse)` cells.
+    pub fn cell_counts(&self) -> (usize, usize) {
+        (self.fine_grid.len(), self.coarse_grid.len())
+    }
+
     #[allow(dead_code)]
     pub fn fine_grid_capacity(&self) -> usize {
         self.fine_grid.capacity()
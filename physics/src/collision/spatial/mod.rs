@@ -1,7 +1,9 @@
 // physics/src/collision/spatial/mod.rs
 
+pub mod bvh;
 pub mod static_grid;
 pub mod dynamic;
 
+pub use bvh::TriangleBvh;
 pub use static_grid::StaticSpatialHash;
 pub use dynamic::HierarchicalSpatialHash;
\ No newline at end of file
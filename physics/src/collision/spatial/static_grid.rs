@@ -3,6 +3,13 @@
 use glam::Vec3;
 use rustc_hash::FxHashSet;
 
+/// Margin (meters) added around the mesh bounds. Fixed rather than a multiple of the
+/// cell size, so the `contains` early-out of the broad phase does not shrink with finer cells.
+pub const BOUNDS_PADDING: f32 = 0.2;
+
+/// Most cells a grid allocates; a finer requested cell size is coarsened to fit.
+const MAX_CELLS: usize = 1_000_000;
+
 /// A fixed-size 3D grid for spatial partitioning.
 /// Optimized for static geometry (like the mannequin) where objects do not move.
 /// Allows fast O(1) lookups of triangles near a particle.
@@ -18,20 +25,33 @@ pub struct StaticSpatialHash {
 
 impl StaticSpatialHash {
     pub fn new(bounds_min: Vec3, bounds_max: Vec3, cell_size: f32) -> Self {
-        let padding = Vec3::splat(cell_size * 2.0);
+        let padding = Vec3::splat(BOUNDS_PADDING);
         let min = bounds_min - padding;
         let max = bounds_max + padding;
 
         let size = max - min;
 
+        // Safety cap to prevent OOM when a tiny cell size meets a large mesh
+        let cells_for = |cell: f32| {
+            let axis = |extent: f32| (extent / cell).ceil().max(1.0) as f64;
+            axis(size.x) * axis(size.y) * axis(size.z)
+        };
+        let mut cell_size = cell_size;
+        if cells_for(cell_size) > MAX_CELLS as f64 {
+            cell_size *= (cells_for(cell_size) / MAX_CELLS as f64).cbrt() as f32;
+            while cells_for(cell_size) > MAX_CELLS as f64 {
+                cell_size *= 1.01;
+            }
+        }
+
         let width = (size.x / cell_size).ceil() as usize;
         let height = (size.y / cell_size).ceil() as usize;
         let depth = (size.z / cell_size).ceil() as usize;
 
         // Safety caps to prevent OOM on huge meshes
-        let safe_width = width.max(1).min(1000);
-        let safe_height = height.max(1).min(1000);
-        let safe_depth = depth.max(1).min(1000);
+        let safe_width = width.clamp(1, 1000);
+        let safe_height = height.clamp(1, 1000);
+        let safe_depth = depth.clamp(1, 1000);
 
         let total_cells = safe_width * safe_height * safe_depth;
 
@@ -56,6 +76,21 @@ impl StaticSpatialHash {
             && p.z <= self.max.z
     }
 
+    /// Edge length of a cell; larger than requested if the cell cap kicked in.
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Number of allocated grid cells.
+    pub fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Total triangle ids stored over all cells (a triangle is counted once per cell).
+    pub fn entry_count(&self) -> usize {
+        self.cells.iter().map(Vec::len).sum()
+    }
+
     /// Clears all cells in the spatial hash, preparing it for a new frame.
     /// Keeps the allocated memory (capacity) for performance.
     pub fn clear(&mut self) {
@@ -92,8 +127,6 @@ impl StaticSpatialHash {
     /// Used during Broad Phase Collision Detection.
     /// OPTIMIZATION: Uses FxHashSet for O(N) deduplication instead of O(N log N) sort.
     /// This is critical because broad phase is called for every active particle.
-    /// Retrieves all triangles in cells overlapping the query radius.
-    /// Used during Broad Phase Collision Detection.
     /// Thread-safe version: requires external buffers to avoid internal mutation.
     pub fn query(
         &self,
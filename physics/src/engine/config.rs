@@ -1,10 +1,19 @@
 // physics/sr/engine/config.rs
 
+use crate::collision::collider::MIN_CELL_SIZE;
 use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+/// Slack added to the derived contact query radius (meters) so a contact found once per
+/// frame still covers the particle's motion over the following substeps.
+pub const CONTACT_QUERY_MARGIN: f32 = 0.045;
 
 /// Global configuration for the physics simulation.
 /// Controls solver quality/speed trade-offs and physical properties like gravity and stiffness.
-#[derive(Clone, Debug)]
+///
+/// Serializes with the Rust field names; missing fields in JSON presets fall back to `Default`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PhysicsConfig {
     // --- Simulation Quality ---
     /// Number of sub-steps per frame. Higher = More stable, Slower.
@@ -13,30 +22,171 @@ pub struct PhysicsConfig {
     pub solver_iterations: usize,
     /// Relaxation parameter (Successive Over-Relaxation). 1.0 = Gauss-Seidel. <1.0 = More stable.
     pub spectral_radius: f32,
+    /// Apply the Chebyshev over-relaxation schedule driven by `spectral_radius`.
+    /// Off = plain Gauss-Seidel (omega = 1), for stiff materials where acceleration rings.
+    pub use_chebyshev: bool,
+    /// Pick the substep count per frame from the fastest particle instead of using `substeps`.
+    pub adaptive_substeps: bool,
+    /// Upper bound on substeps when `adaptive_substeps` is enabled.
+    pub max_substeps: usize,
+    /// Fixed frame length in seconds. When > 0, `step(dt)` feeds `dt` into an accumulator
+    /// and runs whole `fixed_dt` frames, carrying the remainder to the next call, so the
+    /// physics no longer depends on the render frame rate. 0.0 = variable (one frame per call).
+    pub fixed_dt: f32,
+    /// Temporal smoothing of output normals: weight of last frame's normal when blending
+    /// (0.0 = recompute from scratch every frame, higher = less shading flicker, more lag).
+    pub normal_smoothing: f32,
+    /// Accumulate vertex normals (cloth output and collider) weighted by corner angle
+    /// instead of face area. Better on irregular meshes, where area weighting leans
+    /// normals towards large faces.
+    pub angle_weighted_normals: bool,
+    /// Carry the distance/bending XPBD multipliers over from the previous substep and
+    /// pre-apply them, so nearly static drapes converge in fewer iterations.
+    pub warm_start: bool,
+    /// Record the distance residual after every solver iteration (debug; costs one
+    /// extra pass over the edges per iteration).
+    pub track_convergence: bool,
+
+    // --- Sleeping ---
+    /// Put particles that stay still to sleep (skipped by integrator and constraints).
+    pub sleep_enabled: bool,
+    /// Per-frame displacement (meters) below which a particle counts as still.
+    pub sleep_threshold: f32,
+    /// Consecutive still frames before a particle falls asleep.
+    pub sleep_frames: u32,
 
     // --- Environment ---
     pub gravity: Vec3,
+    /// Scales gravity linearly from 0 to full over the first N frames after construction
+    /// (or `reset`), so a pattern starting far from the body settles on it gently. 0 = off.
+    pub gravity_ramp_frames: u32,
+    /// Compute per-triangle drag/lift/wind. Off = gravity + `damping` only (cheaper indoors).
+    pub aerodynamics_enabled: bool,
     pub wind: Vec3,
+    /// Amplitude (m/s) of noise-driven gusts added to `wind`. 0.0 = steady wind.
+    pub wind_turbulence: f32,
+    /// Seed for the turbulence noise, so gusts are reproducible.
+    pub wind_seed: u32,
+    /// Shade triangles that the body hides from the wind (a ray cast upwind against the
+    /// collider once per frame), so the leeward side is not blown as hard as the windward one.
+    pub wind_occlusion: bool,
     /// Global Velocity Damping (0.0 to 1.0). Simulates air resistance.
     pub damping: f32,
+    /// Farthest a particle may move in one substep's integration (meters), so a huge
+    /// gust or impulse cannot tunnel through the body before collisions run.
+    /// A fraction of the shortest edge is a good value. 0.0 = unlimited.
+    pub max_displacement: f32,
 
     // --- Material Properties ---
     pub drag_coeff: f32,
     pub lift_coeff: f32,
-    #[allow(dead_code)]
+    /// Fabric area density in kg/m^2 (gsm / 1000), used when `mass_from_area` is set.
     pub density: f32,
+    /// Derive each particle's mass from its rest-pose triangle area and `density` at
+    /// construction. Off = every particle weighs 1 (the constraint compliances are tuned for it).
+    pub mass_from_area: bool,
+    /// Pin vertices that no triangle references (inverse mass 0) at construction,
+    /// instead of letting them fall forever. `Simulation::orphan_vertices` lists them.
+    pub pin_orphan_vertices: bool,
+
+    // --- Constraint Toggles (debugging: isolate which constraint causes an artifact) ---
+    /// Solve the edge distance (stretch) constraints.
+    pub distance_enabled: bool,
+    /// Solve the cross-edge distance bending constraints (dihedral bending has its own `dihedral_enabled`).
+    pub bending_enabled: bool,
+    /// Solve the long-range tether constraints.
+    pub tether_enabled: bool,
+    /// XPBD compliance of the vertical (anti-sag) tethers. 0.0 = inextensible.
+    pub tether_vertical_compliance: f32,
+    /// XPBD compliance of the horizontal (anti-widen) tethers. A little give here keeps
+    /// shoulder bridges from producing a stiff, unnatural neckline. 0.0 = inextensible.
+    pub tether_horizontal_compliance: f32,
+    /// Solve the triangle area (shear) constraints.
+    pub area_enabled: bool,
 
     // --- Constraint Stiffness ---
-    /// Compliance (inverse stiffness) for distance constraints.
+    /// Compliance (inverse stiffness, m/N) for distance constraints; the raw XPBD value.
     /// 0.0 = Infinite stiffness (jittery). Small value (e.g. 1e-5) = Stable.
+    /// Ignored when `distance_stiffness` is set.
     pub distance_compliance: f32,
+    /// Edge stretch stiffness in N/m (0.0 = use `distance_compliance`). Converted to
+    /// compliance as `1 / distance_stiffness`; the solver divides compliance by the
+    /// substep `dt^2`, so a preset means the same material at any `substeps` or frame rate.
+    pub distance_stiffness: f32,
+    /// Compliance of a fully soft (stiffness 0) edge when a per-vertex stiffness map is set.
+    pub stiffness_map_scale: f32,
     /// Resistance to area change (Shearing). Very low for cloth.
     pub area_compliance: f32,
+    /// Enable dihedral (hinge) bending. Rest angles come from the initial mesh,
+    /// so pre-folded pleats and darts keep their shape.
+    pub dihedral_enabled: bool,
+    /// Compliance of the dihedral bending constraint (rad^-1 units).
+    pub dihedral_compliance: f32,
+    /// Turns dihedral bending into a hinge with stops: each hinge folds freely between
+    /// `dihedral_min_angle` and `dihedral_max_angle` and is only corrected back to the
+    /// nearest stop outside them (knife pleats, lapels). Off = held at the rest angle.
+    pub dihedral_limits_enabled: bool,
+    /// Lower fold stop (signed radians, 0 = flat, range [-PI, PI]).
+    pub dihedral_min_angle: f32,
+    /// Upper fold stop (signed radians, 0 = flat, range [-PI, PI]).
+    pub dihedral_max_angle: f32,
+    /// Successive over-relaxation factor for the distance corrections, multiplied on
+    /// top of the Chebyshev `omega`. 1.0 = plain correction; range (0, 2).
+    pub distance_relaxation: f32,
+    /// Relaxation factor for both bending models (cross-edge and dihedral). Values
+    /// below 1.0 damp the ringing of stiff fabrics; range (0, 2).
+    pub bending_relaxation: f32,
+    /// Relaxation factor for the tether corrections; range (0, 2).
+    pub tether_relaxation: f32,
+    /// Relaxation factor for the area corrections; range (0, 2).
+    pub area_relaxation: f32,
+    /// Stiffness of the springs pulling grabbed particles (mouse and group grab) to
+    /// their targets, in `[0, 1]`. 1.0 = rigid (snap); lower values drag more elastically.
+    pub grab_stiffness: f32,
+    /// Stiffness of the attachments pinning particles to the collider surface, in `[0, 1]`.
+    /// 1.0 = rigid; lower values let attached cloth stretch away a little.
+    pub attachment_stiffness: f32,
     /// Distance between cloth layers or cloth/body.
+    /// The visible cloth-body gap is this plus `cloth_radius` plus the collider inflation.
     pub contact_thickness: f32,
+    /// Radius of the sphere each cloth particle collides as (fabric volume, meters).
+    /// Added to `contact_thickness` for body contacts and, once per side, to
+    /// `self_collision_thickness`, so stacked folds sit apart by the real fabric thickness.
+    /// 0.0 = zero-thickness shell.
+    pub cloth_radius: f32,
+    /// Radius of the discrete (closest-point) body contact query. 0.0 = derive it from
+    /// `contact_thickness + cloth_radius + collider inflation` plus a fixed margin for the
+    /// distance a particle can travel while its contact is reused across substeps.
+    pub contact_query_radius: f32,
+    /// Slack (meters) added to the once-per-frame broad-phase query radius, on top of the
+    /// particle's predicted displacement over the frame.
+    pub broad_phase_margin: f32,
+    /// Cap on the broad-phase collider candidates gathered per frame (all particles together).
+    /// Particles past the cap get no body collision that frame. 0 = `CANDIDATES_PER_PARTICLE`
+    /// per particle, the size the candidate buffer is preallocated with.
+    pub max_candidates: usize,
     pub static_friction: f32,
     pub dynamic_friction: f32,
     pub collision_stiffness: f32,
+    /// Caps the body-contact correction applied per resolve (meters; 0 = unlimited).
+    /// A particle that lands deep inside the body (teleport, grab, pose jump) is then
+    /// pushed out over several substeps instead of popping out in one.
+    pub max_collision_correction: f32,
+
+    // --- Collider ---
+    /// Laplacian smoothing factor per collider smoothing iteration (0 = no movement,
+    /// 1 = snap to the neighbor average). The iteration count is the constructor's
+    /// `collider_smoothing`; 0 iterations disables smoothing for already-clean meshes.
+    /// A change takes effect at the next `update_collider`, which re-processes the raw
+    /// vertices; a static collider keeps the smoothing it was built with.
+    pub collider_smoothing_lambda: f32,
+    /// Use a BVH instead of the uniform grid for the collider broad phase.
+    /// Better for elongated bodies and meshes with very uneven triangle sizes.
+    pub collider_bvh: bool,
+    /// Cell size (meters) of the collider's uniform grid. 0.0 = derived from the mean
+    /// collider edge length, otherwise at least 0.02; set it when candidate lists are huge (cells too coarse)
+    /// or contacts are slow to gather (cells too fine).
+    pub collider_cell_size: f32,
 
     // --- Self-Collision ---
     /// Enable cloth self-collision detection
@@ -47,10 +197,14 @@ pub struct PhysicsConfig {
     pub self_collision_stiffness: f32,
     /// Solve self-collision every N substeps (performance optimization)
     pub self_collision_frequency: u8,
+    /// Cap on self-collision pairs resolved per detection pass. Past the cap the
+    /// deepest overlaps are kept, so dense folds degrade gracefully instead of
+    /// dropping contacts by mesh order.
+    pub self_collision_max_pairs: usize,
 }
 
-impl PhysicsConfig {
-    pub fn default() -> Self {
+impl Default for PhysicsConfig {
+    fn default() -> Self {
         Self {
             // OPTIMIZED: 4 substeps x 4 iterations = 16 solves/frame (High Performance)
             // Reduced from 8 to restore FPS. "Soft Physics" handles stability.
@@ -60,34 +214,336 @@ impl PhysicsConfig {
             // DISABLED: Chebyshev acceleration causes jitter in tight collisions.
             // 0.2 = Minimal acceleration (Safe). avoiding erratic updates in tight spots.
             spectral_radius: 0.5,
+            use_chebyshev: true,
+
+            // Adaptive substepping is opt-in; 16 caps the cost of fast flicks.
+            adaptive_substeps: false,
+            max_substeps: 16,
+            // Variable timestep by default (one frame per `step` call)
+            fixed_dt: 0.0,
+            // Warm starting is opt-in; the cold start is the tuned default.
+            warm_start: false,
+            track_convergence: false,
+            normal_smoothing: 0.0,
+            angle_weighted_normals: false,
+
+            // Sleeping is opt-in: 0.1mm/frame for half a second at 60 FPS.
+            sleep_enabled: false,
+            sleep_threshold: 1.0e-4,
+            sleep_frames: 30,
 
             gravity: Vec3::new(0.0, -9.81, 0.0),
+            gravity_ramp_frames: 0,
+            aerodynamics_enabled: true,
             wind: Vec3::new(0.0, 0.0, 0.0),
+            wind_turbulence: 0.0,
+            wind_seed: 0,
+            wind_occlusion: false,
             // Increased damping to kill "push-pull" vibrations (0.95 dissipates 5% energy/frame)
             damping: 0.99,
+            max_displacement: 0.0,
 
             // Reduced drag so the cloth falls naturally instead of floating
             drag_coeff: 0.5,
             lift_coeff: 0.05,
             density: 1.0,
+            mass_from_area: false,
+            pin_orphan_vertices: true,
+
+            distance_enabled: true,
+            bending_enabled: true,
+            tether_enabled: true,
+            // Inextensible tethers (the historical behavior) in both directions
+            tether_vertical_compliance: 0.0,
+            tether_horizontal_compliance: 0.0,
+            area_enabled: true,
 
             // STIFF: 1.0e-6 makes it very rigid (Denim/Leather).
             // Removes almost all rubbery feel.
             distance_compliance: 1.0e-7,
+            distance_stiffness: 0.0,
             area_compliance: 2.0e-4,
+            // Soft end of painted stiffness maps: noticeably stretchy (jersey/skirt).
+            stiffness_map_scale: 1.0e-3,
+            // Dihedral bending is opt-in; the cross-edge distance bending stays the default.
+            dihedral_enabled: false,
+            dihedral_compliance: 1.0e-3,
+            dihedral_limits_enabled: false,
+            dihedral_min_angle: -std::f32::consts::PI,
+            dihedral_max_angle: std::f32::consts::PI,
+            // No extra relaxation: corrections are scaled by the Chebyshev omega only
+            distance_relaxation: 1.0,
+            bending_relaxation: 1.0,
+            tether_relaxation: 1.0,
+            area_relaxation: 1.0,
+
+            // Rigid grab by default (matches the original snapping behavior)
+            grab_stiffness: 1.0,
+            attachment_stiffness: 1.0,
 
             contact_thickness: 0.005,
+            cloth_radius: 0.0,
+            contact_query_radius: 0.0,
+            broad_phase_margin: 0.02,
+            max_candidates: 0,
             static_friction: 0.3,
             dynamic_friction: 0.2,
 
             // SOFTENED: 0.5 reduces violent "kick-back" from body.
             collision_stiffness: 0.75,
+            max_collision_correction: 0.0,
+
+            // Matches the historical hardcoded smoothing factor
+            collider_smoothing_lambda: 0.5,
+            collider_bvh: false,
+            collider_cell_size: 0.0,
 
             // Self-Collision: Enabled by default with balanced settings
             self_collision_enabled: true,
             self_collision_thickness: 0.005, // 5mm
             self_collision_stiffness: 0.5,
             self_collision_frequency: 2, // Every other substep
+            self_collision_max_pairs: 10000,
         }
     }
 }
+
+impl PhysicsConfig {
+    /// Starts a builder from the default configuration.
+    pub fn builder() -> PhysicsConfigBuilder {
+        PhysicsConfigBuilder {
+            config: Self::default(),
+        }
+    }
+
+    /// Parses a JSON preset (e.g. `silk.json`) and validates it.
+    /// Fields not present in the JSON keep their default values.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let config: Self =
+            serde_json::from_str(json).map_err(|e| format!("Invalid physics config JSON: {}", e))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Serializes the configuration to JSON (the format accepted by `from_json`).
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Compliance the distance constraints use: `1 / distance_stiffness` when a stiffness
+    /// is set, otherwise the raw `distance_compliance`.
+    pub fn effective_distance_compliance(&self) -> f32 {
+        if self.distance_stiffness > 0.0 {
+            1.0 / self.distance_stiffness
+        } else {
+            self.distance_compliance
+        }
+    }
+
+    /// Fold stops `(min, max)` of the dihedral hinges, or `None` to hold the rest angle.
+    pub fn dihedral_limits(&self) -> Option<(f32, f32)> {
+        self.dihedral_limits_enabled
+            .then_some((self.dihedral_min_angle, self.dihedral_max_angle))
+    }
+
+    /// Rest distance between a cloth particle and the body surface.
+    pub fn body_contact_distance(&self) -> f32 {
+        self.contact_thickness + self.cloth_radius
+    }
+
+    /// Radius of the discrete body contact query for a collider inflated by `inflation`.
+    /// Uses `contact_query_radius` when set; otherwise the contact distance plus
+    /// `CONTACT_QUERY_MARGIN` (which reproduces the historical fixed 5cm at default settings).
+    pub fn discrete_contact_radius(&self, inflation: f32) -> f32 {
+        if self.contact_query_radius > 0.0 {
+            self.contact_query_radius
+        } else {
+            self.body_contact_distance() + inflation.max(0.0) + CONTACT_QUERY_MARGIN
+        }
+    }
+
+    /// Minimum separation between two cloth particles (both carry `cloth_radius`).
+    pub fn self_contact_distance(&self) -> f32 {
+        self.self_collision_thickness + 2.0 * self.cloth_radius
+    }
+
+    /// Checks that every field is in a usable range.
+    pub fn validate(&self) -> Result<(), String> {
+        fn check(ok: bool, msg: &str) -> Result<(), String> {
+            if ok { Ok(()) } else { Err(msg.to_string()) }
+        }
+        fn non_negative(v: f32) -> bool {
+            v.is_finite() && v >= 0.0
+        }
+        fn unit(v: f32) -> bool {
+            v.is_finite() && (0.0..=1.0).contains(&v)
+        }
+
+        check(self.substeps >= 1, "substeps must be at least 1")?;
+        check(self.solver_iterations >= 1, "solver_iterations must be at least 1")?;
+        check(self.max_substeps >= 1, "max_substeps must be at least 1")?;
+        check(non_negative(self.fixed_dt), "fixed_dt must be >= 0")?;
+        check(
+            self.spectral_radius.is_finite() && (0.0..1.0).contains(&self.spectral_radius),
+            "spectral_radius must be in [0, 1)",
+        )?;
+        check(
+            self.normal_smoothing.is_finite() && (0.0..1.0).contains(&self.normal_smoothing),
+            "normal_smoothing must be in [0, 1)",
+        )?;
+        check(non_negative(self.sleep_threshold), "sleep_threshold must be >= 0")?;
+        check(self.gravity.is_finite(), "gravity must be finite")?;
+        check(self.wind.is_finite(), "wind must be finite")?;
+        check(non_negative(self.wind_turbulence), "wind_turbulence must be >= 0")?;
+        check(unit(self.damping), "damping must be in [0, 1]")?;
+        check(non_negative(self.max_displacement), "max_displacement must be >= 0")?;
+        check(non_negative(self.drag_coeff), "drag_coeff must be >= 0")?;
+        check(non_negative(self.lift_coeff), "lift_coeff must be >= 0")?;
+        check(self.density.is_finite() && self.density > 0.0, "density must be > 0")?;
+        check(non_negative(self.distance_compliance), "distance_compliance must be >= 0")?;
+        check(non_negative(self.distance_stiffness), "distance_stiffness must be >= 0")?;
+        check(non_negative(self.stiffness_map_scale), "stiffness_map_scale must be >= 0")?;
+        check(non_negative(self.area_compliance), "area_compliance must be >= 0")?;
+        check(non_negative(self.tether_vertical_compliance), "tether_vertical_compliance must be >= 0")?;
+        check(non_negative(self.tether_horizontal_compliance), "tether_horizontal_compliance must be >= 0")?;
+        check(non_negative(self.dihedral_compliance), "dihedral_compliance must be >= 0")?;
+        let half_turn = -std::f32::consts::PI..=std::f32::consts::PI;
+        check(
+            half_turn.contains(&self.dihedral_min_angle) && half_turn.contains(&self.dihedral_max_angle),
+            "dihedral_min_angle and dihedral_max_angle must be in [-PI, PI]",
+        )?;
+        check(
+            self.dihedral_min_angle <= self.dihedral_max_angle,
+            "dihedral_min_angle must be <= dihedral_max_angle",
+        )?;
+        for (value, msg) in [
+            (self.distance_relaxation, "distance_relaxation must be in (0, 2)"),
+            (self.bending_relaxation, "bending_relaxation must be in (0, 2)"),
+            (self.tether_relaxation, "tether_relaxation must be in (0, 2)"),
+            (self.area_relaxation, "area_relaxation must be in (0, 2)"),
+        ] {
+            check(value > 0.0 && value < 2.0, msg)?;
+        }
+        check((0.0..=1.0).contains(&self.grab_stiffness), "grab_stiffness must be in [0, 1]")?;
+        check(
+            (0.0..=1.0).contains(&self.attachment_stiffness),
+            "attachment_stiffness must be in [0, 1]",
+        )?;
+        check(non_negative(self.contact_thickness), "contact_thickness must be >= 0")?;
+        check(non_negative(self.cloth_radius), "cloth_radius must be >= 0")?;
+        check(non_negative(self.contact_query_radius), "contact_query_radius must be >= 0")?;
+        check(non_negative(self.broad_phase_margin), "broad_phase_margin must be >= 0")?;
+        check(non_negative(self.static_friction), "static_friction must be >= 0")?;
+        check(non_negative(self.dynamic_friction), "dynamic_friction must be >= 0")?;
+        check(unit(self.collision_stiffness), "collision_stiffness must be in [0, 1]")?;
+        check(non_negative(self.max_collision_correction), "max_collision_correction must be >= 0")?;
+        check(unit(self.collider_smoothing_lambda), "collider_smoothing_lambda must be in [0, 1]")?;
+        check(
+            self.collider_cell_size == 0.0
+                || (self.collider_cell_size.is_finite() && self.collider_cell_size >= MIN_CELL_SIZE),
+            "collider_cell_size must be 0 (derived) or >= 0.02",
+        )?;
+        check(
+            self.self_collision_thickness.is_finite() && self.self_collision_thickness > 0.0,
+            "self_collision_thickness must be > 0",
+        )?;
+        check(unit(self.self_collision_stiffness), "self_collision_stiffness must be in [0, 1]")?;
+        check(self.self_collision_frequency >= 1, "self_collision_frequency must be at least 1")?;
+        check(self.self_collision_max_pairs >= 1, "self_collision_max_pairs must be at least 1")?;
+        Ok(())
+    }
+}
+
+/// Chainable builder for `PhysicsConfig`. `build()` validates the result.
+#[derive(Clone, Debug)]
+pub struct PhysicsConfigBuilder {
+    config: PhysicsConfig,
+}
+
+macro_rules! builder_setters {
+    ($($field:ident: $ty:ty),* $(,)?) => {
+        $(
+            pub fn $field(mut self, value: $ty) -> Self {
+                self.config.$field = value;
+                self
+            }
+        )*
+    };
+}
+
+impl PhysicsConfigBuilder {
+    builder_setters! {
+        substeps: usize,
+        solver_iterations: usize,
+        spectral_radius: f32,
+        use_chebyshev: bool,
+        adaptive_substeps: bool,
+        max_substeps: usize,
+        fixed_dt: f32,
+        warm_start: bool,
+        track_convergence: bool,
+        normal_smoothing: f32,
+        angle_weighted_normals: bool,
+        sleep_enabled: bool,
+        sleep_threshold: f32,
+        sleep_frames: u32,
+        gravity: Vec3,
+        gravity_ramp_frames: u32,
+        aerodynamics_enabled: bool,
+        wind: Vec3,
+        wind_turbulence: f32,
+        wind_seed: u32,
+        wind_occlusion: bool,
+        damping: f32,
+        max_displacement: f32,
+        drag_coeff: f32,
+        lift_coeff: f32,
+        density: f32,
+        mass_from_area: bool,
+        pin_orphan_vertices: bool,
+        distance_enabled: bool,
+        bending_enabled: bool,
+        tether_enabled: bool,
+        tether_vertical_compliance: f32,
+        tether_horizontal_compliance: f32,
+        area_enabled: bool,
+        distance_compliance: f32,
+        distance_stiffness: f32,
+        stiffness_map_scale: f32,
+        area_compliance: f32,
+        dihedral_enabled: bool,
+        dihedral_compliance: f32,
+        dihedral_limits_enabled: bool,
+        dihedral_min_angle: f32,
+        dihedral_max_angle: f32,
+        distance_relaxation: f32,
+        bending_relaxation: f32,
+        tether_relaxation: f32,
+        area_relaxation: f32,
+        grab_stiffness: f32,
+        attachment_stiffness: f32,
+        contact_thickness: f32,
+        cloth_radius: f32,
+        contact_query_radius: f32,
+        broad_phase_margin: f32,
+        max_candidates: usize,
+        static_friction: f32,
+        dynamic_friction: f32,
+        collision_stiffness: f32,
+        max_collision_correction: f32,
+        collider_smoothing_lambda: f32,
+        collider_bvh: bool,
+        collider_cell_size: f32,
+        self_collision_enabled: bool,
+        self_collision_thickness: f32,
+        self_collision_stiffness: f32,
+        self_collision_frequency: u8,
+        self_collision_max_pairs: usize,
+    }
+
+    /// Validates and returns the configuration.
+    pub fn build(self) -> Result<PhysicsConfig, String> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
@@ -0,0 +1,77 @@
+// physics/src/engine/memory.rs
+
+use super::simulation::Simulation;
+use serde::Serialize;
+
+/// Length and allocated capacity (in elements) of one buffer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct BufferUsage {
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl BufferUsage {
+    fn of<T>(buffer: &Vec<T>) -> Self {
+        Self {
+            len: buffer.len(),
+            capacity: buffer.capacity(),
+        }
+    }
+}
+
+/// Sizes of the buffers that dominate the heap, for tuning `self_collision_max_pairs`
+/// and friends on memory-constrained devices. Capacities only ever grow, so they show
+/// the peak since the last rebuild.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct MemoryReport {
+    pub particles: usize,
+    /// Broad-phase collider candidates (all particles, flattened).
+    pub candidate_indices: BufferUsage,
+    pub contacts: BufferUsage,
+    /// Self-collision vertex-vertex pairs.
+    pub collision_pairs: BufferUsage,
+    /// Self-collision vertex-triangle pairs.
+    pub vt_pairs: BufferUsage,
+    pub distance_constraints: usize,
+    pub bending_constraints: usize,
+    pub tether_constraints: usize,
+    pub area_constraints: usize,
+    pub dihedral_constraints: usize,
+    /// Allocated cells of the collider grid (0 when using the BVH).
+    pub collider_grid_cells: usize,
+    /// Triangle ids stored over all collider grid cells.
+    pub collider_grid_entries: usize,
+    /// Occupied cells of the self-collision hash.
+    pub self_collision_fine_cells: usize,
+    pub self_collision_coarse_cells: usize,
+}
+
+impl MemoryReport {
+    pub fn new(sim: &Simulation) -> Self {
+        let solver = &sim.solver;
+        let (collider_grid_cells, collider_grid_entries) = sim.collider.lookup.grid_stats();
+        let (self_collision_fine_cells, self_collision_coarse_cells) =
+            sim.self_collision.hash.cell_counts();
+
+        Self {
+            particles: sim.state.count,
+            candidate_indices: BufferUsage::of(&sim.resolver.candidate_indices),
+            contacts: BufferUsage::of(&sim.resolver.contacts),
+            collision_pairs: BufferUsage::of(&sim.self_collision.collision_pairs),
+            vt_pairs: BufferUsage::of(&sim.self_collision.vt_pairs),
+            distance_constraints: solver.distance_constraint.constraints.len(),
+            bending_constraints: solver.bending_constraint.constraints.len(),
+            tether_constraints: solver.tether_constraint.constraints.len(),
+            area_constraints: solver.area_constraint.rest_areas().len(),
+            dihedral_constraints: solver.dihedral_constraint.constraints.len(),
+            collider_grid_cells,
+            collider_grid_entries,
+            self_collision_fine_cells,
+            self_collision_coarse_cells,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
@@ -1,8 +1,21 @@
 // physics/src/engine/mod.rs
 pub mod config;
+pub mod memory;
+pub mod multi;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod native;
 pub mod state;
 pub mod simulation;
+pub mod snapshot;
+pub mod topology;
+pub mod uv_lookup;
 
-pub use config::PhysicsConfig;
+pub use config::{PhysicsConfig, PhysicsConfigBuilder};
+pub use memory::MemoryReport;
+pub use multi::MultiSimulation;
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::NativeEngine;
 pub use state::PhysicsState;
-pub use simulation::Simulation;
\ No newline at end of file
+pub use simulation::Simulation;
+pub use topology::MeshTopology;
+pub use uv_lookup::UvLookup;
\ No newline at end of file
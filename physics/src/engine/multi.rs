@@ -0,0 +1,132 @@
+// physics/src/engine/multi.rs
+
+use crate::collision::MeshCollider;
+use crate::engine::simulation::validate_collider;
+use crate::engine::{PhysicsConfig, Simulation};
+
+/// Several garments (e.g. shirt + tie + scarf) draped over one shared body.
+/// The collider is preprocessed and its triangle lookup built once, and every garment
+/// collides against it, so a scene costs one collider update and one step call per frame
+/// instead of one per garment. The broad-phase queries themselves are not shared: each
+/// garment still gathers candidates for its own particles against the shared lookup.
+/// The collider's smoothing comes from the constructor; the garments' own
+/// `collider_smoothing_lambda` settings do not apply to it.
+pub struct MultiSimulation {
+    /// The shared collision geometry (e.g., the Mannequin).
+    pub collider: MeshCollider,
+    /// Independent garments; each keeps its own state, config and constraints.
+    pub garments: Vec<Simulation>,
+}
+
+impl MultiSimulation {
+    /// `collider_smoothing` Laplacian passes, each moving a vertex `collider_smoothing_lambda`
+    /// of the way to its neighbor average (see `PhysicsConfig::collider_smoothing_lambda`).
+    pub fn new(
+        collider_pos: Vec<f32>,
+        collider_normals: Vec<f32>,
+        collider_indices: Vec<u32>,
+        collider_smoothing: usize,
+        collider_smoothing_lambda: f32,
+        collider_inflation: f32,
+    ) -> Self {
+        Self {
+            collider: MeshCollider::with_smoothing(
+                collider_pos,
+                collider_normals,
+                collider_indices,
+                collider_smoothing,
+                collider_smoothing_lambda,
+                collider_inflation,
+            ),
+            garments: Vec::new(),
+        }
+    }
+
+    /// Validating constructor: reports a malformed collider buffer or a smoothing factor
+    /// outside `[0, 1]` instead of panicking.
+    pub fn try_new(
+        collider_pos: Vec<f32>,
+        collider_normals: Vec<f32>,
+        collider_indices: Vec<u32>,
+        collider_smoothing: usize,
+        collider_smoothing_lambda: f32,
+        collider_inflation: f32,
+    ) -> Result<Self, String> {
+        validate_collider(&collider_pos, &collider_normals, &collider_indices)?;
+        if !(0.0..=1.0).contains(&collider_smoothing_lambda) {
+            return Err("collider_smoothing_lambda must be in [0, 1]".to_string());
+        }
+        Ok(Self::new(
+            collider_pos,
+            collider_normals,
+            collider_indices,
+            collider_smoothing,
+            collider_smoothing_lambda,
+            collider_inflation,
+        ))
+    }
+
+    /// Garment `index`, or an error naming the valid range.
+    pub fn garment(&self, index: usize) -> Result<&Simulation, String> {
+        self.garments.get(index).ok_or_else(|| {
+            format!(
+                "garment {} out of range ({} garments)",
+                index,
+                self.garments.len()
+            )
+        })
+    }
+
+    /// Adds a garment and returns its index. The garment gets no collider of its own;
+    /// it always steps against the shared one.
+    pub fn add_garment(
+        &mut self,
+        garment_pos: Vec<f32>,
+        garment_indices: Vec<u32>,
+        garment_uvs: Vec<f32>,
+        scale_factor: f32,
+        config: PhysicsConfig,
+    ) -> Result<usize, String> {
+        let sim = Simulation::try_with_config(
+            garment_pos,
+            garment_indices,
+            garment_uvs,
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            0,
+            0.0,
+            scale_factor,
+            config,
+        )?;
+        self.garments.push(sim);
+        Ok(self.garments.len() - 1)
+    }
+
+    pub fn garment_count(&self) -> usize {
+        self.garments.len()
+    }
+
+    /// Sticks particles of garment `garment` (original input order) to the shared
+    /// collider, see `Simulation::attach_to_collider`.
+    pub fn attach_to_collider(&mut self, garment: usize, indices: &[u32]) -> Result<(), String> {
+        self.garment(garment)?;
+        self.garments[garment].attach_to_shared_collider(indices, &self.collider)
+    }
+
+    /// Advances every garment by `dt` seconds against the shared collider.
+    pub fn step(&mut self, dt: f32) {
+        for garment in &mut self.garments {
+            garment.step_with_collider(dt, &mut self.collider);
+        }
+    }
+
+    /// Updates the shared collider once for all garments (e.g. for animation).
+    pub fn update_collider(&mut self, positions: &[f32]) {
+        self.collider.update(positions);
+        // A moving body may push resting cloth
+        for garment in &mut self.garments {
+            garment.state.wake_all();
+        }
+    }
+}
@@ -0,0 +1,70 @@
+// physics/src/engine/native.rs
+
+use crate::engine::{PhysicsConfig, Simulation};
+use crate::utils::profiler::Profiler;
+use std::time::Instant;
+
+/// Headless counterpart of the WASM `PhysicsEngine` for native benchmarks and CI.
+/// Owns a `Simulation` (reachable through `sim` for anything not wrapped here); with the
+/// `profiling` feature the per-phase profiler runs on `std::time::Instant`.
+pub struct NativeEngine {
+    pub sim: Simulation,
+}
+
+impl NativeEngine {
+    /// Builds an engine from flat buffers. Collider normals are recomputed internally,
+    /// so none are needed; the collider is used unsmoothed and uninflated.
+    pub fn new(
+        garment_pos: Vec<f32>,
+        garment_indices: Vec<u32>,
+        garment_uvs: Vec<f32>,
+        collider_pos: Vec<f32>,
+        collider_indices: Vec<u32>,
+        config: PhysicsConfig,
+    ) -> Result<Self, String> {
+        let collider_normals = vec![0.0; collider_pos.len()];
+        let sim = Simulation::try_with_config(
+            garment_pos,
+            garment_indices,
+            garment_uvs,
+            collider_pos,
+            collider_normals,
+            collider_indices,
+            0,
+            0.0,
+            1.0,
+            config,
+        )?;
+        Ok(Self { sim })
+    }
+
+    pub fn step(&mut self, dt: f32) {
+        self.sim.step(dt);
+    }
+
+    /// Steps `frames` times and returns the wall-clock time spent, in milliseconds.
+    pub fn run(&mut self, frames: usize, dt: f32) -> f64 {
+        let start = Instant::now();
+        for _ in 0..frames {
+            self.sim.step(dt);
+        }
+        start.elapsed().as_secs_f64() * 1000.0
+    }
+
+    /// Particle positions as `[x, y, z, ...]` in original input order.
+    pub fn positions(&self) -> Vec<f32> {
+        let state = &self.sim.state;
+        (0..state.count)
+            .flat_map(|orig| state.positions[state.internal_index[orig] as usize].truncate().to_array())
+            .collect()
+    }
+
+    /// Profiler report (JSON, same format as the WASM `get_profile_report`).
+    pub fn profile_report(&self) -> String {
+        Profiler::get_report_json()
+    }
+
+    pub fn reset_profiler(&self) {
+        Profiler::reset();
+    }
+}
@@ -1,13 +1,21 @@
 // physics/src/engine/simulation.rs
 
+use crate::collision::collider::MIN_CELL_SIZE;
 use crate::collision::self_collision::SelfCollisionConfig;
-use crate::collision::{CollisionResolver, MeshCollider, SelfCollision};
-use crate::engine::{PhysicsConfig, PhysicsState};
-use crate::systems::constraints::MouseConstraint;
+use crate::collision::{CollisionResolver, MeshCollider, SdfCollider, SelfCollision};
+use crate::engine::{PhysicsConfig, PhysicsState, UvLookup, snapshot};
+use crate::systems::constraints::{
+    AttachmentConstraint, GroupGrabConstraint, MouseConstraint, TetherGroup, TetherMode,
+};
 use crate::systems::dynamics::{Integrator, Solver};
-use crate::systems::forces::Aerodynamics;
+use crate::systems::forces::{Aerodynamics, ExternalForces};
 use crate::utils::normals;
-use crate::utils::profiler::{ProfileCategory, Profiler};
+use crate::utils::profiler::{ProfileCategory, ProfileCounter, Profiler};
+use glam::{Vec2, Vec3, Vec4};
+
+/// Most fixed-timestep frames a single `step` call may run, so a long hitch does not
+/// trigger a cascade of ever slower catch-up calls.
+const MAX_FIXED_FRAMES: usize = 8;
 
 /// The core physics simulation state and logic container.
 /// Holds all subsystems (solver, collider, aerodynamics, etc.) and orchestrates the time step.
@@ -20,23 +28,69 @@ pub struct Simulation {
     pub config: PhysicsConfig,
     /// The static collision geometry (e.g., the Mannequin).
     pub collider: MeshCollider,
+    /// Optional distance-field collider for detailed bodies (queried in constant time).
+    pub sdf_collider: Option<SdfCollider>,
     /// Handles collision detection and response.
     pub resolver: CollisionResolver,
     /// Solves internal constraints (Distance, Bending, etc.).
     pub solver: Solver,
     /// Calculates external wind/drag forces.
     pub aerodynamics: Aerodynamics,
+    /// Host-injected per-particle forces, consumed by the next frame.
+    pub external_forces: ExternalForces,
     /// Handles user interaction (Mouse dragging).
     pub mouse: MouseConstraint,
+    /// Drags a cluster of particles by one shared translation (e.g. a grabbing hand).
+    pub group_grab: GroupGrabConstraint,
+    /// Particles stuck to the collider surface (waistbands, belts).
+    pub attachments: AttachmentConstraint,
     /// Handles cloth-on-cloth self-collision.
     pub self_collision: SelfCollision,
     /// Substep counter for reduced-frequency self-collision.
     substep_counter: u32,
     /// Frame counter for lazy updates (e.g. normals).
     frame_count: u32,
+    /// Hardest body impact of the last `step` call (internal index, m/s).
+    strongest_impact: Option<(usize, f32)>,
+    /// Unsimulated time carried between `step` calls in fixed-timestep mode.
+    time_accumulator: f32,
+    /// Simulated seconds (sum of every `step` dt). f64 so long sessions do not drift.
+    sim_time: f64,
+    /// Starting pose in original input order, restored by `reset`.
+    initial_positions: Vec<Vec3>,
+    /// UV-space triangle grid for `uv_to_world` (rebuilt with the constraints).
+    uv_lookup: UvLookup,
+    /// Scale factor used to tune constraint compliance (kept for rebuilds).
+    scale_factor: f32,
+    /// Substep count used by the last `step` call.
+    last_substeps: usize,
+    /// Substep duration used by the last `step` call (0 before the first step).
+    last_sdt: f32,
+    /// Positions at the start of the current frame (for sleep detection).
+    frame_start_positions: Vec<Vec4>,
+    /// Config the last frame ran with. A change pushes the grab/attachment stiffness
+    /// into their constraints and wakes every particle, since sleeping ones skip
+    /// integration and would miss new wind, gravity, etc.
+    applied_config: PhysicsConfig,
+    /// Per-region solver iteration multipliers, re-applied after rebuilds.
+    region_multipliers: Vec<u32>,
+    /// Tethers added or re-moded through the API (original order), replayed in order
+    /// after rebuilds.
+    tether_edits: Vec<TetherEdit>,
+    /// Per-vertex stiffness (original input order), re-applied after rebuilds.
+    stiffness_map: Option<Vec<f32>>,
+    /// Non-finite particles repaired by the last `step` call.
+    last_nan_count: usize,
+    /// Last frame's output normals (scratch for `normal_smoothing`).
+    prev_normals: Vec<Vec4>,
+    /// Reference direction for consistently oriented normals (`None` = authored winding).
+    normal_up: Option<Vec3>,
+    /// Index buffer with consistent winding, used for normals when `normal_up` is set.
+    normal_indices: Vec<u32>,
 }
 
 impl Simulation {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         garment_pos: Vec<f32>,
         garment_indices: Vec<u32>,
@@ -48,117 +102,1243 @@ impl Simulation {
         collider_inflation: f32,
         scale_factor: f32,
     ) -> Self {
-        let state = PhysicsState::new(&garment_pos, &garment_indices, &garment_uvs);
-        let particle_count = state.count;
+        Self::with_config(
+            garment_pos,
+            garment_indices,
+            garment_uvs,
+            collider_pos,
+            collider_normals,
+            collider_indices,
+            collider_smoothing,
+            collider_inflation,
+            scale_factor,
+            PhysicsConfig::default(),
+        )
+    }
+
+    /// Validating constructor: checks the mesh buffers and the configuration
+    /// and reports which one is malformed instead of panicking.
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_with_config(
+        garment_pos: Vec<f32>,
+        garment_indices: Vec<u32>,
+        garment_uvs: Vec<f32>,
+        collider_pos: Vec<f32>,
+        collider_normals: Vec<f32>,
+        collider_indices: Vec<u32>,
+        collider_smoothing: usize,
+        collider_inflation: f32,
+        scale_factor: f32,
+        config: PhysicsConfig,
+    ) -> Result<Self, String> {
+        validate_mesh("garment", &garment_pos, &garment_indices)?;
+        let vertex_count = garment_pos.len() / 3;
+        if garment_uvs.len() != vertex_count * 2 {
+            return Err(format!(
+                "garment_uvs: expected {} floats (2 per vertex), got {}",
+                vertex_count * 2,
+                garment_uvs.len()
+            ));
+        }
 
-        let config = PhysicsConfig::default();
+        validate_collider(&collider_pos, &collider_normals, &collider_indices)?;
 
-        let collider = MeshCollider::new(
+        if !(scale_factor.is_finite() && scale_factor > 0.0) {
+            return Err(format!("scale_factor must be > 0, got {}", scale_factor));
+        }
+        config.validate()?;
+
+        Ok(Self::with_config(
+            garment_pos,
+            garment_indices,
+            garment_uvs,
             collider_pos,
             collider_normals,
             collider_indices,
             collider_smoothing,
             collider_inflation,
+            scale_factor,
+            config,
+        ))
+    }
+
+    /// Same as `new`, but starts from the given configuration instead of the defaults.
+    /// Assumes well-formed buffers; use `try_with_config` for untrusted input.
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_config(
+        garment_pos: Vec<f32>,
+        garment_indices: Vec<u32>,
+        garment_uvs: Vec<f32>,
+        collider_pos: Vec<f32>,
+        collider_normals: Vec<f32>,
+        collider_indices: Vec<u32>,
+        collider_smoothing: usize,
+        collider_inflation: f32,
+        scale_factor: f32,
+        config: PhysicsConfig,
+    ) -> Self {
+        let mut state = PhysicsState::new(&garment_pos, &garment_indices, &garment_uvs);
+        if config.mass_from_area {
+            state.apply_area_density(config.density);
+        }
+        let orphans = state.orphan_vertices();
+        if !orphans.is_empty() {
+            crate::console_log!(
+                "[physics] {} garment vertices are not used by any triangle (first: {})",
+                orphans.len(),
+                orphans[0]
+            );
+            if config.pin_orphan_vertices {
+                for &i in &orphans {
+                    state.inv_mass[i] = 0.0;
+                }
+            }
+        }
+        let particle_count = state.count;
+        let initial_positions = state.positions.iter().map(|p| p.truncate()).collect();
+
+        let mut collider = MeshCollider::with_smoothing(
+            collider_pos,
+            collider_normals,
+            collider_indices,
+            collider_smoothing,
+            config.collider_smoothing_lambda,
+            collider_inflation,
         );
+        collider.set_cell_size(config.collider_cell_size);
+        collider.use_bvh(config.collider_bvh);
+        collider.set_angle_weighted_normals(config.angle_weighted_normals);
 
         let resolver = CollisionResolver::new(particle_count);
         let aerodynamics = Aerodynamics::new(particle_count);
+        let external_forces = ExternalForces::new(particle_count);
 
-        let solver = Solver::new(&state, scale_factor, config.distance_compliance);
-        let mouse = MouseConstraint::new();
+        let solver = Solver::new(&state, scale_factor, config.effective_distance_compliance());
+        let mut mouse = MouseConstraint::new();
+        mouse.set_stiffness(config.grab_stiffness);
+        let mut group_grab = GroupGrabConstraint::new();
+        group_grab.set_stiffness(config.grab_stiffness);
 
         let self_collision_config = SelfCollisionConfig {
-            thickness: config.self_collision_thickness,
+            thickness: config.self_contact_distance(),
             stiffness: config.self_collision_stiffness,
             frequency: config.self_collision_frequency,
-            max_pairs: 10000,
+            max_pairs: config.self_collision_max_pairs,
+            ..Default::default()
         };
         let self_collision = SelfCollision::new(&state, self_collision_config);
+        let uv_lookup = UvLookup::new(&state.uvs, &state.indices);
 
         Self {
             state,
+            applied_config: config.clone(),
             config,
             collider,
+            sdf_collider: None,
             resolver,
             solver,
             aerodynamics,
+            external_forces,
             mouse,
+            group_grab,
+            attachments: AttachmentConstraint::default(),
             self_collision,
             substep_counter: 0,
             frame_count: 0,
+            sim_time: 0.0,
+            initial_positions,
+            uv_lookup,
+            time_accumulator: 0.0,
+            strongest_impact: None,
+            scale_factor,
+            last_substeps: 0,
+            last_sdt: 0.0,
+            frame_start_positions: Vec::new(),
+            region_multipliers: Vec::new(),
+            tether_edits: Vec::new(),
+            stiffness_map: None,
+            last_nan_count: 0,
+            prev_normals: Vec::new(),
+            normal_up: None,
+            normal_indices: Vec::new(),
+        }
+    }
+
+    /// Updates sleep counters from this frame's displacement.
+    /// A particle sleeps once it and all of its 1-ring neighbors have been still
+    /// for `sleep_frames` frames, so a moving neighbor keeps (or wakes) it.
+    fn update_sleep_state(&mut self) {
+        let threshold_sq = self.config.sleep_threshold * self.config.sleep_threshold;
+        let frames = self.config.sleep_frames;
+        let state = &mut self.state;
+
+        for i in 0..state.count {
+            let disp_sq = (state.positions[i] - self.frame_start_positions[i]).length_squared();
+            if disp_sq < threshold_sq {
+                state.still_frames[i] = state.still_frames[i].saturating_add(1);
+            } else {
+                state.still_frames[i] = 0;
+            }
+        }
+
+        for i in 0..state.count {
+            let settled = state.still_frames[i] >= frames
+                && state
+                    .topology
+                    .neighbors(i)
+                    .iter()
+                    .all(|&n| state.still_frames[n as usize] >= frames);
+
+            if settled && state.awake[i] {
+                // Kill residual velocity so the particle does not jump when woken
+                state.prev_positions[i] = state.positions[i];
+            }
+            state.awake[i] = !settled;
+        }
+    }
+
+    /// Applies settings changed through `config` since the last frame: the spring
+    /// stiffnesses are re-mapped only when they changed (so a compliance set directly
+    /// on a constraint survives other edits), and sleeping particles are woken.
+    fn apply_config_changes(&mut self) {
+        if self.config.grab_stiffness != self.applied_config.grab_stiffness {
+            self.mouse.set_stiffness(self.config.grab_stiffness);
+            self.group_grab.set_stiffness(self.config.grab_stiffness);
+        }
+        if self.config.attachment_stiffness != self.applied_config.attachment_stiffness {
+            self.attachments.set_stiffness(self.config.attachment_stiffness);
+        }
+        self.collider.smoothing_lambda = self.config.collider_smoothing_lambda;
+        self.state.wake_all();
+        self.applied_config.clone_from(&self.config);
+    }
+
+    /// Wakes sleeping particles that something pushes on: a contact inside the collider
+    /// surface (e.g. a new SDF collider overlapping resting cloth) or a self-collision
+    /// pair with an awake particle. Resting cloth sits outside the surface at its contact
+    /// gap, so plain resting contacts do not keep it awake.
+    fn wake_touched_particles(
+        state: &mut PhysicsState,
+        resolver: &CollisionResolver,
+        self_collision: &SelfCollision,
+    ) {
+        for c in &resolver.contacts {
+            let i = c.particle_index;
+            if !state.awake[i] && (state.positions[i].truncate() - c.surface_point).dot(c.normal) < 0.0 {
+                state.wake(i);
+            }
+        }
+        for (i, j) in self_collision.pairs() {
+            if state.awake[i] != state.awake[j] {
+                state.wake(i);
+                state.wake(j);
+            }
+        }
+    }
+
+    /// Body contacts from the last `step`, 7 floats per contact:
+    /// `[particle_index, surface_x, surface_y, surface_z, normal_x, normal_y, normal_z]`,
+    /// with the particle index mapped back to original input order.
+    pub fn contacts_flat(&self) -> Vec<f32> {
+        let contacts = self.resolver.contacts();
+        let mut out = Vec::with_capacity(contacts.len() * 7);
+        for c in contacts {
+            out.push(self.state.original_index[c.particle_index] as f32);
+            out.extend_from_slice(&c.surface_point.to_array());
+            out.extend_from_slice(&c.normal.to_array());
+        }
+        out
+    }
+
+    /// Total kinetic energy `sum(0.5 * m * v^2)` using the Verlet velocity of the last substep.
+    /// Pinned particles (inv_mass 0) contribute nothing. Returns 0 before the first step.
+    pub fn kinetic_energy(&self) -> f32 {
+        if self.last_sdt <= 0.0 {
+            return 0.0;
+        }
+
+        let inv_dt_sq = 1.0 / (self.last_sdt * self.last_sdt);
+        let mut energy = 0.0;
+        for i in 0..self.state.count {
+            let w = self.state.inv_mass[i];
+            if w == 0.0 {
+                continue;
+            }
+            let disp = self.state.positions[i] - self.state.prev_positions[i];
+            energy += 0.5 * (1.0 / w) * disp.length_squared() * inv_dt_sq;
+        }
+        energy
+    }
+
+    /// Garment vertices no triangle references (original input order, ascending).
+    /// Pinned at construction unless `config.pin_orphan_vertices` is off.
+    pub fn orphan_vertices(&self) -> Vec<u32> {
+        let mut orphans: Vec<u32> = self
+            .state
+            .orphan_vertices()
+            .into_iter()
+            .map(|i| self.state.original_index[i])
+            .collect();
+        orphans.sort_unstable();
+        orphans
+    }
+
+    /// Closest particle (internal index) to the world point `p`, or `None` for an empty mesh.
+    /// Uses the self-collision hash when it is populated; otherwise (self-collision off,
+    /// limited to a region, before the first step, or `p` far from the cloth) scans
+    /// every particle.
+    pub fn nearest_vertex(&self, p: Vec3) -> Option<usize> {
+        let hash_usable = self.config.self_collision_enabled
+            && self.self_collision.config.region.is_none()
+            && self.frame_count > 0;
+        if hash_usable && let Some(i) = self.self_collision.nearest_particle(&self.state, p) {
+            return Some(i);
+        }
+
+        self.state
+            .positions
+            .iter()
+            .map(|q| q.truncate().distance_squared(p))
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+
+    /// Current world position of the cloth point with texture coordinate `uv`, found by
+    /// locating the triangle containing `uv` and interpolating its corners. Follows the
+    /// drape, so a decal placed in UV space tracks the folds. `None` outside the UV islands.
+    pub fn uv_to_world(&self, uv: Vec2) -> Option<Vec3> {
+        let state = &self.state;
+        let (t, bary) = self.uv_lookup.locate(&state.uvs, &state.indices, uv)?;
+        let corners = &state.indices[t * 3..t * 3 + 3];
+        Some(
+            (0..3)
+                .map(|k| state.positions[corners[k] as usize].truncate() * bary[k])
+                .sum(),
+        )
+    }
+
+    /// Number of particles currently asleep.
+    pub fn sleeping_count(&self) -> usize {
+        self.state.awake.iter().filter(|&&a| !a).count()
+    }
+
+    /// Hardest impact against the body during the last `step` call, as
+    /// `(particle in original input order, inbound normal speed in m/s)`.
+    /// Meant for feedback such as a sound when cloth slaps the body.
+    pub fn strongest_impact(&self) -> Option<(usize, f32)> {
+        self.strongest_impact
+            .map(|(i, speed)| (self.state.original_index[i] as usize, speed))
+    }
+
+    /// Number of frames simulated so far (one per `step` call unless `fixed_dt` is set).
+    pub fn frame_count(&self) -> u32 {
+        self.frame_count
+    }
+
+    /// Total simulated time in seconds (the accumulated `step` dt).
+    pub fn sim_time(&self) -> f64 {
+        self.sim_time
+    }
+
+    /// Returns the substep count used by the most recent `step`.
+    pub fn last_substeps(&self) -> usize {
+        self.last_substeps
+    }
+
+    /// Distance residual (sum of |C|) after each solver iteration of the last frame,
+    /// summed over substeps. Empty unless `config.track_convergence` is set.
+    pub fn convergence(&self) -> &[f32] {
+        &self.solver.convergence
+    }
+
+    /// Returns how many non-finite particles the last `step` had to repair (0 = stable).
+    pub fn nan_count(&self) -> usize {
+        self.last_nan_count
+    }
+
+    /// Repairs particles whose position became NaN/inf (e.g. after an extreme drag).
+    /// Each one is put back at its last finite position (or, if that is bad too, the
+    /// average of its finite 1-ring neighbors, then its frame-start or rest position)
+    /// with zero velocity.
+    /// Returns the number of repaired particles.
+    fn recover_non_finite(&mut self) -> usize {
+        let state = &mut self.state;
+        let mut bad = Vec::new();
+        for i in 0..state.count {
+            if !state.positions[i].is_finite() {
+                bad.push(i);
+            }
+        }
+        if bad.is_empty() {
+            return 0;
+        }
+
+        // Pass 1: last substep's position (stable for everything that blew up this substep)
+        let mut unresolved = Vec::new();
+        for &i in &bad {
+            let prev = state.prev_positions[i];
+            if prev.is_finite() {
+                state.positions[i] = prev;
+            } else {
+                unresolved.push(i);
+            }
+        }
+
+        // Pass 2: neighbors repaired in pass 1 for anything without a usable history,
+        // then the last good frame-start position (kept while sleeping is enabled)
+        // or the rest position - never the world origin
+        let fallback: Vec<Vec4> = unresolved
+            .iter()
+            .map(|&i| {
+                let mut sum = Vec4::ZERO;
+                let mut n = 0;
+                for &j in state.topology.neighbors(i) {
+                    let p = state.positions[j as usize];
+                    if p.is_finite() {
+                        sum += p;
+                        n += 1;
+                    }
+                }
+                if n > 0 {
+                    sum / n as f32
+                } else {
+                    self.frame_start_positions
+                        .get(i)
+                        .copied()
+                        .filter(|p| p.is_finite())
+                        .unwrap_or(state.rest_positions[i])
+                }
+            })
+            .collect();
+        for (&i, p) in unresolved.iter().zip(fallback) {
+            state.positions[i] = p;
+        }
+
+        for &i in &bad {
+            state.prev_positions[i] = state.positions[i];
+            state.wake(i);
+        }
+        bad.len()
+    }
+
+    /// Chooses a substep count so that no particle moves more than `contact_thickness`
+    /// per substep, based on the Verlet displacement from the previous substep.
+    /// Clamped to `[1, config.max_substeps]`.
+    fn adaptive_substep_count(&self, dt: f32) -> usize {
+        let max_substeps = self.config.max_substeps.max(1);
+        if self.last_sdt <= 0.0 {
+            return self.config.substeps.clamp(1, max_substeps);
+        }
+
+        let mut max_disp_sq: f32 = 0.0;
+        for i in 0..self.state.count {
+            if self.state.inv_mass[i] == 0.0 {
+                continue;
+            }
+            let disp = self.state.positions[i] - self.state.prev_positions[i];
+            max_disp_sq = max_disp_sq.max(disp.length_squared());
+        }
+
+        // Max speed -> distance travelled over the whole frame
+        let max_speed = max_disp_sq.sqrt() / self.last_sdt;
+        let frame_travel = max_speed * dt;
+        let needed = (frame_travel / self.config.contact_thickness.max(1e-6)).ceil() as usize;
+
+        needed.clamp(1, max_substeps)
+    }
+
+    /// Rebuilds all topology-dependent systems (constraints, self-collision exclusion)
+    /// from the current state. Call after the particle layout or topology changes.
+    /// Solver plugins are kept; ones holding particle indices must remap them themselves.
+    pub fn rebuild_systems(&mut self) {
+        self.refresh_normal_indices();
+        let plugins = std::mem::take(&mut self.solver.plugins);
+        self.solver = Solver::new(&self.state, self.scale_factor, self.config.effective_distance_compliance());
+        self.solver.plugins = plugins;
+        self.self_collision = SelfCollision::new(&self.state, self.self_collision.config);
+        let one_sided = self.resolver.one_sided;
+        self.resolver = CollisionResolver::new(self.state.count);
+        self.resolver.one_sided = one_sided;
+        self.frame_start_positions.clear();
+        // Keep the turbulence clock so gusts don't restart from t = 0
+        let wind_time = self.aerodynamics.time();
+        self.aerodynamics = Aerodynamics::new(self.state.count);
+        self.aerodynamics.advance_time(wind_time);
+        self.external_forces = ExternalForces::new(self.state.count);
+        self.uv_lookup = UvLookup::new(&self.state.uvs, &self.state.indices);
+        self.apply_stiffness_map();
+        for k in 0..self.tether_edits.len() {
+            self.apply_tether_edit(self.tether_edits[k]);
+        }
+        self.solver.set_region_iterations(&self.state, &self.region_multipliers);
+    }
+
+    /// Computes normals from a consistently wound copy of the mesh, with every connected
+    /// piece facing `up` (`None` restores the authored winding). Meant for open,
+    /// double-sided garments (scarves, capes) whose normals are otherwise unreliable.
+    /// Tethers are regenerated from the re-oriented normals; call before the first step.
+    pub fn set_normal_orientation(&mut self, up: Option<Vec3>) {
+        self.normal_up = up;
+        self.refresh_normal_indices();
+        if up.is_some() {
+            // Tether generation compares normals, so give it real, oriented ones
+            normals::compute_vertex_normals(
+                &self.state.positions,
+                &self.normal_indices,
+                &mut self.state.normals,
+            );
+        }
+        self.rebuild_systems();
+    }
+
+    fn refresh_normal_indices(&mut self) {
+        self.normal_indices = match self.normal_up {
+            Some(up) => normals::orient_indices(
+                &self.state.positions,
+                &self.state.indices,
+                &self.state.topology,
+                up,
+            ),
+            None => Vec::new(),
+        };
+    }
+
+    /// Sets a painted per-vertex stiffness map (0 = soft, 1 = stiff, original input order).
+    /// Each edge's compliance becomes `(1 - avg stiffness) * config.stiffness_map_scale`,
+    /// never stiffer than the configured distance compliance.
+    pub fn set_stiffness_map(&mut self, stiffness: Vec<f32>) -> Result<(), String> {
+        if stiffness.len() != self.state.count {
+            return Err(format!(
+                "stiffness_map: expected {} values (1 per vertex), got {}",
+                self.state.count,
+                stiffness.len()
+            ));
+        }
+        if let Some(k) = stiffness.iter().position(|s| !s.is_finite()) {
+            return Err(format!("stiffness_map: non-finite value at index {}", k));
+        }
+        self.stiffness_map = Some(stiffness);
+        self.apply_stiffness_map();
+        Ok(())
+    }
+
+    /// Removes the stiffness map; every edge goes back to the configured distance compliance.
+    pub fn clear_stiffness_map(&mut self) {
+        self.stiffness_map = None;
+        self.refresh_distance_compliance();
+    }
+
+    /// Sets the edge stretch stiffness in N/m (0 = fall back to the raw compliance).
+    /// Independent of substep count and frame rate; the stiffness map still applies on top.
+    pub fn set_distance_stiffness(&mut self, stiffness: f32) {
+        self.config.distance_stiffness = stiffness.max(0.0);
+        self.refresh_distance_compliance();
+    }
+
+    /// Sets the raw XPBD distance compliance (m/N, clamped to >= 0). Only used while no
+    /// `distance_stiffness` is set.
+    pub fn set_distance_compliance(&mut self, compliance: f32) {
+        self.config.distance_compliance = compliance.max(0.0);
+        self.refresh_distance_compliance();
+    }
+
+    fn refresh_distance_compliance(&mut self) {
+        if self.stiffness_map.is_some() {
+            self.apply_stiffness_map();
+        } else {
+            self.solver
+                .distance_constraint
+                .compliances
+                .fill(self.config.effective_distance_compliance());
+        }
+    }
+
+    fn apply_stiffness_map(&mut self) {
+        if let Some(map) = &self.stiffness_map {
+            // Map is stored in original order; constraints use internal indices
+            let internal: Vec<f32> = (0..self.state.count)
+                .map(|i| map[self.state.original_index[i] as usize])
+                .collect();
+            self.solver.distance_constraint.apply_stiffness_map(
+                &internal,
+                self.config.stiffness_map_scale,
+                self.config.effective_distance_compliance(),
+            );
+        }
+    }
+
+    /// Reorders particles by the Morton code of their current (rest) positions so that
+    /// constraint solving touches memory in a spatially coherent order.
+    /// Intended to be called once, right after construction.
+    /// Use `state.original_index` / `state.internal_index` to map between orders.
+    pub fn reorder_particles(&mut self) {
+        let order = self.state.morton_order();
+        let original = self.state.original_index.clone();
+        self.state.apply_permutation(&order);
+        self.mouse.release();
+        self.group_grab.release();
+        let internal = &self.state.internal_index;
+        self.attachments.remap(|i| internal[original[i] as usize] as usize);
+        self.rebuild_systems();
+    }
+
+    /// Refines the garment by one level of midpoint subdivision (4x the triangles) and
+    /// rebuilds every constraint and the self-collision exclusion for the new mesh.
+    /// New vertices are appended after the existing ones (original order `count + edge`)
+    /// and inherit pinning, layers, regions and stiffness from their parent edge.
+    /// Grabs are released; attachments keep their (unchanged) particles.
+    pub fn subdivide(&mut self) {
+        let parents = self.state.subdivide();
+        if self.config.mass_from_area {
+            self.state.apply_area_density(self.config.density);
+        }
+        let original = &self.state.original_index;
+        for &[a, b] in &parents {
+            let (pa, pb) = (original[a] as usize, original[b] as usize);
+            let mid = (self.initial_positions[pa] + self.initial_positions[pb]) * 0.5;
+            self.initial_positions.push(mid);
+        }
+        if let Some(map) = &mut self.stiffness_map {
+            for &[a, b] in &parents {
+                let s = 0.5 * (map[original[a] as usize] + map[original[b] as usize]);
+                map.push(s);
+            }
         }
+        self.mouse.release();
+        self.group_grab.release();
+        self.prev_normals.clear();
+        self.rebuild_systems();
+    }
+
+    /// Puts every particle back at its starting pose, at rest, and restarts the frame
+    /// counter, the simulated time and the gravity ramp. Pins, attachments, plugins and
+    /// settings are kept; grabs and queued forces are dropped.
+    pub fn reset(&mut self) {
+        for (orig, &p) in self.initial_positions.iter().enumerate() {
+            let i = self.state.internal_index[orig] as usize;
+            self.state.positions[i] = Vec4::from((p, 0.0));
+            self.state.prev_positions[i] = self.state.positions[i];
+        }
+        self.state.wake_all();
+        self.mouse.release();
+        self.group_grab.release();
+        self.external_forces.clear();
+        self.resolver.contacts.clear();
+        self.resolver.update_contact_mask();
+        self.strongest_impact = None;
+        self.frame_count = 0;
+        self.sim_time = 0.0;
+        self.time_accumulator = 0.0;
+        self.substep_counter = 0;
+        self.frame_start_positions.clear();
+        self.prev_normals.clear();
     }
 
     /// Advances the simulation by `dt` seconds.
     /// Uses fixed sub-stepping with SIMD-accelerated constraint solving.
     ///
     /// PROFILING: Each phase is instrumented for performance analysis.
+    ///
+    /// With `config.fixed_dt` set, `dt` is accumulated instead and zero or more frames of
+    /// exactly `fixed_dt` are run (at most `MAX_FIXED_FRAMES`; older backlog is dropped).
     pub fn step(&mut self, dt: f32) {
+        self.advance(dt, None);
+    }
+
+    /// Steps by `dt` until `kinetic_energy()` drops below `energy_threshold` or
+    /// `max_frames` steps have run, and returns the number of steps taken.
+    /// Energy is checked after each step, so at least one step runs (if `max_frames > 0`).
+    pub fn settle(&mut self, dt: f32, max_frames: u32, energy_threshold: f32) -> u32 {
+        for frame in 1..=max_frames {
+            self.step(dt);
+            if self.kinetic_energy() < energy_threshold {
+                return frame;
+            }
+        }
+        max_frames
+    }
+
+    /// Same as `step`, but collides against `collider` instead of the owned one.
+    /// Lets several garments share a single body (see `MultiSimulation`).
+    pub fn step_with_collider(&mut self, dt: f32, collider: &mut MeshCollider) {
+        self.advance(dt, Some(collider));
+    }
+
+    fn advance(&mut self, dt: f32, mut shared_collider: Option<&mut MeshCollider>) {
+        self.strongest_impact = None;
+        let fixed_dt = self.config.fixed_dt;
+        if fixed_dt <= 0.0 {
+            self.step_frame(dt, shared_collider);
+            return;
+        }
+
+        self.time_accumulator += dt.max(0.0);
+        // The epsilon keeps `dt == fixed_dt` from missing its frame to rounding
+        let frames = (self.time_accumulator / fixed_dt + 1e-4).floor() as usize;
+        if frames > MAX_FIXED_FRAMES {
+            // A long stall (tab in background, breakpoint): catch up a little, drop the rest
+            self.time_accumulator = 0.0;
+        } else {
+            self.time_accumulator = (self.time_accumulator - frames as f32 * fixed_dt).max(0.0);
+        }
+        for _ in 0..frames.min(MAX_FIXED_FRAMES) {
+            self.step_frame(fixed_dt, shared_collider.as_deref_mut());
+        }
+    }
+
+    fn step_frame(&mut self, dt: f32, shared_collider: Option<&mut MeshCollider>) {
         Profiler::begin_frame();
 
-        // Use fixed substeps from config (no adaptive)
-        let sdt = dt / self.config.substeps as f32;
+        if self.config != self.applied_config {
+            self.apply_config_changes();
+        }
+
+        if self.config.sleep_enabled {
+            // A grabbed particle is always awake
+            if let Some(idx) = self.mouse.grabbed_index
+                && idx < self.state.count
+            {
+                self.state.wake(idx);
+            }
+            for &idx in &self.group_grab.indices {
+                self.state.wake(idx);
+            }
+            self.frame_start_positions.clear();
+            self.frame_start_positions.extend_from_slice(&self.state.positions);
+        }
+
+        let substeps = if self.config.adaptive_substeps {
+            self.adaptive_substep_count(dt)
+        } else {
+            self.config.substeps
+        };
+        let sdt = dt / substeps as f32;
+
+        // Verlet stores velocity as a displacement per substep; rescale it when
+        // the adaptive substep length changes so speed is preserved.
+        if self.config.adaptive_substeps && self.last_sdt > 0.0 && sdt != self.last_sdt {
+            let ratio = sdt / self.last_sdt;
+            for i in 0..self.state.count {
+                let pos = self.state.positions[i];
+                let prev = self.state.prev_positions[i];
+                self.state.prev_positions[i] = pos - (pos - prev) * ratio;
+            }
+        }
+
+        let collider = match shared_collider {
+            Some(collider) => collider,
+            None => &mut self.collider,
+        };
 
         // Broad-phase collision detection (once per frame)
         Profiler::start(ProfileCategory::BroadPhase);
-        self.resolver.broad_phase(&self.state, &mut self.collider);
+        self.resolver
+            .broad_phase(&self.state, collider, &self.config, substeps);
         Profiler::end(ProfileCategory::BroadPhase);
 
         // Narrow-phase collision detection (Once per frame)
         Profiler::start(ProfileCategory::NarrowPhase);
         self.resolver
-            .narrow_phase(&mut self.state, &self.collider, &self.config, sdt);
+            .narrow_phase(&mut self.state, collider, &self.config, sdt);
+        if let Some(sdf) = &self.sdf_collider {
+            sdf.collect_contacts(&self.state, &self.config, sdt, &mut self.resolver.contacts);
+        }
+        self.resolver.update_contact_mask();
+        if self.config.sleep_enabled {
+            Self::wake_touched_particles(&mut self.state, &self.resolver, &self.self_collision);
+        }
+        if let Some((i, speed)) = self.resolver.strongest_impact()
+            && self.strongest_impact.is_none_or(|(_, best)| speed > best)
+        {
+            self.strongest_impact = Some((i, speed));
+        }
+        Profiler::set_counter(ProfileCounter::Contacts, self.resolver.contacts.len() as u64);
         Profiler::end(ProfileCategory::NarrowPhase);
 
         // External forces (aerodynamics) - Decimated (Once per frame)
         Profiler::start(ProfileCategory::Aerodynamics);
-        let forces = self.aerodynamics.apply(&self.state, &self.config, sdt);
+        self.aerodynamics.advance_time(dt);
+        self.aerodynamics.update_occlusion(&self.state, &self.config, collider);
+        let aero = self.aerodynamics.apply(&self.state, &self.config, sdt);
+        let forces = self.external_forces.combine(aero);
         Profiler::end(ProfileCategory::Aerodynamics);
 
-        for _ in 0..self.config.substeps {
+        if self.config.track_convergence {
+            self.solver.reset_convergence(self.config.solver_iterations);
+        }
+
+        let ramp = self.config.gravity_ramp_frames;
+        let gravity = if self.frame_count < ramp {
+            self.config.gravity * (self.frame_count as f32 / ramp as f32)
+        } else {
+            self.config.gravity
+        };
+
+        for substep in 0..substeps {
+            Profiler::begin_substep();
+
             // Integration (updates positions based on velocity and forces)
             Profiler::start(ProfileCategory::Integration);
-            Integrator::integrate(&mut self.state, &self.config, forces, sdt);
+            Integrator::integrate_with_gravity(&mut self.state, &self.config, forces, gravity, sdt);
             Profiler::end(ProfileCategory::Integration);
 
-            // Mouse interaction
+            // Mouse interaction (swept against the collider so fast drags do not tunnel)
             Profiler::start(ProfileCategory::MouseConstraint);
+            let grab_start = self
+                .mouse
+                .grabbed_index
+                .filter(|&idx| idx < self.state.count)
+                .map(|idx| self.state.prev_positions[idx].truncate());
             self.mouse.solve(&mut self.state, sdt);
+            if let Some(from) = grab_start {
+                self.mouse
+                    .sweep(&mut self.state, collider, from, self.config.body_contact_distance());
+            }
+            self.group_grab.solve(&mut self.state, sdt);
             Profiler::end(ProfileCategory::MouseConstraint);
 
             // SIMD-accelerated constraint solving
             Profiler::start(ProfileCategory::Constraints);
+            self.attachments.solve(&mut self.state, collider, sdt);
             self.solver
                 .solve(&mut self.state, &self.resolver, &self.config, sdt);
             Profiler::end(ProfileCategory::Constraints);
 
             // Self-collision at reduced frequency for performance
             if self.config.self_collision_enabled {
-                let freq = self.self_collision.config.frequency as u32;
-                if freq == 0 || self.substep_counter % freq == 0 {
+                // Read every substep so `set_self_collision_frequency` applies immediately;
+                // 0 is treated like 1 (every substep)
+                let freq = (self.self_collision.config.frequency as u32).max(1);
+                if self.substep_counter.is_multiple_of(freq) {
                     Profiler::start(ProfileCategory::SelfCollision);
                     self.self_collision.solve(&mut self.state);
                     Profiler::end(ProfileCategory::SelfCollision);
                 }
             }
             self.substep_counter = self.substep_counter.wrapping_add(1);
+            Profiler::end_substep(substep);
+        }
+        self.external_forces.clear();
+
+        // Keep a numerical blow-up from wiping out the whole garment
+        self.last_nan_count = self.recover_non_finite();
+        if self.last_nan_count > 0 {
+            crate::console_log!("[physics] Recovered {} non-finite particles", self.last_nan_count);
         }
 
         // Compute vertex normals in WASM
         Profiler::start(ProfileCategory::Normals);
-        normals::compute_vertex_normals(
+        // The first frame has no history (normals start as +Y placeholders)
+        let smoothing = if self.frame_count > 0 { self.config.normal_smoothing } else { 0.0 };
+        if smoothing > 0.0 {
+            self.prev_normals.clear();
+            self.prev_normals.extend_from_slice(&self.state.normals);
+        }
+        let normal_indices = if self.normal_up.is_some() {
+            &self.normal_indices
+        } else {
+            &self.state.indices
+        };
+        normals::compute_vertex_normals_weighted(
             &self.state.positions,
-            &self.state.indices,
+            normal_indices,
             &mut self.state.normals,
+            self.config.angle_weighted_normals,
         );
+        normals::blend_normals(&mut self.state.normals, &self.prev_normals, smoothing);
         Profiler::end(ProfileCategory::Normals);
 
+        if self.config.sleep_enabled {
+            self.update_sleep_state();
+        }
+
         self.frame_count = self.frame_count.wrapping_add(1);
+        self.sim_time += dt as f64;
+        self.last_substeps = substeps;
+        self.last_sdt = sdt;
 
         Profiler::end_frame();
     }
 
     pub fn update_collider(&mut self, positions: &[f32]) {
         self.collider.update(positions);
+        // A moving body may push resting cloth
+        self.state.wake_all();
+    }
+
+    /// Offsets the collider surface along its normals by `inflation` (meters; may be
+    /// negative). Use it to tune the gap when cloth sinks into the body.
+    pub fn set_collider_inflation(&mut self, inflation: f32) {
+        self.collider.set_inflation(inflation);
+        self.state.wake_all();
+    }
+
+    /// Switches the collider broad phase between the uniform grid and a BVH.
+    pub fn set_collider_bvh(&mut self, enabled: bool) {
+        self.config.collider_bvh = enabled;
+        self.collider.use_bvh(enabled);
+    }
+
+    /// Sets the collider grid cell size in meters (0.0 = derive from the mean edge length;
+    /// anything else is raised to at least `MIN_CELL_SIZE`).
+    pub fn set_collider_cell_size(&mut self, cell_size: f32) {
+        self.config.collider_cell_size = if cell_size > 0.0 { cell_size.max(MIN_CELL_SIZE) } else { 0.0 };
+        self.collider.set_cell_size(self.config.collider_cell_size);
+    }
+
+    /// Switches cloth output and collider normals between area- and angle-weighted.
+    pub fn set_angle_weighted_normals(&mut self, enabled: bool) {
+        self.config.angle_weighted_normals = enabled;
+        self.collider.set_angle_weighted_normals(enabled);
+    }
+
+    /// Enables or disables particle sleeping. Disabling wakes every particle.
+    pub fn set_sleep_enabled(&mut self, enabled: bool) {
+        self.config.sleep_enabled = enabled;
+        if !enabled {
+            self.state.wake_all();
+        }
+    }
+
+    /// Sets the aerodynamic drag and lift coefficients (clamped to >= 0).
+    /// `Aerodynamics::apply` reads them from `config` every frame, so no rebuild is needed.
+    pub fn set_aero_coefficients(&mut self, drag: f32, lift: f32) {
+        self.config.drag_coeff = drag.max(0.0);
+        self.config.lift_coeff = lift.max(0.0);
+    }
+
+    /// Changes the cloth-body offset. The narrow phase (velocity clamp) and contact
+    /// resolution read it from `config` every step, so no collider rebuild is needed.
+    /// The visible gap is `contact_thickness` + `cloth_radius` + the collider's `inflation_amount`.
+    pub fn set_contact_thickness(&mut self, thickness: f32) {
+        self.config.contact_thickness = thickness.max(1e-5);
+    }
+
+    /// Changes the self-collision separation distance, re-gridding the spatial hash.
+    pub fn set_self_collision_thickness(&mut self, thickness: f32) {
+        self.config.self_collision_thickness = thickness.max(1e-5);
+        self.self_collision.set_thickness(self.config.self_contact_distance());
+    }
+
+    /// Gives each cloth particle a collision radius (fabric volume, clamped to >= 0).
+    /// Widens both the body gap and the self-collision separation; re-grids the
+    /// self-collision hash.
+    pub fn set_cloth_radius(&mut self, radius: f32) {
+        self.config.cloth_radius = radius.max(0.0);
+        self.self_collision.set_thickness(self.config.self_contact_distance());
+    }
+
+    /// Changes the self-collision repulsion strength (clamped to 0..1).
+    pub fn set_self_collision_stiffness(&mut self, stiffness: f32) {
+        let stiffness = stiffness.clamp(0.0, 1.0);
+        self.config.self_collision_stiffness = stiffness;
+        self.self_collision.config.stiffness = stiffness;
+    }
+
+    /// Runs self-collision every `frequency` substeps from the next substep on (at least 1).
+    /// Lower it when the scene turns chaotic (e.g. the user starts dragging) and raise
+    /// it again once the cloth calms down.
+    pub fn set_self_collision_frequency(&mut self, frequency: u8) {
+        let frequency = frequency.max(1);
+        self.config.self_collision_frequency = frequency;
+        self.self_collision.config.frequency = frequency;
+    }
+
+    /// Changes the per-pass self-collision pair cap (at least 1). Takes effect at the
+    /// next detection pass.
+    pub fn set_self_collision_max_pairs(&mut self, max_pairs: usize) {
+        let max_pairs = max_pairs.max(1);
+        self.config.self_collision_max_pairs = max_pairs;
+        self.self_collision.config.max_pairs = max_pairs;
+    }
+
+    /// Captures positions, prev_positions, inv_mass and the substep counter
+    /// as a versioned binary snapshot (see `engine::snapshot`).
+    pub fn serialize(&self) -> Vec<u8> {
+        snapshot::encode(&self.state, self.substep_counter)
+    }
+
+    /// Restores a snapshot from `serialize`. Fails without modifying anything if the
+    /// snapshot is malformed or the particle count differs from the current mesh.
+    pub fn deserialize(&mut self, bytes: &[u8]) -> Result<(), String> {
+        self.substep_counter = snapshot::decode_into(&mut self.state, bytes)?;
+        self.state.wake_all();
+        self.frame_start_positions.clear();
+        Ok(())
+    }
+
+    /// Declares the source quads of a triangulated garment (4 indices per quad, original
+    /// input order), so distance and bending constraints no longer depend on which
+    /// diagonal the triangulation picked. Rebuilds the constraints from the current
+    /// pose, so call it before the first step.
+    pub fn set_quads(&mut self, quads: &[u32]) -> Result<(), String> {
+        PhysicsState::validate_quads(quads, self.state.count)?;
+        let internal = &self.state.internal_index;
+        self.state.quads = quads
+            .chunks_exact(4)
+            .map(|q| [q[0], q[1], q[2], q[3]].map(|i| internal[i as usize] as usize))
+            .collect();
+        self.rebuild_systems();
+        Ok(())
+    }
+
+    /// Uses `rest_positions` (`[x, y, z, ...]`, original input order) as the rest shape and
+    /// rebuilds the constraints from it, while the current positions keep seeding the
+    /// dynamics. E.g. start from a pre-draped pose but keep the flat pattern's rest lengths.
+    pub fn set_rest_positions(&mut self, rest_positions: &[f32]) -> Result<(), String> {
+        self.state.set_rest_positions(rest_positions)?;
+        if self.config.mass_from_area {
+            self.state.apply_area_density(self.config.density);
+        }
+        self.rebuild_systems();
+        Ok(())
+    }
+
+    /// Queues `force` (Newtons) on particle `index` (original input order) for the next
+    /// frame, on top of gravity and wind. Forces accumulate until that frame consumes them;
+    /// the particle is woken so a sleeping region still reacts.
+    pub fn add_force(&mut self, index: usize, force: Vec3) -> Result<(), String> {
+        if index >= self.state.count {
+            return Err(format!(
+                "add_force: index {} out of range ({} vertices)",
+                index, self.state.count
+            ));
+        }
+        let i = self.state.internal_index[index] as usize;
+        self.external_forces.add(i, force);
+        self.state.wake(i);
+        Ok(())
+    }
+
+    /// Grabs particle `index` (original input order) with the mouse constraint, pulling
+    /// it towards `target`. Replaces any previous grab.
+    pub fn grab(&mut self, index: usize, target: Vec3) -> Result<(), String> {
+        if index >= self.state.count {
+            return Err(format!(
+                "grab: index {} out of range ({} vertices)",
+                index, self.state.count
+            ));
+        }
+        let i = self.state.internal_index[index] as usize;
+        self.mouse.grab(i, target);
+        Ok(())
+    }
+
+    /// Grabs a cluster of particles (original input order) at their current positions;
+    /// `group_grab.translate` then moves them together. Replaces any previous group.
+    pub fn set_group_grab(&mut self, indices: &[u32]) -> Result<(), String> {
+        if let Some(&index) = indices.iter().find(|&&i| i as usize >= self.state.count) {
+            return Err(format!(
+                "set_group_grab: index {} out of range ({} vertices)",
+                index, self.state.count
+            ));
+        }
+        let internal: Vec<usize> = indices
+            .iter()
+            .map(|&i| self.state.internal_index[i as usize] as usize)
+            .collect();
+        self.group_grab.grab(&self.state, &internal);
+        Ok(())
+    }
+
+    /// Sticks the given particles (original input order) to the closest point of the
+    /// collider surface at their current positions, replacing any previous attachments.
+    /// They then follow the body as it animates instead of sliding over it.
+    /// Binds against the owned collider; a garment stepped against a shared collider
+    /// must use `attach_to_shared_collider` (see `MultiSimulation::attach_to_collider`).
+    pub fn attach_to_collider(&mut self, indices: &[u32]) -> Result<(), String> {
+        self.attachments = Self::bind_attachments(&self.state, &self.collider, indices)?;
+        self.attachments.set_stiffness(self.config.attachment_stiffness);
+        Ok(())
+    }
+
+    /// Same as `attach_to_collider`, binding against `collider`, which must be the
+    /// collider this simulation is stepped with (`step_with_collider`).
+    pub fn attach_to_shared_collider(&mut self, indices: &[u32], collider: &MeshCollider) -> Result<(), String> {
+        self.attachments = Self::bind_attachments(&self.state, collider, indices)?;
+        self.attachments.set_stiffness(self.config.attachment_stiffness);
+        Ok(())
+    }
+
+    fn bind_attachments(
+        state: &PhysicsState,
+        collider: &MeshCollider,
+        indices: &[u32],
+    ) -> Result<AttachmentConstraint, String> {
+        if collider.triangles.is_empty() {
+            return Err("attach_to_collider: the collider has no triangles".to_string());
+        }
+        if let Some(&index) = indices.iter().find(|&&i| i as usize >= state.count) {
+            return Err(format!(
+                "attach_to_collider: index {} out of range ({} vertices)",
+                index, state.count
+            ));
+        }
+        let internal: Vec<usize> = indices
+            .iter()
+            .map(|&i| state.internal_index[i as usize] as usize)
+            .collect();
+        Ok(AttachmentConstraint::new(state, collider, &internal))
+    }
+
+    /// Removes every collider attachment.
+    pub fn clear_attachments(&mut self) {
+        self.attachments = AttachmentConstraint::default();
+    }
+
+    /// Assigns self-collision layers, one per particle in original input order
+    /// (missing entries keep their current layer).
+    pub fn set_particle_layers(&mut self, layers: &[u8]) {
+        for (orig, &layer) in layers.iter().enumerate().take(self.state.count) {
+            let i = self.state.internal_index[orig] as usize;
+            self.state.layers[i] = layer;
+        }
+    }
+
+    /// Assigns solver regions, one per particle in original input order
+    /// (missing entries keep their current region).
+    pub fn set_particle_regions(&mut self, regions: &[u8]) {
+        for (orig, &region) in regions.iter().enumerate().take(self.state.count) {
+            let i = self.state.internal_index[orig] as usize;
+            self.state.regions[i] = region;
+        }
+        self.solver.set_region_iterations(&self.state, &self.region_multipliers);
+    }
+
+    /// Solves the constraints touching `region` `multiplier` times per iteration
+    /// (1 = like everything else). See `Solver::set_region_iterations`.
+    pub fn set_region_iterations(&mut self, region: u8, multiplier: u32) {
+        let region = region as usize;
+        if self.region_multipliers.len() <= region {
+            self.region_multipliers.resize(region + 1, 1);
+        }
+        self.region_multipliers[region] = multiplier.max(1);
+        self.solver.set_region_iterations(&self.state, &self.region_multipliers);
+    }
+
+    /// Adds a tether between particles `a` and `b` (original input order) at their rest
+    /// distance, using `group`'s compliance, e.g. a `Bilateral` strut for corset boning.
+    /// Kept across rebuilds.
+    pub fn add_tether(&mut self, a: u32, b: u32, group: TetherGroup, mode: TetherMode) -> Result<(), String> {
+        self.check_tether_pair("add_tether", a, b)?;
+        let edit = TetherEdit::Add([a, b], group, mode);
+        self.apply_tether_edit(edit);
+        self.tether_edits.push(edit);
+        Ok(())
+    }
+
+    /// Sets the mode of the tethers between particles `a` and `b` (original input order).
+    /// Errors if there is no tether between them. Kept across rebuilds.
+    pub fn set_tether_mode(&mut self, a: u32, b: u32, mode: TetherMode) -> Result<(), String> {
+        self.check_tether_pair("set_tether_mode", a, b)?;
+        let edit = TetherEdit::Mode([a, b], mode);
+        if self.apply_tether_edit(edit) == 0 {
+            return Err(format!("set_tether_mode: no tether between {} and {}", a, b));
+        }
+        self.tether_edits.push(edit);
+        Ok(())
+    }
+
+    /// Sets the mode of every tether in `group`, including tethers added to it so far.
+    /// Kept across rebuilds.
+    pub fn set_tether_group_mode(&mut self, group: TetherGroup, mode: TetherMode) {
+        let edit = TetherEdit::GroupMode(group, mode);
+        self.apply_tether_edit(edit);
+        self.tether_edits.push(edit);
+    }
+
+    fn check_tether_pair(&self, name: &str, a: u32, b: u32) -> Result<(), String> {
+        if let Some(index) = [a, b].into_iter().find(|&i| i as usize >= self.state.count) {
+            return Err(format!(
+                "{}: index {} out of range ({} vertices)",
+                name, index, self.state.count
+            ));
+        }
+        if a == b {
+            return Err(format!("{}: both ends are vertex {}", name, a));
+        }
+        Ok(())
+    }
+
+    /// Applies one tether edit to the current solver; returns the number of tethers touched.
+    fn apply_tether_edit(&mut self, edit: TetherEdit) -> usize {
+        let internal = |i: u32| self.state.internal_index[i as usize] as usize;
+        let tether = &mut self.solver.tether_constraint;
+        match edit {
+            TetherEdit::Add([a, b], group, mode) => {
+                tether.add(&self.state, &[[internal(a), internal(b)]], group, mode);
+                1
+            }
+            TetherEdit::Mode([a, b], mode) => tether.set_mode(internal(a), internal(b), mode),
+            TetherEdit::GroupMode(group, mode) => {
+                tether.set_group_mode(group, mode);
+                tether.groups.iter().filter(|&&g| g == group).count()
+            }
+        }
+    }
+
+    /// Attaches (or replaces) the distance-field collider.
+    pub fn set_sdf_collider(&mut self, sdf: SdfCollider) {
+        self.sdf_collider = Some(sdf);
+    }
+
+    /// Detaches the distance-field collider.
+    pub fn clear_sdf_collider(&mut self) {
+        self.sdf_collider = None;
+    }
+}
+
+/// A tether change made through the API, in original vertex order.
+#[derive(Clone, Copy)]
+enum TetherEdit {
+    Add([u32; 2], TetherGroup, TetherMode),
+    Mode([u32; 2], TetherMode),
+    GroupMode(TetherGroup, TetherMode),
+}
+
+/// Checks the collider buffers passed to a constructor (positions, normals, indices).
+pub(crate) fn validate_collider(
+    positions: &[f32],
+    normals: &[f32],
+    indices: &[u32],
+) -> Result<(), String> {
+    validate_mesh("collider", positions, indices)?;
+    if normals.len() != positions.len() {
+        return Err(format!(
+            "collider_normals: expected {} floats (3 per vertex), got {}",
+            positions.len(),
+            normals.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Checks a flat xyz position buffer and its triangle index buffer.
+fn validate_mesh(name: &str, positions: &[f32], indices: &[u32]) -> Result<(), String> {
+    if !positions.len().is_multiple_of(3) {
+        return Err(format!(
+            "{}_pos: length {} is not a multiple of 3",
+            name,
+            positions.len()
+        ));
+    }
+    if let Some(k) = positions.iter().position(|v| !v.is_finite()) {
+        return Err(format!("{}_pos: non-finite value at index {}", name, k));
+    }
+    if !indices.len().is_multiple_of(3) {
+        return Err(format!(
+            "{}_indices: length {} is not a multiple of 3",
+            name,
+            indices.len()
+        ));
+    }
+    let vertex_count = positions.len() / 3;
+    if let Some(k) = indices.iter().position(|&i| i as usize >= vertex_count) {
+        return Err(format!(
+            "{}_indices: index {} at position {} is out of range ({} vertices)",
+            name, indices[k], k, vertex_count
+        ));
     }
+    Ok(())
 }
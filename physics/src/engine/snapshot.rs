@@ -0,0 +1,107 @@
+// physics/src/engine/snapshot.rs
+
+//! Versioned binary snapshot of the dynamic particle state (save/restore of a draped pose).
+//!
+//! Layout (little-endian):
+//! `magic "VPHY" | version u32 | count u32 | substep_counter u32 |
+//!  positions [f32; 4] * count | prev_positions [f32; 4] * count | inv_mass f32 * count`
+//!
+//! Particles are written in original input order, so a snapshot stays valid
+//! whether or not `reorder_particles` was called on either side.
+
+use super::state::PhysicsState;
+use glam::Vec4;
+
+const MAGIC: &[u8; 4] = b"VPHY";
+const VERSION: u32 = 1;
+const HEADER_LEN: usize = 16;
+
+/// Encodes positions, prev_positions, inv_mass and the substep counter.
+pub fn encode(state: &PhysicsState, substep_counter: u32) -> Vec<u8> {
+    let n = state.count;
+    let mut out = Vec::with_capacity(HEADER_LEN + n * 36);
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&VERSION.to_le_bytes());
+    out.extend_from_slice(&(n as u32).to_le_bytes());
+    out.extend_from_slice(&substep_counter.to_le_bytes());
+
+    for buffer in [&state.positions, &state.prev_positions] {
+        for k in 0..n {
+            let p = buffer[state.internal_index[k] as usize];
+            for v in p.to_array() {
+                out.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+    }
+    for k in 0..n {
+        let w = state.inv_mass[state.internal_index[k] as usize];
+        out.extend_from_slice(&w.to_le_bytes());
+    }
+    out
+}
+
+/// Restores a snapshot produced by `encode` into `state`, returning the saved substep counter.
+/// Nothing is modified if the snapshot is malformed, holds non-finite values, or was taken
+/// from a mesh with a different particle count.
+pub fn decode_into(state: &mut PhysicsState, bytes: &[u8]) -> Result<u32, String> {
+    if bytes.len() < HEADER_LEN || &bytes[0..4] != MAGIC {
+        return Err("Snapshot: missing header".to_string());
+    }
+    let read_u32 = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+
+    let version = read_u32(4);
+    if version != VERSION {
+        return Err(format!(
+            "Snapshot: unsupported version {} (expected {})",
+            version, VERSION
+        ));
+    }
+    let n = read_u32(8) as usize;
+    if n != state.count {
+        return Err(format!(
+            "Snapshot: particle count {} does not match the current mesh ({})",
+            n, state.count
+        ));
+    }
+    let expected = HEADER_LEN + n * 36;
+    if bytes.len() != expected {
+        return Err(format!(
+            "Snapshot: expected {} bytes, got {}",
+            expected,
+            bytes.len()
+        ));
+    }
+    let substep_counter = read_u32(12);
+
+    let read_f32 = |at: usize| f32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+    // The stored w is padding; it is dropped so a foreign snapshot cannot seed a nonzero w.
+    let read_vec4 = |at: usize| Vec4::new(read_f32(at), read_f32(at + 4), read_f32(at + 8), 0.0);
+
+    let pos_base = HEADER_LEN;
+    let prev_base = pos_base + n * 16;
+    let mass_base = prev_base + n * 16;
+    // Checked before anything is written: a NaN position would only be repaired by the next
+    // step, and nothing repairs a NaN mass
+    for k in 0..n {
+        if !read_vec4(pos_base + k * 16).is_finite() {
+            return Err(format!("Snapshot: non-finite position for particle {}", k));
+        }
+        if !read_vec4(prev_base + k * 16).is_finite() {
+            return Err(format!(
+                "Snapshot: non-finite prev_position for particle {}",
+                k
+            ));
+        }
+        if !read_f32(mass_base + k * 4).is_finite() {
+            return Err(format!("Snapshot: non-finite inv_mass for particle {}", k));
+        }
+    }
+    for k in 0..n {
+        let i = state.internal_index[k] as usize;
+        state.positions[i] = read_vec4(pos_base + k * 16);
+        state.prev_positions[i] = read_vec4(prev_base + k * 16);
+        state.inv_mass[i] = read_f32(mass_base + k * 4);
+    }
+
+    Ok(substep_counter)
+}
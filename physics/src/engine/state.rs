@@ -1,20 +1,30 @@
 // physics/src/engine/state.rs
-use glam::{Vec4, Vec2}; // Removed Vec3
+use glam::{Vec2, Vec3, Vec4};
+use super::topology::MeshTopology;
+use crate::collision::spatial::dynamic::morton_encode;
 
 /// The core data container for the particle system.
 /// Uses Structure-of-Arrays (SoA) layout for better cache locality and SIMD alignment.
 /// All vectors are 16-byte aligned (Vec4) to support WebAssembly SIMD implementation.
+/// Particle data stays `f32` even with the `f64` feature: it is shared with JS, so only
+/// the scalar constraint kernels widen their math (see `utils::real`).
 #[derive(Clone)]
 pub struct PhysicsState {
     pub count: usize,
 
     // --- Particle Data (Aligned to 16 bytes / Vec4) ---
-    /// Current position (xyz) + Padding (w)
+    /// Current position (xyz) + Padding (w).
+    /// w is always 0: every write goes through `Vec4::from((v3, 0.0))` or keeps an
+    /// existing zero, so 4-wide length/dot math on these vectors stays exact.
     pub positions: Vec<Vec4>,
-    /// Previous position (xyz) + Padding (w) - for Verlet integration.
+    /// Previous position (xyz) + Padding (w, always 0) - for Verlet integration.
     pub prev_positions: Vec<Vec4>,
     /// Calculated velocity (for damping/aerodynamics).
     pub normals: Vec<Vec4>,
+    /// Rest shape (xyz, w = 0) the constraint builders measure rest lengths, areas and
+    /// angles from. Equal to the initial `positions` unless set separately, e.g. to
+    /// start from a pre-draped pose while keeping the flat pattern's rest quantities.
+    pub rest_positions: Vec<Vec4>,
 
     pub inv_mass: Vec<f32>,
     pub uvs: Vec<Vec2>,
@@ -22,6 +32,32 @@ pub struct PhysicsState {
     // --- Topology ---
     /// Triangle indices (3 per triangle).
     pub indices: Vec<u32>,
+    /// Shared vertex adjacency, built once from `indices`.
+    pub topology: MeshTopology,
+    /// Source quads (4 corners in winding order), if the mesh was authored as quads.
+    /// Empty for pure triangle meshes. Lets constraints ignore the triangulation diagonal.
+    pub quads: Vec<[usize; 4]>,
+
+    // --- Sleeping ---
+    /// False for particles that have been still long enough to skip integration/solving.
+    pub awake: Vec<bool>,
+    /// Consecutive frames each particle has moved less than the sleep threshold.
+    pub still_frames: Vec<u32>,
+
+    // --- Collision Layers ---
+    /// Self-collision layer per particle (e.g. 0 = shirt, 1 = jacket).
+    /// Topology exclusion only applies within a layer; which layers collide is
+    /// controlled by `SelfCollisionConfig::layer_masks`.
+    pub layers: Vec<u8>,
+    /// Solver region per particle (e.g. 0 = bodice, 1 = hem). Regions can be given extra
+    /// constraint sweeps per iteration (see `Solver::set_region_iterations`).
+    pub regions: Vec<u8>,
+
+    // --- Reordering ---
+    /// Maps internal particle index -> index in the original input buffer.
+    pub original_index: Vec<u32>,
+    /// Maps original input index -> internal particle index (inverse of `original_index`).
+    pub internal_index: Vec<u32>,
 }
 
 impl PhysicsState {
@@ -57,15 +93,300 @@ impl PhysicsState {
         }
 
         let inv_mass = vec![1.0; count];
+        let topology = MeshTopology::new(raw_indices, count);
 
         PhysicsState {
             count,
+            rest_positions: positions.clone(),
             positions,
             prev_positions,
             inv_mass,
             normals,
             uvs,
             indices: raw_indices.to_vec(),
+            topology,
+            quads: Vec::new(),
+            awake: vec![true; count],
+            still_frames: vec![0; count],
+            layers: vec![0; count],
+            regions: vec![0; count],
+            original_index: (0..count as u32).collect(),
+            internal_index: (0..count as u32).collect(),
+        }
+    }
+
+    /// Builds a state from a quad index buffer (4 indices per quad).
+    /// Each quad is split along its `0-2` diagonal for the triangle buffer (rendering,
+    /// collision, normals); the quads are kept so constraints can treat both diagonals alike.
+    /// Errors if the buffer is not whole quads or an index is past the last vertex.
+    pub fn new_with_quads(
+        raw_positions: &[f32],
+        raw_quads: &[u32],
+        raw_uvs: &[f32]
+    ) -> Result<Self, String> {
+        Self::validate_quads(raw_quads, raw_positions.len() / 3)?;
+        let mut indices = Vec::with_capacity(raw_quads.len() / 4 * 6);
+        for q in raw_quads.chunks_exact(4) {
+            indices.extend_from_slice(&[q[0], q[1], q[2], q[0], q[2], q[3]]);
+        }
+        let mut state = Self::new(raw_positions, &indices, raw_uvs);
+        state.quads = raw_quads
+            .chunks_exact(4)
+            .map(|q| [q[0] as usize, q[1] as usize, q[2] as usize, q[3] as usize])
+            .collect();
+        Ok(state)
+    }
+
+    /// Builds a state that starts at `raw_positions` but takes its rest shape (rest
+    /// lengths, areas and angles) from `raw_rest_positions`, e.g. a pre-draped garment
+    /// over its flat pattern. Errors if the rest buffer has the wrong length or non-finite values.
+    pub fn new_with_rest(
+        raw_positions: &[f32],
+        raw_rest_positions: &[f32],
+        raw_indices: &[u32],
+        raw_uvs: &[f32]
+    ) -> Result<Self, String> {
+        let mut state = Self::new(raw_positions, raw_indices, raw_uvs);
+        state.set_rest_positions(raw_rest_positions)?;
+        Ok(state)
+    }
+
+    /// Checks a quad index buffer (4 indices per quad) against `count` vertices.
+    pub(crate) fn validate_quads(quads: &[u32], count: usize) -> Result<(), String> {
+        if !quads.len().is_multiple_of(4) {
+            return Err(format!("quads: length {} is not a multiple of 4", quads.len()));
+        }
+        if let Some(&bad) = quads.iter().find(|&&i| i as usize >= count) {
+            return Err(format!("quads: index {} out of range ({} vertices)", bad, count));
+        }
+        Ok(())
+    }
+
+    /// Sets the rest shape from `[x, y, z, ...]` in original input order, leaving the
+    /// simulated positions untouched. Constraints built afterwards measure their rest
+    /// lengths, areas and angles from it.
+    pub fn set_rest_positions(&mut self, raw_rest_positions: &[f32]) -> Result<(), String> {
+        if raw_rest_positions.len() != self.count * 3 {
+            return Err(format!(
+                "rest_positions: expected {} floats ({} vertices), got {}",
+                self.count * 3,
+                self.count,
+                raw_rest_positions.len()
+            ));
+        }
+        if raw_rest_positions.iter().any(|v| !v.is_finite()) {
+            return Err("rest_positions: contains non-finite values".to_string());
+        }
+        for (orig, p) in raw_rest_positions.chunks_exact(3).enumerate() {
+            let i = self.internal_index[orig] as usize;
+            self.rest_positions[i] = Vec4::new(p[0], p[1], p[2], 0.0);
+        }
+        Ok(())
+    }
+
+    /// Replaces the uniform unit masses with `density * area / 3` per incident triangle
+    /// (rest-pose areas, `density` in kg/m^2), so large triangles weigh more.
+    /// Pinned particles (inverse mass 0) stay pinned; vertices without triangles keep their mass.
+    pub fn apply_area_density(&mut self, density: f32) {
+        let mut mass = vec![0.0f32; self.count];
+        for tri in self.indices.chunks_exact(3) {
+            let [a, b, c] = [tri[0] as usize, tri[1] as usize, tri[2] as usize];
+            let (pa, pb, pc) = (
+                self.rest_positions[a].truncate(),
+                self.rest_positions[b].truncate(),
+                self.rest_positions[c].truncate(),
+            );
+            let share = density * 0.5 * (pb - pa).cross(pc - pa).length() / 3.0;
+            mass[a] += share;
+            mass[b] += share;
+            mass[c] += share;
+        }
+
+        for (w, m) in self.inv_mass.iter_mut().zip(mass) {
+            if *w != 0.0 && m > 0.0 {
+                *w = 1.0 / m;
+            }
+        }
+    }
+
+    /// Vertices not referenced by any triangle (internal indices, ascending).
+    /// They get no constraints or collisions and would free-fall forever, so they
+    /// usually point at a stray vertex in the asset.
+    pub fn orphan_vertices(&self) -> Vec<usize> {
+        let mut referenced = vec![false; self.count];
+        for &i in &self.indices {
+            if let Some(r) = referenced.get_mut(i as usize) {
+                *r = true;
+            }
+        }
+        (0..self.count).filter(|&i| !referenced[i]).collect()
+    }
+
+    /// Copies the positions into `out` with the same layout as the raw buffer
+    /// (`[x, y, z, w, ...]`, stride 4, engine particle order).
+    /// `out` must hold exactly `4 * count` floats.
+    pub fn copy_positions_into(&self, out: &mut [f32]) -> Result<(), String> {
+        if out.len() != self.count * 4 {
+            return Err(format!(
+                "position buffer has {} floats, expected {} (4 per particle)",
+                out.len(),
+                self.count * 4
+            ));
         }
+        for (dst, p) in out.chunks_exact_mut(4).zip(&self.positions) {
+            dst.copy_from_slice(&p.to_array());
+        }
+        Ok(())
+    }
+
+    /// Returns true if every listed particle is asleep (constraint can be skipped).
+    #[inline(always)]
+    pub fn all_asleep(&self, indices: &[usize]) -> bool {
+        indices.iter().all(|&i| !self.awake[i])
+    }
+
+    /// Wakes a particle and restarts its stillness counter.
+    #[inline]
+    pub fn wake(&mut self, i: usize) {
+        self.awake[i] = true;
+        self.still_frames[i] = 0;
+    }
+
+    /// Wakes every particle.
+    pub fn wake_all(&mut self) {
+        self.awake.fill(true);
+        self.still_frames.fill(0);
+    }
+
+    /// Axis-aligned bounds `(min, max)` of the current positions, in one pass.
+    /// Returns `(ZERO, ZERO)` for an empty state.
+    pub fn aabb(&self) -> (Vec3, Vec3) {
+        if self.positions.is_empty() {
+            return (Vec3::ZERO, Vec3::ZERO);
+        }
+        let mut min = Vec3::splat(f32::MAX);
+        let mut max = Vec3::splat(f32::MIN);
+        for p in &self.positions {
+            min = min.min(p.truncate());
+            max = max.max(p.truncate());
+        }
+        (min, max)
+    }
+
+    /// Computes a particle order sorted by the Morton code of the current positions,
+    /// so that particles close in space end up close in memory.
+    /// Returns `order` where `order[new] = old`.
+    pub fn morton_order(&self) -> Vec<usize> {
+        let (min, max) = self.aabb();
+
+        // Quantize to the 10-bit range supported by `morton_encode` (offset by -512)
+        let extent = (max - min).max(Vec3::splat(1e-6));
+        let scale = Vec3::splat(1023.0) / extent;
+
+        let keys: Vec<u64> = self.positions.iter().map(|p| {
+            let q = ((p.truncate() - min) * scale).floor();
+            morton_encode(q.x as i32 - 512, q.y as i32 - 512, q.z as i32 - 512)
+        }).collect();
+
+        let mut order: Vec<usize> = (0..self.count).collect();
+        order.sort_by_key(|&i| (keys[i], i));
+        order
+    }
+
+    /// Permutes every per-particle array by `order` (`order[new] = old`),
+    /// remaps the triangle (and quad) indices and rebuilds the topology.
+    /// `original_index`/`internal_index` are updated so callers can map back to input order.
+    pub fn apply_permutation(&mut self, order: &[usize]) {
+        assert_eq!(order.len(), self.count, "Permutation must cover every particle");
+
+        let mut old_to_new = vec![0u32; self.count];
+        for (new, &old) in order.iter().enumerate() {
+            old_to_new[old] = new as u32;
+        }
+
+        self.positions = order.iter().map(|&o| self.positions[o]).collect();
+        self.prev_positions = order.iter().map(|&o| self.prev_positions[o]).collect();
+        self.rest_positions = order.iter().map(|&o| self.rest_positions[o]).collect();
+        self.normals = order.iter().map(|&o| self.normals[o]).collect();
+        self.inv_mass = order.iter().map(|&o| self.inv_mass[o]).collect();
+        self.uvs = order.iter().map(|&o| self.uvs[o]).collect();
+        self.awake = order.iter().map(|&o| self.awake[o]).collect();
+        self.still_frames = order.iter().map(|&o| self.still_frames[o]).collect();
+        self.layers = order.iter().map(|&o| self.layers[o]).collect();
+        self.regions = order.iter().map(|&o| self.regions[o]).collect();
+
+        for idx in self.indices.iter_mut() {
+            *idx = old_to_new[*idx as usize];
+        }
+        self.topology = MeshTopology::new(&self.indices, self.count);
+        for quad in self.quads.iter_mut() {
+            *quad = quad.map(|i| old_to_new[i] as usize);
+        }
+
+        self.original_index = order.iter().map(|&o| self.original_index[o]).collect();
+        for (new, &orig) in self.original_index.iter().enumerate() {
+            self.internal_index[orig as usize] = new as u32;
+        }
+    }
+
+    /// One level of midpoint subdivision: every edge gets a vertex at its midpoint and
+    /// every triangle is split into four (same winding). The existing particles keep
+    /// their internal and original indices; the new ones are appended in edge order.
+    /// New particles interpolate positions, UVs and rest shape and are pinned only if
+    /// both parents are. Quads are dropped, as they do not survive the split.
+    /// Returns the parents (internal indices) of each new particle, in order.
+    pub fn subdivide(&mut self) -> Vec<[usize; 2]> {
+        let count = self.count;
+        let edges = self.topology.edges.clone();
+        let midpoint = |a: usize, b: usize| -> u32 {
+            match self.topology.edge_index(a, b) {
+                Some(e) => (count + e) as u32,
+                // Degenerate edge (repeated vertex)
+                None => a as u32,
+            }
+        };
+
+        let mut indices = Vec::with_capacity(self.indices.len() * 4);
+        for tri in self.indices.chunks_exact(3) {
+            let [a, b, c] = [tri[0], tri[1], tri[2]];
+            let ab = midpoint(a as usize, b as usize);
+            let bc = midpoint(b as usize, c as usize);
+            let ca = midpoint(c as usize, a as usize);
+            indices.extend_from_slice(&[a, ab, ca, ab, b, bc, ca, bc, c, ab, bc, ca]);
+        }
+
+        for &[a, b] in &edges {
+            let mid = |v: &[Vec4]| (v[a] + v[b]) * 0.5;
+            let p = mid(&self.positions);
+            let prev = mid(&self.prev_positions);
+            let rest = mid(&self.rest_positions);
+            let n = mid(&self.normals).truncate().try_normalize().unwrap_or(Vec3::Y);
+            let uv = (self.uvs[a] + self.uvs[b]) * 0.5;
+            let w = match (self.inv_mass[a], self.inv_mass[b]) {
+                (0.0, 0.0) => 0.0,
+                (0.0, w) | (w, 0.0) => w,
+                (wa, wb) => 0.5 * (wa + wb),
+            };
+
+            self.positions.push(p);
+            self.prev_positions.push(prev);
+            self.rest_positions.push(rest);
+            self.normals.push(Vec4::from((n, 0.0)));
+            self.uvs.push(uv);
+            self.inv_mass.push(w);
+            self.layers.push(self.layers[a]);
+            self.regions.push(self.regions[a]);
+        }
+
+        self.count = count + edges.len();
+        self.indices = indices;
+        self.topology = MeshTopology::new(&self.indices, self.count);
+        self.quads.clear();
+        self.awake = vec![true; self.count];
+        self.still_frames = vec![0; self.count];
+        // New particles are both internal and original index `count + edge`
+        self.original_index.extend(count as u32..self.count as u32);
+        self.internal_index.extend(count as u32..self.count as u32);
+        edges
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,130 @@
+// physics/src/engine/topology.rs
+
+/// Shared vertex adjacency for the garment mesh, stored in CSR (Compressed Sparse Row) form.
+/// Built once from the triangle indices and reused by every constraint builder
+/// and the self-collision exclusion, instead of each rebuilding its own `HashSet`s.
+#[derive(Clone, Debug, Default)]
+pub struct MeshTopology {
+    /// `neighbors[neighbor_offsets[i]..neighbor_offsets[i + 1]]` are the vertices sharing an edge with `i`.
+    pub neighbor_offsets: Vec<usize>,
+    /// Flattened, per-vertex sorted neighbor lists.
+    pub neighbors: Vec<u32>,
+    /// Unique undirected edges as `[min, max]`, sorted by `(min, max)`.
+    pub edges: Vec<[usize; 2]>,
+    /// Number of triangles sharing each edge (1 = boundary, 2 = interior, >2 = non-manifold).
+    pub edge_face_counts: Vec<u32>,
+    /// First two triangles sharing each edge (`NO_FACE` if absent), parallel to `edges`.
+    pub edge_faces: Vec<[u32; 2]>,
+    /// True for vertices on an open boundary (touching an edge used by a single triangle).
+    pub boundary_vertices: Vec<bool>,
+}
+
+/// Marks a missing entry in `MeshTopology::edge_faces`.
+pub const NO_FACE: u32 = u32::MAX;
+
+impl MeshTopology {
+    /// Builds the adjacency from a triangle index buffer.
+    /// Degenerate edges (a vertex repeated within a triangle) are ignored.
+    pub fn new(indices: &[u32], vertex_count: usize) -> Self {
+        let num_triangles = indices.len() / 3;
+
+        // Directed half-pairs, then sort + dedup: O(E log E), no per-vertex hash sets
+        let mut pairs: Vec<(u32, u32)> = Vec::with_capacity(num_triangles * 6);
+        for t in 0..num_triangles {
+            let tri = [indices[t * 3], indices[t * 3 + 1], indices[t * 3 + 2]];
+            for k in 0..3 {
+                let a = tri[k];
+                let b = tri[(k + 1) % 3];
+                if a != b {
+                    pairs.push((a, b));
+                    pairs.push((b, a));
+                }
+            }
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+
+        let mut neighbor_offsets = vec![0usize; vertex_count + 1];
+        for &(a, _) in &pairs {
+            neighbor_offsets[a as usize + 1] += 1;
+        }
+        for i in 0..vertex_count {
+            neighbor_offsets[i + 1] += neighbor_offsets[i];
+        }
+
+        let neighbors: Vec<u32> = pairs.iter().map(|&(_, b)| b).collect();
+        let edges: Vec<[usize; 2]> = pairs
+            .iter()
+            .filter(|&&(a, b)| a < b)
+            .map(|&(a, b)| [a as usize, b as usize])
+            .collect();
+
+        // Edge -> face map (triangle order, so deterministic)
+        let mut edge_face_counts = vec![0u32; edges.len()];
+        let mut edge_faces = vec![[NO_FACE; 2]; edges.len()];
+        for t in 0..num_triangles {
+            let tri = [indices[t * 3], indices[t * 3 + 1], indices[t * 3 + 2]];
+            for k in 0..3 {
+                let (a, b) = (tri[k] as usize, tri[(k + 1) % 3] as usize);
+                if a == b {
+                    continue;
+                }
+                let key = [a.min(b), a.max(b)];
+                if let Ok(e) = edges.binary_search(&key) {
+                    let slot = edge_face_counts[e] as usize;
+                    if slot < 2 {
+                        edge_faces[e][slot] = t as u32;
+                    }
+                    edge_face_counts[e] += 1;
+                }
+            }
+        }
+
+        let mut boundary_vertices = vec![false; vertex_count];
+        for (e, &[a, b]) in edges.iter().enumerate() {
+            if edge_face_counts[e] == 1 {
+                boundary_vertices[a] = true;
+                boundary_vertices[b] = true;
+            }
+        }
+
+        Self {
+            neighbor_offsets,
+            neighbors,
+            edges,
+            edge_face_counts,
+            edge_faces,
+            boundary_vertices,
+        }
+    }
+
+    /// Returns the index of edge `{a, b}` in `edges`, if it exists.
+    #[inline]
+    pub fn edge_index(&self, a: usize, b: usize) -> Option<usize> {
+        self.edges.binary_search(&[a.min(b), a.max(b)]).ok()
+    }
+
+    /// Number of vertices covered by this topology.
+    #[inline]
+    pub fn vertex_count(&self) -> usize {
+        self.neighbor_offsets.len().saturating_sub(1)
+    }
+
+    /// Returns the (sorted) 1-ring neighbors of vertex `i`.
+    #[inline]
+    pub fn neighbors(&self, i: usize) -> &[u32] {
+        &self.neighbors[self.neighbor_offsets[i]..self.neighbor_offsets[i + 1]]
+    }
+
+    /// Returns true if vertex `i` lies on an open boundary (hem, scarf edge, cape rim).
+    #[inline]
+    pub fn is_boundary(&self, i: usize) -> bool {
+        self.boundary_vertices[i]
+    }
+
+    /// Returns true if `a` and `b` share an edge.
+    #[inline]
+    pub fn are_adjacent(&self, a: usize, b: usize) -> bool {
+        self.neighbors(a).binary_search(&(b as u32)).is_ok()
+    }
+}
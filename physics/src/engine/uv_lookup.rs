@@ -0,0 +1,98 @@
+// physics/src/engine/uv_lookup.rs
+
+use glam::Vec2;
+
+/// Cells per side are ~sqrt(triangle count), capped to keep the grid small.
+const MAX_CELLS_PER_SIDE: usize = 256;
+
+/// Uniform 2D grid over the garment triangles in UV space, so a UV coordinate can be
+/// mapped to the triangle containing it without scanning the whole mesh.
+/// Stores triangle ids only; positions, UVs and indices are read from the state at query
+/// time, so the grid stays valid while the cloth moves and after particle reordering.
+#[derive(Clone, Debug, Default)]
+pub struct UvLookup {
+    min: Vec2,
+    cell_size: Vec2,
+    width: usize,
+    height: usize,
+    cells: Vec<Vec<u32>>,
+}
+
+impl UvLookup {
+    /// Builds the grid from per-vertex `uvs` and the triangle `indices`.
+    pub fn new(uvs: &[Vec2], indices: &[u32]) -> Self {
+        let triangle_count = indices.len() / 3;
+        if triangle_count == 0 {
+            return Self::default();
+        }
+
+        let mut min = Vec2::splat(f32::MAX);
+        let mut max = Vec2::splat(f32::MIN);
+        for &i in indices {
+            min = min.min(uvs[i as usize]);
+            max = max.max(uvs[i as usize]);
+        }
+
+        let side = ((triangle_count as f32).sqrt().ceil() as usize).clamp(1, MAX_CELLS_PER_SIDE);
+        let cell_size = ((max - min) / side as f32).max(Vec2::splat(1e-6));
+        let mut lookup = Self {
+            min,
+            cell_size,
+            width: side,
+            height: side,
+            cells: vec![Vec::new(); side * side],
+        };
+
+        for (t, tri) in indices.chunks_exact(3).enumerate() {
+            let [a, b, c] = [tri[0], tri[1], tri[2]].map(|i| uvs[i as usize]);
+            let (x0, y0) = lookup.cell(a.min(b).min(c));
+            let (x1, y1) = lookup.cell(a.max(b).max(c));
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    lookup.cells[x + y * lookup.width].push(t as u32);
+                }
+            }
+        }
+        lookup
+    }
+
+    /// Grid cell containing `uv`, clamped to the grid.
+    #[inline]
+    fn cell(&self, uv: Vec2) -> (usize, usize) {
+        let local = ((uv - self.min) / self.cell_size).max(Vec2::ZERO);
+        (
+            (local.x as usize).min(self.width - 1),
+            (local.y as usize).min(self.height - 1),
+        )
+    }
+
+    /// Finds the triangle containing `uv` and its barycentric weights, or `None` if `uv`
+    /// lies outside every UV island. Where islands overlap, the first match wins.
+    pub fn locate(&self, uvs: &[Vec2], indices: &[u32], uv: Vec2) -> Option<(usize, [f32; 3])> {
+        if self.cells.is_empty() {
+            return None;
+        }
+        let (x, y) = self.cell(uv);
+        self.cells[x + y * self.width].iter().find_map(|&t| {
+            let t = t as usize;
+            let tri = &indices[t * 3..t * 3 + 3];
+            let [a, b, c] = [tri[0], tri[1], tri[2]].map(|i| uvs[i as usize]);
+            barycentric(a, b, c, uv).map(|bary| (t, bary))
+        })
+    }
+}
+
+/// Barycentric weights of `p` in the 2D triangle `(a, b, c)`, if it lies inside
+/// (with a small tolerance so points on shared edges are not lost to rounding).
+fn barycentric(a: Vec2, b: Vec2, c: Vec2, p: Vec2) -> Option<[f32; 3]> {
+    let (v0, v1, v2) = (b - a, c - a, p - a);
+    let d = v0.perp_dot(v1);
+    if d.abs() < 1e-12 {
+        return None;
+    }
+    let l1 = v2.perp_dot(v1) / d;
+    let l2 = v0.perp_dot(v2) / d;
+    let l0 = 1.0 - l1 - l2;
+    const EPS: f32 = -1e-5;
+    (l0 >= EPS && l1 >= EPS && l2 >= EPS).then_some([l0, l1, l2])
+}
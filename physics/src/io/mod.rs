@@ -0,0 +1,4 @@
+// physics/src/io/mod.rs
+pub mod obj;
+
+pub use obj::{MeshData, parse_obj, write_obj};
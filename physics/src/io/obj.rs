@@ -0,0 +1,160 @@
+// physics/src/io/obj.rs
+
+//! Minimal Wavefront OBJ reader/writer for garment and collider assets.
+//! Only `v`, `vt` and `f` are read; materials, groups and normals are ignored.
+
+use crate::engine::state::PhysicsState;
+use std::fmt::Write;
+
+/// Flat mesh buffers in the layout `PhysicsEngine::new` expects.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeshData {
+    /// `[x, y, z, ...]`, 3 floats per vertex.
+    pub positions: Vec<f32>,
+    /// Triangle list, 3 indices per triangle (0-based).
+    pub indices: Vec<u32>,
+    /// `[u, v, ...]`, 2 floats per vertex (0 for vertices without a `vt`).
+    pub uvs: Vec<f32>,
+}
+
+/// Parses OBJ text into flat buffers. Polygons are fan-triangulated.
+///
+/// Vertices are never split: connectivity comes from the `v` indices, so the cloth
+/// stays one piece across UV seams. A vertex takes the UV of the first face corner
+/// that references it.
+pub fn parse_obj(text: &str) -> Result<MeshData, String> {
+    let mut positions: Vec<f32> = Vec::new();
+    let mut tex_coords: Vec<[f32; 2]> = Vec::new();
+    let mut faces: Vec<[(u32, Option<u32>); 3]> = Vec::new();
+
+    for (line_no, raw) in text.lines().enumerate() {
+        let line_no = line_no + 1;
+        let line = raw.split('#').next().unwrap_or("").trim();
+        let mut tokens = line.split_whitespace();
+        let Some(keyword) = tokens.next() else { continue };
+
+        match keyword {
+            "v" => {
+                let xyz = parse_floats::<3>(&mut tokens, line_no, "v")?;
+                positions.extend_from_slice(&xyz);
+            }
+            "vt" => {
+                let uv = parse_floats::<2>(&mut tokens, line_no, "vt")?;
+                tex_coords.push(uv);
+            }
+            "f" => {
+                let vertex_count = (positions.len() / 3) as i64;
+                let uv_count = tex_coords.len() as i64;
+                let corners = tokens
+                    .map(|t| parse_corner(t, vertex_count, uv_count, line_no))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if corners.len() < 3 {
+                    return Err(format!("OBJ line {}: face needs at least 3 vertices", line_no));
+                }
+                for k in 1..corners.len() - 1 {
+                    faces.push([corners[0], corners[k], corners[k + 1]]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let vertex_count = positions.len() / 3;
+    let mut uvs = vec![0.0; vertex_count * 2];
+    let mut has_uv = vec![false; vertex_count];
+    let mut indices = Vec::with_capacity(faces.len() * 3);
+
+    for face in &faces {
+        for &(v, vt) in face {
+            indices.push(v);
+            let v = v as usize;
+            if let Some(vt) = vt
+                && !has_uv[v]
+            {
+                let [u, w] = tex_coords[vt as usize];
+                uvs[v * 2] = u;
+                uvs[v * 2 + 1] = w;
+                has_uv[v] = true;
+            }
+        }
+    }
+
+    Ok(MeshData {
+        positions,
+        indices,
+        uvs,
+    })
+}
+
+fn parse_floats<'a, const N: usize>(
+    tokens: &mut impl Iterator<Item = &'a str>,
+    line_no: usize,
+    keyword: &str,
+) -> Result<[f32; N], String> {
+    let mut out = [0.0; N];
+    for value in out.iter_mut() {
+        let token = tokens
+            .next()
+            .ok_or_else(|| format!("OBJ line {}: `{}` needs {} values", line_no, keyword, N))?;
+        *value = token
+            .parse()
+            .map_err(|_| format!("OBJ line {}: invalid number `{}`", line_no, token))?;
+    }
+    Ok(out)
+}
+
+/// Parses one face corner (`v`, `v/vt`, `v//vn` or `v/vt/vn`) into 0-based indices.
+/// Negative indices count back from the last element read so far.
+fn parse_corner(
+    token: &str,
+    vertex_count: i64,
+    uv_count: i64,
+    line_no: usize,
+) -> Result<(u32, Option<u32>), String> {
+    let mut parts = token.split('/');
+    let v = resolve_index(parts.next().unwrap_or(""), vertex_count, line_no, "vertex")?;
+    let vt = match parts.next() {
+        Some(s) if !s.is_empty() => Some(resolve_index(s, uv_count, line_no, "texture coordinate")?),
+        _ => None,
+    };
+    Ok((v, vt))
+}
+
+fn resolve_index(token: &str, count: i64, line_no: usize, what: &str) -> Result<u32, String> {
+    let raw: i64 = token
+        .parse()
+        .map_err(|_| format!("OBJ line {}: invalid {} index `{}`", line_no, what, token))?;
+    let index = if raw < 0 { count + raw } else { raw - 1 };
+    if raw == 0 || index < 0 || index >= count {
+        return Err(format!("OBJ line {}: {} index {} out of range", line_no, what, raw));
+    }
+    Ok(index as u32)
+}
+
+/// Writes the current garment pose as OBJ text (`v`, `vt`, `vn` and 1-based `f v/vt/vn`).
+/// Vertices are emitted in original input order, so the file lines up with the source asset
+/// even after `reorder_particles`.
+pub fn write_obj(state: &PhysicsState) -> String {
+    let n = state.count;
+    let mut out = String::with_capacity(n * 96 + state.indices.len() * 12);
+    let _ = writeln!(out, "# vestra-physics garment: {} vertices, {} triangles", n, state.indices.len() / 3);
+
+    for k in 0..n {
+        let p = state.positions[state.internal_index[k] as usize];
+        let _ = writeln!(out, "v {} {} {}", p.x, p.y, p.z);
+    }
+    for k in 0..n {
+        let uv = state.uvs[state.internal_index[k] as usize];
+        let _ = writeln!(out, "vt {} {}", uv.x, uv.y);
+    }
+    for k in 0..n {
+        let nrm = state.normals[state.internal_index[k] as usize];
+        let _ = writeln!(out, "vn {} {} {}", nrm.x, nrm.y, nrm.z);
+    }
+
+    for tri in state.indices.chunks_exact(3) {
+        let [a, b, c] = [tri[0], tri[1], tri[2]].map(|i| state.original_index[i as usize] + 1);
+        let _ = writeln!(out, "f {a}/{a}/{a} {b}/{b}/{b} {c}/{c}/{c}");
+    }
+    out
+}
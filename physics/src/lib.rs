@@ -1,10 +1,13 @@
 // physics/src/lib.rs
 pub mod collision;
 pub mod engine;
+pub mod io;
 pub mod systems;
 pub mod utils;
 
-use engine::Simulation;
+use collision::self_collision::{Aabb, SelfCollisionMode};
+use engine::{MemoryReport, MultiSimulation, PhysicsConfig, Simulation};
+use systems::constraints::{TetherGroup, TetherMode};
 use utils::profiler::Profiler;
 use wasm_bindgen::prelude::*;
 
@@ -19,11 +22,19 @@ pub use wasm_bindgen_rayon::init_thread_pool;
 #[wasm_bindgen]
 pub struct PhysicsEngine {
     sim: Simulation,
+    /// JS impact callback and the minimum inbound speed (m/s) that triggers it.
+    collision_callback: Option<(js_sys::Function, f32)>,
 }
 
 #[wasm_bindgen]
 impl PhysicsEngine {
+    /// Throws a JS error naming the malformed buffer (wrong length, out-of-range index)
+    /// instead of panicking inside WASM.
+    /// `stiffness_map` (optional, one value in [0, 1] per vertex) softens/stiffens edges locally.
+    /// `collider_friction` (optional, one multiplier per collider triangle) varies friction
+    /// over the body, e.g. a slippery lining vs grippy bare skin.
     #[wasm_bindgen(constructor)]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         garment_pos: Vec<f32>,
         garment_indices: Vec<u32>,
@@ -34,26 +45,105 @@ impl PhysicsEngine {
         collider_smoothing: usize,
         collider_inflation: f32,
         scale_factor: f32,
-    ) -> PhysicsEngine {
+        stiffness_map: Option<Vec<f32>>,
+        collider_friction: Option<Vec<f32>>,
+    ) -> Result<PhysicsEngine, JsValue> {
         utils::set_panic_hook();
 
-        PhysicsEngine {
-            sim: Simulation::new(
-                garment_pos,
-                garment_indices,
-                garment_uvs,
-                collider_pos,
-                collider_normals,
-                collider_indices,
-                collider_smoothing,
-                collider_inflation,
-                scale_factor,
-            ),
+        let mut sim = Simulation::try_with_config(
+            garment_pos,
+            garment_indices,
+            garment_uvs,
+            collider_pos,
+            collider_normals,
+            collider_indices,
+            collider_smoothing,
+            collider_inflation,
+            scale_factor,
+            PhysicsConfig::default(),
+        )
+        .map_err(|e| JsValue::from_str(&e))?;
+
+        if let Some(map) = stiffness_map {
+            sim.set_stiffness_map(map).map_err(|e| JsValue::from_str(&e))?;
+        }
+        if let Some(friction) = collider_friction {
+            sim.collider.set_friction(friction).map_err(|e| JsValue::from_str(&e))?;
         }
+
+        Ok(PhysicsEngine { sim, collision_callback: None })
+    }
+
+    /// Same as the constructor, but takes a JSON preset (see `PhysicsConfig::from_json`).
+    /// Returns an error if the JSON is malformed or a value is out of range.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_from_config_json(
+        garment_pos: Vec<f32>,
+        garment_indices: Vec<u32>,
+        garment_uvs: Vec<f32>,
+        collider_pos: Vec<f32>,
+        collider_normals: Vec<f32>,
+        collider_indices: Vec<u32>,
+        collider_smoothing: usize,
+        collider_inflation: f32,
+        scale_factor: f32,
+        config_json: &str,
+    ) -> Result<PhysicsEngine, JsValue> {
+        utils::set_panic_hook();
+
+        let config = PhysicsConfig::from_json(config_json).map_err(|e| JsValue::from_str(&e))?;
+
+        let sim = Simulation::try_with_config(
+            garment_pos,
+            garment_indices,
+            garment_uvs,
+            collider_pos,
+            collider_normals,
+            collider_indices,
+            collider_smoothing,
+            collider_inflation,
+            scale_factor,
+            config,
+        )
+        .map_err(|e| JsValue::from_str(&e))?;
+
+        Ok(PhysicsEngine { sim, collision_callback: None })
     }
 
     pub fn step(&mut self, dt: f32) {
         self.sim.step(dt);
+
+        // Dispatched after the step, so JS never runs while the simulation is borrowed
+        if let Some((callback, min_speed)) = &self.collision_callback
+            && let Some((index, speed)) = self.sim.strongest_impact()
+            && speed >= *min_speed
+        {
+            // A throwing callback must not abort the simulation
+            let _ = callback.call2(&JsValue::NULL, &JsValue::from(index as u32), &JsValue::from(speed));
+        }
+    }
+
+    /// Restores the starting pose (at rest) and restarts the frame counter and the
+    /// gravity ramp; settings, pins and attachments are kept.
+    pub fn reset(&mut self) {
+        self.sim.reset();
+    }
+
+    /// Scales gravity from 0 to full over the first `frames` frames after construction
+    /// or `reset` (0 = off), so the cloth settles onto the body gently on first load.
+    pub fn set_gravity_ramp_frames(&mut self, frames: u32) {
+        self.sim.config.gravity_ramp_frames = frames;
+    }
+
+    /// Calls `callback(index, speed)` once per `step` with the hardest cloth-body impact
+    /// (vertex in original input order, inbound speed in m/s) when it reaches `min_speed`,
+    /// e.g. to play a sound when the cloth slaps the body.
+    pub fn set_collision_callback(&mut self, callback: js_sys::Function, min_speed: f32) {
+        self.collision_callback = Some((callback, min_speed.max(0.0)));
+    }
+
+    pub fn clear_collision_callback(&mut self) {
+        self.collision_callback = None;
     }
 
     /// Updates the collider mesh with new vertex positions (e.g. for animation).
@@ -62,32 +152,257 @@ impl PhysicsEngine {
         self.sim.update_collider(&positions);
     }
 
+    /// Uses a BVH instead of the uniform grid for collider lookups
+    /// (recommended for full-body avatars and unevenly tessellated scans).
+    pub fn set_collider_bvh(&mut self, enabled: bool) {
+        self.sim.set_collider_bvh(enabled);
+    }
+
+    /// Sets the collider grid cell size in meters. 0.0 (the default) derives it from
+    /// the mean collider edge length; a detailed hand wants smaller cells than a torso.
+    pub fn set_collider_cell_size(&mut self, cell_size: f32) {
+        self.sim.set_collider_cell_size(cell_size);
+    }
+
+    /// Sets a friction multiplier per collider triangle (applied to both static and dynamic
+    /// friction); an empty array restores uniform friction.
+    pub fn set_collider_friction(&mut self, friction: Vec<f32>) -> Result<(), JsValue> {
+        self.sim.collider.set_friction(friction).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Pushes the collider out along its smoothed normals by `inflation` meters
+    /// (replaces the constructor's `collider_inflation`).
+    pub fn set_collider_inflation(&mut self, inflation: f32) {
+        self.sim.set_collider_inflation(inflation);
+    }
+
     /// Returns a pointer to the positions buffer.
-    /// Layout: [x, y, z, w, x, y, z, w...] (Stride = 4 floats)
+    /// Layout: [x, y, z, w, x, y, z, w...] (Stride = 4 floats, w is always 0)
+    /// The pointer (and any JS view over it) is invalidated whenever the buffer is
+    /// reallocated or WASM memory grows; re-fetch it every frame, or use `copy_positions`.
     pub fn get_positions_ptr(&self) -> *const f32 {
         self.sim.state.positions[0].as_ref().as_ptr()
     }
 
+    /// Refines the garment by one level of midpoint subdivision (each triangle becomes 4)
+    /// and rebuilds all constraints. New vertices are appended after the existing ones, so
+    /// re-fetch every buffer pointer and `get_particle_count` / `get_index_count` afterwards.
+    pub fn subdivide(&mut self) {
+        self.sim.subdivide();
+    }
+
+    /// Returns an owned copy of the positions, same layout as `get_positions_ptr`.
+    /// Slower (one copy per call) but never dangles, whatever happens to the buffer.
+    pub fn copy_positions(&self) -> Vec<f32> {
+        let mut out = vec![0.0; self.sim.state.count * 4];
+        // Sized above, so the length check cannot fail
+        let _ = self.sim.state.copy_positions_into(&mut out);
+        out
+    }
+
+    /// Fills `out` (4 floats per particle) with the positions, same layout as
+    /// `get_positions_ptr`, reusing the caller's array instead of allocating.
+    pub fn copy_positions_into(&self, out: &mut [f32]) -> Result<(), JsValue> {
+        self.sim.state.copy_positions_into(out).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Orients the output normals consistently (flood fill over the mesh), each connected
+    /// piece facing `(up_x, up_y, up_z)`. For open, double-sided garments; `enabled = false`
+    /// restores the authored winding. Call before the first step (tethers are regenerated).
+    pub fn set_normal_orientation(&mut self, enabled: bool, up_x: f32, up_y: f32, up_z: f32) {
+        let up = enabled.then(|| glam::Vec3::new(up_x, up_y, up_z));
+        self.sim.set_normal_orientation(up);
+    }
+
+    /// Blends each frame's normals with the previous frame's (0 = off, < 1) to
+    /// reduce shading flicker while the cloth settles.
+    pub fn set_normal_smoothing(&mut self, smoothing: f32) {
+        self.sim.config.normal_smoothing = smoothing.clamp(0.0, 0.99);
+    }
+
+    /// Uses angle-weighted instead of area-weighted vertex normals (cloth and collider),
+    /// which behave better on meshes with very uneven triangle sizes.
+    pub fn set_angle_weighted_normals(&mut self, enabled: bool) {
+        self.sim.set_angle_weighted_normals(enabled);
+    }
+
     /// Returns a pointer to the normals buffer.
     /// Layout: [x, y, z, w, x, y, z, w...] (Stride = 4 floats)
     pub fn get_normals_ptr(&self) -> *const f32 {
         self.sim.state.normals[0].as_ref().as_ptr()
     }
 
+    /// Returns a pointer to the engine's triangle index buffer (u32, 3 per triangle).
+    /// Indices refer to the engine's particle order, so they always match `get_positions_ptr`
+    /// (including after `reorder_particles`).
+    pub fn get_indices_ptr(&self) -> *const u32 {
+        self.sim.state.indices.as_ptr()
+    }
+
+    /// Returns the number of entries in the index buffer (3 x triangle count).
+    pub fn get_index_count(&self) -> usize {
+        self.sim.state.indices.len()
+    }
+
+    /// Returns a pointer to the UV buffer in engine particle order.
+    /// Layout: [u, v, u, v...] (Stride = 2 floats)
+    pub fn get_uvs_ptr(&self) -> *const f32 {
+        self.sim.state.uvs.as_ptr() as *const f32
+    }
+
+    /// Attaches a Signed Distance Field collider sampled from a flattened 3D grid.
+    /// Layout: `data[x + y * dim_x + z * dim_x * dim_y]`, negative inside the body.
+    /// `bounds` = [min_x, min_y, min_z, max_x, max_y, max_z].
+    /// Throws a JS error for malformed bounds or a `data` length not matching the dims.
+    pub fn set_sdf_collider(
+        &mut self,
+        data: Vec<f32>,
+        dim_x: usize,
+        dim_y: usize,
+        dim_z: usize,
+        bounds: Vec<f32>,
+    ) -> Result<(), JsValue> {
+        if bounds.len() != 6 {
+            return Err(JsValue::from_str(&format!(
+                "sdf bounds: expected 6 values (min xyz, max xyz), got {}",
+                bounds.len()
+            )));
+        }
+        let bounds_min = glam::Vec3::new(bounds[0], bounds[1], bounds[2]);
+        let bounds_max = glam::Vec3::new(bounds[3], bounds[4], bounds[5]);
+        let sdf = collision::SdfCollider::try_new(data, [dim_x, dim_y, dim_z], bounds_min, bounds_max)
+            .map_err(|e| JsValue::from_str(&e))?;
+        self.sim.set_sdf_collider(sdf);
+        Ok(())
+    }
+
+    /// Removes the SDF collider, leaving only the mesh collider active.
+    pub fn clear_sdf_collider(&mut self) {
+        self.sim.clear_sdf_collider();
+    }
+
+    /// Reorders particles by Morton code for cache-coherent solving.
+    /// After calling this, positions/normals are in internal order; use
+    /// `get_internal_index_ptr` to map original vertex `k` to `internal[k]`.
+    pub fn reorder_particles(&mut self) {
+        self.sim.reorder_particles();
+    }
+
+    /// Returns a pointer to the original -> internal particle index map (u32, length = particle count).
+    pub fn get_internal_index_ptr(&self) -> *const u32 {
+        self.sim.state.internal_index.as_ptr()
+    }
+
+    /// Returns a pointer to the internal -> original particle index map (u32, length = particle count).
+    pub fn get_original_index_ptr(&self) -> *const u32 {
+        self.sim.state.original_index.as_ptr()
+    }
+
+    /// Returns the body contacts from the last `step` as a flat array, 7 floats per contact:
+    /// `[particle_index, surface_x, surface_y, surface_z, normal_x, normal_y, normal_z]`.
+    /// The particle index is in original input order; contacts are sorted by it within each collider.
+    pub fn get_contacts(&self) -> Vec<f32> {
+        self.sim.contacts_flat()
+    }
+
+    /// Returns a pointer to the per-particle contact flags from the last `step`
+    /// (u8, 1 = touching the body, length = particle count), in engine particle order
+    /// like `get_positions_ptr`. E.g. for a fit-tightness overlay.
+    pub fn get_contact_mask_ptr(&self) -> *const u8 {
+        self.sim.resolver.contact_mask().as_ptr()
+    }
+
+    /// Returns the particle closest to `(x, y, z)` in engine particle order (the index into
+    /// `get_positions_ptr`, so an accessory can follow it every frame), or `undefined` for
+    /// an empty mesh. Map it with `get_original_index_ptr` for original-order APIs.
+    pub fn nearest_vertex(&self, x: f32, y: f32, z: f32) -> Option<u32> {
+        self.sim.nearest_vertex(glam::Vec3::new(x, y, z)).map(|i| i as u32)
+    }
+
+    /// Returns the current world position `[x, y, z]` of the cloth point at texture
+    /// coordinate `(u, v)`, e.g. to pin a decal that follows the folds; empty if the UV lies
+    /// outside the garment's UV islands.
+    pub fn uv_to_world(&self, u: f32, v: f32) -> Vec<f32> {
+        self.sim
+            .uv_to_world(glam::Vec2::new(u, v))
+            .map_or_else(Vec::new, |p| p.to_array().to_vec())
+    }
+
+    /// Selects one-sided (default, body) or two-sided (e.g. flag pole) collider contacts.
+    pub fn set_collision_one_sided(&mut self, one_sided: bool) {
+        self.sim.resolver.one_sided = one_sided;
+    }
+
     // --- Interaction Methods ---
 
-    pub fn set_interaction(&mut self, index: usize, x: f32, y: f32, z: f32) {
-        self.sim.mouse.grab(index, glam::Vec3::new(x, y, z));
+    /// Grabs particle `index` (in original input order, so picking stays valid after reordering).
+    /// Throws on an out-of-range index.
+    pub fn set_interaction(&mut self, index: usize, x: f32, y: f32, z: f32) -> Result<(), JsValue> {
+        self.sim
+            .grab(index, glam::Vec3::new(x, y, z))
+            .map_err(|e| JsValue::from_str(&e))
     }
 
     pub fn update_interaction(&mut self, x: f32, y: f32, z: f32) {
         self.sim.mouse.update_target(glam::Vec3::new(x, y, z));
     }
 
+    /// Sets the grab spring stiffness in [0, 1]. 1 = rigid (grabbed particle snaps to the
+    /// cursor); lower values make dragging elastic and keep fast flicks stable.
+    pub fn set_grab_stiffness(&mut self, stiffness: f32) {
+        self.sim.config.grab_stiffness = stiffness.clamp(0.0, 1.0);
+    }
+
     pub fn end_interaction(&mut self) {
         self.sim.mouse.release();
     }
 
+    /// Grabs a cluster of vertices (original input order) that then moves rigidly with
+    /// `update_group_interaction`, e.g. a hand holding a fistful of cloth.
+    pub fn set_group_interaction(&mut self, indices: Vec<u32>) -> Result<(), JsValue> {
+        self.sim.set_group_grab(&indices).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Moves the grabbed cluster by `(dx, dy, dz)` on top of the previous updates.
+    pub fn update_group_interaction(&mut self, dx: f32, dy: f32, dz: f32) {
+        self.sim.group_grab.translate(glam::Vec3::new(dx, dy, dz));
+    }
+
+    pub fn end_group_interaction(&mut self) {
+        self.sim.group_grab.release();
+    }
+
+    /// Sticks vertices (original input order) to the nearest body surface point, e.g. a
+    /// waistband that should ride the hips. Replaces previous attachments; throws on a bad index.
+    pub fn attach_to_collider(&mut self, indices: Vec<u32>) -> Result<(), JsValue> {
+        self.sim.attach_to_collider(&indices).map_err(|e| JsValue::from_str(&e))
+    }
+
+    pub fn clear_attachments(&mut self) {
+        self.sim.clear_attachments();
+    }
+
+    /// Sets the attachment stiffness in [0, 1]. 1 = rigid; lower values let attached cloth give a little.
+    pub fn set_attachment_stiffness(&mut self, stiffness: f32) {
+        self.sim.config.attachment_stiffness = stiffness.clamp(0.0, 1.0);
+    }
+
+    /// Sets a rest shape distinct from the starting pose (`[x, y, z, ...]`, original input
+    /// order) and rebuilds the constraints from it. Call before the first `step`.
+    pub fn set_rest_positions(&mut self, rest_positions: Vec<f32>) -> Result<(), JsValue> {
+        self.sim
+            .set_rest_positions(&rest_positions)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Adds a force (Newtons) to particle `index` (original input order) for the next
+    /// `step` only; calls within a frame accumulate. E.g. a scripted gust at the hem.
+    pub fn add_force(&mut self, index: usize, x: f32, y: f32, z: f32) -> Result<(), JsValue> {
+        self.sim
+            .add_force(index, glam::Vec3::new(x, y, z))
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
     // --- Profiling Methods ---
 
     /// Returns the profiling report as a JSON string.
@@ -101,12 +416,18 @@ impl PhysicsEngine {
         Profiler::reset();
     }
 
-    /// Enables or disables profiling.
-    /// When disabled, profiling calls have near-zero overhead.
+    /// Enables or disables profiling. Off by default; when disabled, profiling calls
+    /// have near-zero overhead.
     pub fn set_profiler_enabled(&self, enabled: bool) {
         Profiler::set_enabled(enabled);
     }
 
+    /// Enables or disables the per-substep timing breakdown (`substeps` in the report).
+    /// Off by default.
+    pub fn set_profiler_substep_breakdown(&self, enabled: bool) {
+        Profiler::set_substep_breakdown(enabled);
+    }
+
     /// Returns the particle count for diagnostic purposes.
     pub fn get_particle_count(&self) -> usize {
         self.sim.state.count
@@ -122,6 +443,76 @@ impl PhysicsEngine {
         self.sim.config.solver_iterations
     }
 
+    /// Returns the current bounds of the cloth as `[min_x, min_y, min_z, max_x, max_y, max_z]`.
+    /// Computed on demand, so it always reflects the latest step.
+    pub fn get_aabb(&self) -> Vec<f32> {
+        let (min, max) = self.sim.state.aabb();
+        vec![min.x, min.y, min.z, max.x, max.y, max.z]
+    }
+
+    /// Returns the lengths and capacities of the big buffers (collision candidates,
+    /// self-collision pairs), the constraint counts and the spatial hash cell totals as JSON,
+    /// to track down WASM heap growth.
+    pub fn get_memory_report(&self) -> String {
+        MemoryReport::new(&self.sim).to_json()
+    }
+
+    /// Returns rest-shape mesh quality figures as `[min_edge, max_edge, mean_edge,
+    /// min_triangle_area, max_triangle_area, degenerate_triangles]` (meters, m^2).
+    /// Lets an import tool reject assets with zero-length edges or sliver triangles.
+    pub fn get_mesh_stats(&self) -> Vec<f32> {
+        let stats = self.sim.solver.mesh_stats();
+        vec![
+            stats.min_edge_length,
+            stats.max_edge_length,
+            stats.mean_edge_length,
+            stats.min_triangle_area,
+            stats.max_triangle_area,
+            stats.degenerate_triangles as f32,
+        ]
+    }
+
+    /// Returns the collider vertices the physics actually uses (after smoothing and
+    /// inflation) as `[x, y, z, ...]`, e.g. to draw the collision hull as a debug overlay.
+    /// Triangles use the same indices as the collider passed to the constructor.
+    pub fn get_collider_positions(&self) -> Vec<f32> {
+        self.sim.collider.flat_vertices()
+    }
+
+    /// Returns the normals of the processed collider surface as `[x, y, z, ...]`.
+    pub fn get_collider_normals(&self) -> Vec<f32> {
+        self.sim.collider.flat_normals()
+    }
+
+    /// Returns the total kinetic energy of the cloth (Joules, with per-particle mass = 1/inv_mass).
+    /// Poll this to detect when the drape has settled.
+    pub fn get_kinetic_energy(&self) -> f32 {
+        self.sim.kinetic_energy()
+    }
+
+    /// Steps by `dt` until the kinetic energy falls below `energy_threshold` or
+    /// `max_frames` is reached, without a JS round-trip per frame (batch pose generation).
+    /// Returns the number of frames used. The collision callback is not dispatched.
+    pub fn settle(&mut self, dt: f32, max_frames: u32, energy_threshold: f32) -> u32 {
+        self.sim.settle(dt, max_frames, energy_threshold)
+    }
+
+    /// Returns the substep count actually used by the last `step`
+    /// (differs from `get_substeps` when adaptive substepping is enabled).
+    pub fn get_last_substeps(&self) -> usize {
+        self.sim.last_substeps()
+    }
+
+    /// Number of frames stepped so far (always available, unlike the profiler counters).
+    pub fn get_frame_count(&self) -> u32 {
+        self.sim.frame_count()
+    }
+
+    /// Simulated time in seconds since construction.
+    pub fn get_sim_time(&self) -> f64 {
+        self.sim.sim_time()
+    }
+
     // --- Tuning Methods ---
 
     pub fn set_substeps(&mut self, substeps: usize) {
@@ -132,7 +523,412 @@ impl PhysicsEngine {
         self.sim.config.solver_iterations = iterations;
     }
 
+    /// Enables velocity-based adaptive substepping, capped at `max_substeps`.
+    pub fn set_adaptive_substeps(&mut self, enabled: bool, max_substeps: usize) {
+        self.sim.config.adaptive_substeps = enabled;
+        self.sim.config.max_substeps = max_substeps.max(1);
+    }
+
+    /// Runs the physics at a fixed frame length (seconds) regardless of the `step` dt,
+    /// carrying leftover time between calls. 0 = variable timestep (one frame per `step`).
+    pub fn set_fixed_dt(&mut self, fixed_dt: f32) {
+        self.sim.config.fixed_dt = fixed_dt.max(0.0);
+    }
+
+    /// Caps how far a particle may move per substep (meters; 0 = unlimited).
+    /// A cheap guard against tunneling under extreme gusts or impulses.
+    pub fn set_max_displacement(&mut self, max_displacement: f32) {
+        self.sim.config.max_displacement = max_displacement.max(0.0);
+    }
+
+    /// Caps the body-contact push-out per substep (meters; 0 = unlimited), so deep
+    /// penetrations after a pose jump resolve over a few frames instead of popping.
+    pub fn set_max_collision_correction(&mut self, max_correction: f32) {
+        self.sim.config.max_collision_correction = max_correction.max(0.0);
+    }
+
+    /// Enables particle sleeping: particles that move less than `threshold` meters per frame
+    /// for `frames` consecutive frames stop being integrated/solved until disturbed.
+    /// Errors (changing nothing) if `threshold` is negative or not finite.
+    pub fn set_sleep_enabled(&mut self, enabled: bool, threshold: f32, frames: u32) -> Result<(), JsValue> {
+        let config = PhysicsConfig {
+            sleep_threshold: threshold,
+            sleep_frames: frames,
+            ..self.sim.config.clone()
+        };
+        config.validate().map_err(|e| JsValue::from_str(&e))?;
+        self.sim.config = config;
+        self.sim.set_sleep_enabled(enabled);
+        Ok(())
+    }
+
+    /// Vertices (original input order) that no triangle uses. They are pinned by default;
+    /// a non-empty list usually means a stray vertex in the garment asset.
+    pub fn get_orphan_vertices(&self) -> Vec<u32> {
+        self.sim.orphan_vertices()
+    }
+
+    /// Returns how many particles had NaN/inf positions repaired during the last `step`.
+    pub fn get_nan_count(&self) -> usize {
+        self.sim.nan_count()
+    }
+
+    /// Switches the Chebyshev over-relaxation on or off and sets its spectral radius
+    /// (clamped to [0, 0.99]). Off = plain Gauss-Seidel: slower to converge, never overshoots.
+    pub fn set_chebyshev(&mut self, enabled: bool, spectral_radius: f32) {
+        self.sim.config.use_chebyshev = enabled;
+        self.sim.config.spectral_radius = spectral_radius.clamp(0.0, 0.99);
+    }
+
+    /// Reuses the previous substep's distance/bending multipliers as the solver's starting
+    /// point, so settled garments reach the same residual with fewer iterations.
+    pub fn set_warm_start(&mut self, enabled: bool) {
+        self.sim.config.warm_start = enabled;
+    }
+
+    /// Enables per-iteration convergence tracking (debug; adds one pass over the edges per iteration).
+    pub fn set_convergence_tracking(&mut self, enabled: bool) {
+        self.sim.config.track_convergence = enabled;
+        if !enabled {
+            self.sim.solver.convergence.clear();
+        }
+    }
+
+    /// Returns the distance residual after each solver iteration of the last step
+    /// (one value per iteration, summed over substeps). Empty unless tracking is enabled.
+    pub fn get_convergence(&self) -> Vec<f32> {
+        self.sim.convergence().to_vec()
+    }
+
+    /// Returns how many particles are currently asleep.
+    pub fn get_sleeping_count(&self) -> usize {
+        self.sim.sleeping_count()
+    }
+
+    /// Turns drag/lift/wind on or off. Off skips the per-triangle air pass entirely;
+    /// global damping still settles the cloth.
+    pub fn set_aerodynamics_enabled(&mut self, enabled: bool) {
+        self.sim.config.aerodynamics_enabled = enabled;
+    }
+
+    /// Sets the air response, e.g. high drag for a silk scarf, low for a heavy coat.
+    /// Negative values are clamped to 0.
+    pub fn set_aero_coefficients(&mut self, drag: f32, lift: f32) {
+        self.sim.set_aero_coefficients(drag, lift);
+    }
+
+    /// Sets the gust amplitude (m/s) added on top of the base wind. 0 = steady wind.
+    pub fn set_wind_turbulence(&mut self, amount: f32) {
+        self.sim.config.wind_turbulence = amount.max(0.0);
+    }
+
+    /// Shades cloth hidden from the wind by the body (one upwind ray per triangle per frame).
+    pub fn set_wind_occlusion(&mut self, enabled: bool) {
+        self.sim.config.wind_occlusion = enabled;
+    }
+
+    /// Sets the cloth-body offset (meters). Takes effect on the next step without
+    /// rebuilding the collider; the visible gap is this plus the collider inflation.
+    pub fn set_contact_thickness(&mut self, thickness: f32) {
+        self.sim.set_contact_thickness(thickness);
+    }
+
+    /// Overrides the radius of the discrete body contact query (meters); 0 derives it
+    /// from the contact thickness, cloth radius and collider inflation.
+    pub fn set_contact_query_radius(&mut self, radius: f32) {
+        self.sim.config.contact_query_radius = radius.max(0.0);
+    }
+
+    /// Collision radius of each cloth particle (fabric thickness / 2), applied to body
+    /// contacts and self-collision so layered folds do not interpenetrate.
+    pub fn set_cloth_radius(&mut self, radius: f32) {
+        self.sim.set_cloth_radius(radius);
+    }
+
     pub fn set_self_collision_enabled(&mut self, enabled: bool) {
         self.sim.config.self_collision_enabled = enabled;
     }
+
+    /// Sets the minimum cloth-cloth separation (meters); rebuilds the self-collision hash grid.
+    pub fn set_self_collision_thickness(&mut self, thickness: f32) {
+        self.sim.set_self_collision_thickness(thickness);
+    }
+
+    /// Sets the self-collision repulsion strength (0.0 - 1.0).
+    pub fn set_self_collision_stiffness(&mut self, stiffness: f32) {
+        self.sim.set_self_collision_stiffness(stiffness);
+    }
+
+    /// Caps the collider candidates gathered per frame (0 = 100 per particle); particles
+    /// past the cap skip body collision that frame. Bounds memory on very dense colliders.
+    pub fn set_max_candidates(&mut self, max_candidates: usize) {
+        self.sim.config.max_candidates = max_candidates;
+    }
+
+    /// Runs self-collision every N substeps (1 = every substep, 0 is treated as 1).
+    /// Takes effect on the next substep, so it can be lowered while the user drags.
+    pub fn set_self_collision_frequency(&mut self, frequency: u8) {
+        self.sim.set_self_collision_frequency(frequency);
+    }
+
+    /// Caps the self-collision pairs resolved per pass; past the cap the deepest overlaps win.
+    pub fn set_self_collision_max_pairs(&mut self, max_pairs: usize) {
+        self.sim.set_self_collision_max_pairs(max_pairs);
+    }
+
+    /// Switches individual constraint types on or off (all on by default).
+    /// Meant for debugging: e.g. disabling tethers shows whether they cause an artifact.
+    pub fn set_constraints_enabled(&mut self, distance: bool, bending: bool, tether: bool, area: bool) {
+        self.sim.config.distance_enabled = distance;
+        self.sim.config.bending_enabled = bending;
+        self.sim.config.tether_enabled = tether;
+        self.sim.config.area_enabled = area;
+    }
+
+    /// Sets the edge stretch stiffness in N/m (0 = use the raw compliance), so material
+    /// presets can be written in physical units.
+    pub fn set_distance_stiffness(&mut self, stiffness: f32) {
+        self.sim.set_distance_stiffness(stiffness);
+    }
+
+    /// Sets the raw XPBD distance compliance (m/N) for power users; ignored while a
+    /// distance stiffness is set.
+    pub fn set_distance_compliance(&mut self, compliance: f32) {
+        self.sim.set_distance_compliance(compliance);
+    }
+
+    /// Enables dihedral bending, which holds each hinge at its initial (rest) fold angle.
+    pub fn set_dihedral_bending(&mut self, enabled: bool, compliance: f32) {
+        self.sim.config.dihedral_enabled = enabled;
+        self.sim.config.dihedral_compliance = compliance.max(0.0);
+    }
+
+    /// Lets dihedral hinges fold freely between `min_angle` and `max_angle` (signed radians,
+    /// 0 = flat) and only corrects them at those stops, instead of holding the rest angle.
+    /// Needs dihedral bending enabled; `enabled = false` restores rest-angle bending.
+    pub fn set_dihedral_limits(&mut self, enabled: bool, min_angle: f32, max_angle: f32) {
+        use std::f32::consts::PI;
+        let (min_angle, max_angle) = (min_angle.clamp(-PI, PI), max_angle.clamp(-PI, PI));
+        self.sim.config.dihedral_limits_enabled = enabled;
+        self.sim.config.dihedral_min_angle = min_angle.min(max_angle);
+        self.sim.config.dihedral_max_angle = max_angle.max(min_angle);
+    }
+
+    /// Sets the XPBD compliance of the vertical (anti-sag) and horizontal (anti-widen)
+    /// tethers separately. 0.0 = inextensible (the default for both).
+    pub fn set_tether_compliance(&mut self, vertical: f32, horizontal: f32) {
+        self.sim.config.tether_vertical_compliance = vertical.max(0.0);
+        self.sim.config.tether_horizontal_compliance = horizontal.max(0.0);
+    }
+
+    /// Sets the per-constraint over-relaxation factors, applied on top of the Chebyshev
+    /// omega (e.g. distance 1.0, bending 0.7 to calm ringing on stiff fabrics).
+    /// Values are clamped to (0, 2).
+    pub fn set_relaxation(&mut self, distance: f32, bending: f32, tether: f32, area: f32) {
+        let clamp = |v: f32| if v.is_finite() { v.clamp(0.01, 1.99) } else { 1.0 };
+        self.sim.config.distance_relaxation = clamp(distance);
+        self.sim.config.bending_relaxation = clamp(bending);
+        self.sim.config.tether_relaxation = clamp(tether);
+        self.sim.config.area_relaxation = clamp(area);
+    }
+
+    /// Adds a tether between vertices `a` and `b` (original input order) at their rest
+    /// distance. `group` picks the compliance (0 = vertical, 1 = horizontal); `mode` which
+    /// length violations it corrects: 0 = extension only, 1 = compression only, 2 = both
+    /// (rigid boning).
+    pub fn add_tether(&mut self, a: u32, b: u32, group: u8, mode: u8) -> Result<(), JsValue> {
+        let (group, mode) = (tether_group(group)?, tether_mode(mode)?);
+        self.sim.add_tether(a, b, group, mode).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Sets the mode (see `add_tether`) of the tethers between vertices `a` and `b`.
+    /// Throws if there is none.
+    pub fn set_tether_mode(&mut self, a: u32, b: u32, mode: u8) -> Result<(), JsValue> {
+        let mode = tether_mode(mode)?;
+        self.sim.set_tether_mode(a, b, mode).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Sets the mode (see `add_tether`) of every vertical (0) or horizontal (1) tether.
+    pub fn set_tether_group_mode(&mut self, group: u8, mode: u8) -> Result<(), JsValue> {
+        let (group, mode) = (tether_group(group)?, tether_mode(mode)?);
+        self.sim.set_tether_group_mode(group, mode);
+        Ok(())
+    }
+
+    /// Returns the current garment pose as OBJ text (positions, UVs, normals, faces),
+    /// in original vertex order, e.g. to open a draped pose in Blender for QA.
+    pub fn export_obj(&self) -> String {
+        io::write_obj(&self.sim.state)
+    }
+
+    /// Saves the draped pose (positions, velocities, masses) as a versioned byte buffer.
+    pub fn serialize(&self) -> Vec<u8> {
+        self.sim.serialize()
+    }
+
+    /// Restores a pose saved with `serialize`; throws if it was taken from a different mesh.
+    pub fn deserialize(&mut self, bytes: &[u8]) -> Result<(), JsValue> {
+        self.sim.deserialize(bytes).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Sets a per-vertex stiffness map (0 = soft, 1 = stiff); throws if the length is wrong.
+    pub fn set_stiffness_map(&mut self, stiffness: Vec<f32>) -> Result<(), JsValue> {
+        self.sim.set_stiffness_map(stiffness).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Declares the authoring quads (4 indices per quad) behind the triangulated garment,
+    /// removing the triangulation-diagonal bias from stretch/bend. Call before the first step.
+    pub fn set_quads(&mut self, quads: Vec<u32>) -> Result<(), JsValue> {
+        self.sim.set_quads(&quads).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Assigns a self-collision layer to each particle (original input order), e.g. shirt = 0, jacket = 1.
+    pub fn set_particle_layers(&mut self, layers: Vec<u8>) {
+        self.sim.set_particle_layers(&layers);
+    }
+
+    /// Assigns solver regions (u8 per vertex, original input order), e.g. 1 = hem.
+    pub fn set_particle_regions(&mut self, regions: Vec<u8>) {
+        self.sim.set_particle_regions(&regions);
+    }
+
+    /// Solves the constraints touching `region` `multiplier` times per iteration, so slow
+    /// converging areas (a long hem) get more of the budget than stiff ones.
+    pub fn set_region_iterations(&mut self, region: u8, multiplier: u32) {
+        self.sim.set_region_iterations(region, multiplier);
+    }
+
+    /// Enables or disables self-collision between two layers (all layers collide by default).
+    pub fn set_layers_collide(&mut self, a: u8, b: u8, collide: bool) {
+        self.sim.self_collision.config.set_layers_interact(a, b, collide);
+    }
+
+    /// Restricts self-collision to particles inside the box `min..max` (world space),
+    /// e.g. the skirt of a dress, so the rest of the garment costs nothing.
+    pub fn set_self_collision_region(
+        &mut self,
+        min_x: f32,
+        min_y: f32,
+        min_z: f32,
+        max_x: f32,
+        max_y: f32,
+        max_z: f32,
+    ) {
+        self.sim.self_collision.config.region = Some(Aabb::new(
+            glam::Vec3::new(min_x, min_y, min_z),
+            glam::Vec3::new(max_x, max_y, max_z),
+        ));
+    }
+
+    /// Self-collision covers the whole garment again.
+    pub fn clear_self_collision_region(&mut self) {
+        self.sim.self_collision.config.region = None;
+    }
+
+    /// Switches self-collision between point-point (default) and vertex-triangle testing.
+    pub fn set_self_collision_vertex_triangle(&mut self, enabled: bool) {
+        self.sim.self_collision.config.mode = if enabled {
+            SelfCollisionMode::VertexTriangle
+        } else {
+            SelfCollisionMode::PointPoint
+        };
+    }
+}
+
+fn tether_group(index: u8) -> Result<TetherGroup, JsValue> {
+    TetherGroup::from_index(index)
+        .ok_or_else(|| JsValue::from_str(&format!("tether group must be 0 or 1, got {}", index)))
+}
+
+fn tether_mode(index: u8) -> Result<TetherMode, JsValue> {
+    TetherMode::from_index(index)
+        .ok_or_else(|| JsValue::from_str(&format!("tether mode must be 0, 1 or 2, got {}", index)))
+}
+
+/// WASM entry point for several garments sharing one collider (e.g. shirt + tie + scarf).
+/// One `step` / `update_collider` call drives the whole scene; buffers are read per garment.
+#[wasm_bindgen]
+pub struct MultiPhysicsEngine {
+    multi: MultiSimulation,
+}
+
+#[wasm_bindgen]
+impl MultiPhysicsEngine {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        collider_pos: Vec<f32>,
+        collider_normals: Vec<f32>,
+        collider_indices: Vec<u32>,
+        collider_smoothing: usize,
+        collider_smoothing_lambda: f32,
+        collider_inflation: f32,
+    ) -> Result<MultiPhysicsEngine, JsValue> {
+        utils::set_panic_hook();
+        let multi = MultiSimulation::try_new(
+            collider_pos,
+            collider_normals,
+            collider_indices,
+            collider_smoothing,
+            collider_smoothing_lambda,
+            collider_inflation,
+        )
+        .map_err(|e| JsValue::from_str(&e))?;
+        Ok(MultiPhysicsEngine { multi })
+    }
+
+    /// Adds a garment with the default configuration and returns its index.
+    /// Throws a JS error naming the malformed buffer.
+    pub fn add_garment(
+        &mut self,
+        garment_pos: Vec<f32>,
+        garment_indices: Vec<u32>,
+        garment_uvs: Vec<f32>,
+        scale_factor: f32,
+    ) -> Result<usize, JsValue> {
+        self.multi
+            .add_garment(garment_pos, garment_indices, garment_uvs, scale_factor, PhysicsConfig::default())
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    pub fn get_garment_count(&self) -> usize {
+        self.multi.garment_count()
+    }
+
+    pub fn step(&mut self, dt: f32) {
+        self.multi.step(dt);
+    }
+
+    /// Updates the shared collider mesh once for every garment.
+    pub fn update_collider(&mut self, positions: Vec<f32>) {
+        self.multi.update_collider(&positions);
+    }
+
+    /// Sticks particles of one garment (original input order) to the shared body.
+    /// Replaces that garment's previous attachments; throws on a bad garment or vertex index.
+    pub fn attach_to_collider(&mut self, garment: usize, indices: Vec<u32>) -> Result<(), JsValue> {
+        self.multi.attach_to_collider(garment, &indices).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Positions of one garment. Layout: [x, y, z, w, ...] (Stride = 4 floats, w is always 0)
+    /// Throws on an invalid garment index.
+    pub fn get_positions_ptr(&self, garment: usize) -> Result<*const f32, JsValue> {
+        Ok(self.garment(garment)?.state.positions.as_ptr() as *const f32)
+    }
+
+    /// Normals of one garment. Layout: [x, y, z, w, ...] (Stride = 4 floats)
+    /// Throws on an invalid garment index.
+    pub fn get_normals_ptr(&self, garment: usize) -> Result<*const f32, JsValue> {
+        Ok(self.garment(garment)?.state.normals.as_ptr() as *const f32)
+    }
+
+    /// Particle count of one garment. Throws on an invalid garment index.
+    pub fn get_particle_count(&self, garment: usize) -> Result<usize, JsValue> {
+        Ok(self.garment(garment)?.state.count)
+    }
+}
+
+impl MultiPhysicsEngine {
+    fn garment(&self, index: usize) -> Result<&Simulation, JsValue> {
+        self.multi.garment(index).map_err(|e| JsValue::from_str(&e))
+    }
 }
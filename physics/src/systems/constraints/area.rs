@@ -2,17 +2,26 @@
 
 use crate::engine::state::PhysicsState;
 use crate::utils::coloring;
-use glam::Vec4;
+use crate::utils::real::{from_real3, real, to_real3, SIMD_ENABLED};
+use crate::utils::simd::{F32x4, Vec3x4};
+use glam::{Vec3, Vec4};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 /// Enforces triangle area preservation.
 /// Prevents the mesh from shearing or collapsing, simulating the material's resistance to planar deformation.
 ///
-/// NOTE: Area constraints involve 3-body interactions with complex gradient computations.
-/// Full SIMD is less beneficial here due to data dependencies.
+/// OPTIMIZATION: Packs 4 triangles into `Vec3x4` lanes on WASM SIMD targets;
+/// falls back to 4x loop unrolling where SIMD is emulated.
 pub struct AreaConstraint {
     indices: Vec<[usize; 3]>,
     rest_areas: Vec<f32>,
+    /// Rest-shape edge vectors `[p1 - p0, p2 - p0]`, used to re-open collapsed triangles.
+    rest_edges: Vec<[Vec3; 2]>,
     batch_offsets: Vec<usize>,
+    /// Triangles skipped at build time because their rest area is (near) zero.
+    degenerate_count: usize,
 }
 
 impl AreaConstraint {
@@ -21,15 +30,16 @@ impl AreaConstraint {
         let num_triangles = state.indices.len() / 3;
         let mut raw_indices = Vec::with_capacity(num_triangles);
         let mut raw_rest_areas = Vec::with_capacity(num_triangles);
+        let mut raw_rest_edges = Vec::with_capacity(num_triangles);
 
         for i in 0..num_triangles {
             let idx0 = state.indices[i * 3] as usize;
             let idx1 = state.indices[i * 3 + 1] as usize;
             let idx2 = state.indices[i * 3 + 2] as usize;
 
-            let p0 = state.positions[idx0];
-            let p1 = state.positions[idx1];
-            let p2 = state.positions[idx2];
+            let p0 = state.rest_positions[idx0];
+            let p1 = state.rest_positions[idx1];
+            let p2 = state.rest_positions[idx2];
 
             let u = p1 - p0;
             let v = p2 - p0;
@@ -41,84 +51,275 @@ impl AreaConstraint {
             if area > 1e-6 {
                 raw_indices.push([idx0, idx1, idx2]);
                 raw_rest_areas.push(area);
+                raw_rest_edges.push([u3, v3]);
             }
         }
 
+        let degenerate_count = num_triangles - raw_indices.len();
         let (sorted_indices, batch_offsets) = coloring::color_constraints_3(&raw_indices, state.count);
 
         let mut indices = Vec::with_capacity(raw_indices.len());
         let mut rest_areas = Vec::with_capacity(raw_indices.len());
+        let mut rest_edges = Vec::with_capacity(raw_indices.len());
 
         for idx in sorted_indices {
             indices.push(raw_indices[idx]);
             rest_areas.push(raw_rest_areas[idx]);
+            rest_edges.push(raw_rest_edges[idx]);
         }
 
         Self {
             indices,
             rest_areas,
+            rest_edges,
             batch_offsets,
+            degenerate_count,
         }
     }
 
+    /// Rest area of every constrained triangle (in batch order).
+    pub fn rest_areas(&self) -> &[f32] {
+        &self.rest_areas
+    }
+
+    /// Number of triangles left unconstrained because their rest area is (near) zero.
+    pub fn degenerate_count(&self) -> usize {
+        self.degenerate_count
+    }
+
     /// Solves the area constraint using XPBD.
-    /// Uses 4x loop unrolling for instruction-level parallelism.
+    /// Each 4-triangle chunk uses whichever kernel wins on the current target
+    /// (see `USE_SIMD_KERNEL`).
+    /// With the `parallel` feature, each color batch is split across threads.
     #[inline(never)]
     pub fn solve(&self, state: &mut PhysicsState, compliance: f32, omega: f32, dt: f32) {
+        self.solve_with_kernel(state, compliance, omega, dt, Self::USE_SIMD_KERNEL);
+    }
+
+    /// Solves using the `Vec3x4` kernel regardless of target. Exposed for benchmarking.
+    pub fn solve_simd(&self, state: &mut PhysicsState, compliance: f32, omega: f32, dt: f32) {
+        self.solve_with_kernel(state, compliance, omega, dt, true);
+    }
+
+    /// Solves using the 4x unrolled scalar kernel regardless of target. Exposed for benchmarking.
+    pub fn solve_unrolled(&self, state: &mut PhysicsState, compliance: f32, omega: f32, dt: f32) {
+        self.solve_with_kernel(state, compliance, omega, dt, false);
+    }
+
+    /// Kernel selection per target.
+    /// WASM SIMD128: the packed kernel wins (real 4-wide lanes).
+    /// Native (scalar `F32x4` emulation): the unrolled kernel wins, as the
+    /// emulated lanes add select/mask overhead without any vector throughput.
+    /// The `f64` feature always takes the scalar kernel.
+    const USE_SIMD_KERNEL: bool =
+        SIMD_ENABLED && cfg!(all(target_arch = "wasm32", target_feature = "simd128"));
+
+    #[inline(always)]
+    fn solve_with_kernel(
+        &self,
+        state: &mut PhysicsState,
+        compliance: f32,
+        omega: f32,
+        dt: f32,
+        use_simd: bool,
+    ) {
         let alpha = compliance / (dt * dt);
 
-        for b in 0..(self.batch_offsets.len() - 1) {
-            let start = self.batch_offsets[b];
-            let end = self.batch_offsets[b + 1];
-            let count = end - start;
+        // Safety: `color_constraints_3` guarantees that triangles in the same batch
+        // do not share particles, so their position updates are disjoint.
+
+        #[cfg(feature = "parallel")]
+        {
+            struct StatePtr(pub usize);
+            unsafe impl Send for StatePtr {}
+            unsafe impl Sync for StatePtr {}
+            let state_ptr = StatePtr(state as *mut _ as usize);
 
-            let chunks = count / 4;
-            let remainder = count % 4;
+            for b in 0..(self.batch_offsets.len() - 1) {
+                let start = self.batch_offsets[b];
+                let end = self.batch_offsets[b + 1];
+                let count = end - start;
 
-            for chunk in 0..chunks {
-                let base = start + chunk * 4;
-                Self::solve_single(state, &self.indices[base], self.rest_areas[base], alpha, omega);
-                Self::solve_single(state, &self.indices[base + 1], self.rest_areas[base + 1], alpha, omega);
-                Self::solve_single(state, &self.indices[base + 2], self.rest_areas[base + 2], alpha, omega);
-                Self::solve_single(state, &self.indices[base + 3], self.rest_areas[base + 3], alpha, omega);
+                let num_chunks = count / 4;
+
+                (0..num_chunks).into_par_iter().for_each(move |chunk_idx| {
+                    let base = start + chunk_idx * 4;
+                    let state_ref = unsafe { &mut *(state_ptr.0 as *mut PhysicsState) };
+                    self.solve_chunk_4(state_ref, base, alpha, omega, use_simd);
+                });
+
+                // Handle remainder sequentially
+                let remainder_start = start + num_chunks * 4;
+                let state_ref = unsafe { &mut *(state_ptr.0 as *mut PhysicsState) };
+                for k in remainder_start..end {
+                    self.solve_single(state_ref, k, alpha, omega);
+                }
             }
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            for b in 0..(self.batch_offsets.len() - 1) {
+                let start = self.batch_offsets[b];
+                let end = self.batch_offsets[b + 1];
+                let count = end - start;
 
-            for k in (start + chunks * 4)..(start + chunks * 4 + remainder) {
-                Self::solve_single(state, &self.indices[k], self.rest_areas[k], alpha, omega);
+                let chunks = count / 4;
+                let remainder = count % 4;
+
+                for chunk in 0..chunks {
+                    let base = start + chunk * 4;
+                    self.solve_chunk_4(state, base, alpha, omega, use_simd);
+                }
+
+                for k in (start + chunks * 4)..(start + chunks * 4 + remainder) {
+                    self.solve_single(state, k, alpha, omega);
+                }
             }
         }
     }
 
     #[inline(always)]
-    fn solve_single(state: &mut PhysicsState, indices: &[usize; 3], rest_area: f32, alpha: f32, omega: f32) {
+    fn solve_chunk_4(&self, state: &mut PhysicsState, base: usize, alpha: f32, omega: f32, use_simd: bool) {
+        if use_simd {
+            self.solve_simd_4(state, base, alpha, omega);
+        } else {
+            self.solve_single(state, base, alpha, omega);
+            self.solve_single(state, base + 1, alpha, omega);
+            self.solve_single(state, base + 2, alpha, omega);
+            self.solve_single(state, base + 3, alpha, omega);
+        }
+    }
+
+    /// SIMD-accelerated solver for 4 triangles at once.
+    /// Gathers the three vertices of each triangle into `Vec3x4` lanes and computes
+    /// cross products, areas and gradients in parallel.
+    /// Lanes that the scalar path would skip (degenerate, satisfied, pinned) get a zero multiplier.
+    #[inline(always)]
+    fn solve_simd_4(&self, state: &mut PhysicsState, base: usize, alpha: f32, omega: f32) {
+        let [a0, b0, c0] = self.indices[base];
+        let [a1, b1, c1] = self.indices[base + 1];
+        let [a2, b2, c2] = self.indices[base + 2];
+        let [a3, b3, c3] = self.indices[base + 3];
+
+        // Skip chunks whose particles are all asleep
+        if state.all_asleep(&[a0, b0, c0, a1, b1, c1, a2, b2, c2, a3, b3, c3]) {
+            return;
+        }
+
+        // Load inverse masses
+        let w0 = F32x4::new(state.inv_mass[a0], state.inv_mass[a1], state.inv_mass[a2], state.inv_mass[a3]);
+        let w1 = F32x4::new(state.inv_mass[b0], state.inv_mass[b1], state.inv_mass[b2], state.inv_mass[b3]);
+        let w2 = F32x4::new(state.inv_mass[c0], state.inv_mass[c1], state.inv_mass[c2], state.inv_mass[c3]);
+
+        // Gather triangle vertices
+        let p0 = Vec3x4::from_vec4s(state.positions[a0], state.positions[a1], state.positions[a2], state.positions[a3]);
+        let p1 = Vec3x4::from_vec4s(state.positions[b0], state.positions[b1], state.positions[b2], state.positions[b3]);
+        let p2 = Vec3x4::from_vec4s(state.positions[c0], state.positions[c1], state.positions[c2], state.positions[c3]);
+
+        let rest = F32x4::new(
+            self.rest_areas[base],
+            self.rest_areas[base + 1],
+            self.rest_areas[base + 2],
+            self.rest_areas[base + 3],
+        );
+
+        // Current area from the cross product
+        let cross = p1.sub(p0).cross(p2.sub(p0));
+        let cross_len = cross.length();
+        let half = F32x4::splat(0.5);
+        let current_area = cross_len.mul(half);
+
+        let c = current_area.sub(rest);
+
+        // Unit normal (safe against collapsed lanes; those are masked out below)
+        let n = cross.div_scalar(cross_len.max(F32x4::splat(1e-8)));
+
+        let grad0 = p2.sub(p1).cross(n).mul_scalar(half);
+        let grad1 = p0.sub(p2).cross(n).mul_scalar(half);
+        let grad2 = p1.sub(p0).cross(n).mul_scalar(half);
+
+        let denom = w0.mul(grad0.length_squared())
+            .add(w1.mul(grad1.length_squared()))
+            .add(w2.mul(grad2.length_squared()));
+
+        let delta_lambda = c.neg().div(denom.add(F32x4::splat(alpha)).max(F32x4::splat(1e-8)));
+        let mut lambda_omega = delta_lambda.mul(F32x4::splat(omega));
+
+        // Mask lanes the scalar solver would early-out on
+        let zero = F32x4::splat(0.0);
+        lambda_omega = F32x4::select(c.abs().lt_mask(F32x4::splat(1e-6)), zero, lambda_omega);
+        lambda_omega = F32x4::select(current_area.lt_mask(F32x4::splat(1e-9)), zero, lambda_omega);
+        lambda_omega = F32x4::select(denom.lt_mask(F32x4::splat(1e-9)), zero, lambda_omega);
+
+        let corr0 = grad0.mul_scalar(lambda_omega.mul(w0));
+        let corr1 = grad1.mul_scalar(lambda_omega.mul(w1));
+        let corr2 = grad2.mul_scalar(lambda_omega.mul(w2));
+
+        let tris = [[a0, b0, c0], [a1, b1, c1], [a2, b2, c2], [a3, b3, c3]];
+        for (lane, &[i0, i1, i2]) in tris.iter().enumerate() {
+            if w0.lane(lane) > 0.0 {
+                state.positions[i0] += corr0.extract_lane(lane);
+            }
+            if w1.lane(lane) > 0.0 {
+                state.positions[i1] += corr1.extract_lane(lane);
+            }
+            if w2.lane(lane) > 0.0 {
+                state.positions[i2] += corr2.extract_lane(lane);
+            }
+        }
+
+        // Collapsed lanes were masked out above; the scalar path re-opens them
+        for lane in 0..4 {
+            if current_area.lane(lane) < 1e-9 {
+                self.solve_single(state, base + lane, alpha, omega);
+            }
+        }
+    }
+
+    /// Scalar kernel for triangle `k`.
+    /// A collapsed triangle (zero area) has no current normal, so its gradient is taken
+    /// against the rest-shape normal, and against the rest edges themselves if it has
+    /// shrunk to a point. That pushes the vertices apart deterministically until the
+    /// regular gradient is defined again.
+    #[inline(always)]
+    fn solve_single(&self, state: &mut PhysicsState, k: usize, alpha: f32, omega: f32) {
+        let indices = &self.indices[k];
         let [i0, i1, i2] = *indices;
+        if state.all_asleep(indices) {
+            return;
+        }
 
-        let w0 = state.inv_mass[i0];
-        let w1 = state.inv_mass[i1];
-        let w2 = state.inv_mass[i2];
+        let w0 = real(state.inv_mass[i0]);
+        let w1 = real(state.inv_mass[i1]);
+        let w2 = real(state.inv_mass[i2]);
         let w_sum = w0 + w1 + w2;
         if w_sum == 0.0 { return; }
 
-        let p0 = state.positions[i0];
-        let p1 = state.positions[i1];
-        let p2 = state.positions[i2];
+        let p0 = to_real3(state.positions[i0].truncate());
+        let p1 = to_real3(state.positions[i1].truncate());
+        let p2 = to_real3(state.positions[i2].truncate());
 
-        let u = p1 - p0;
-        let v = p2 - p0;
-        let u3 = u.truncate();
-        let v3 = v.truncate();
-        let cross = u3.cross(v3);
+        let cross = (p1 - p0).cross(p2 - p0);
         let current_area = 0.5 * cross.length();
 
-        let c = current_area - rest_area;
+        let c = current_area - real(self.rest_areas[k]);
         if c.abs() < 1e-6 { return; }
-        if current_area < 1e-9 { return; }
 
-        let n = cross / (2.0 * current_area);
-
-        let grad0 = 0.5 * (p2.truncate() - p1.truncate()).cross(n);
-        let grad1 = 0.5 * (p0.truncate() - p2.truncate()).cross(n);
-        let grad2 = 0.5 * (p1.truncate() - p0.truncate()).cross(n);
+        let (grad0, grad1, grad2) = if current_area >= 1e-9 {
+            let n = cross / (2.0 * current_area);
+            (0.5 * (p2 - p1).cross(n), 0.5 * (p0 - p2).cross(n), 0.5 * (p1 - p0).cross(n))
+        } else {
+            let [rest_u, rest_v] = self.rest_edges[k].map(to_real3);
+            let n = rest_u.cross(rest_v).normalize_or_zero();
+            let from_current = (0.5 * (p2 - p1).cross(n), 0.5 * (p0 - p2).cross(n), 0.5 * (p1 - p0).cross(n));
+            if from_current.0.length_squared() + from_current.1.length_squared() + from_current.2.length_squared() > 1e-12 {
+                from_current
+            } else {
+                // Shrunk to a point: rest-shape edges (r0 = 0, r1 = u, r2 = v)
+                (0.5 * (rest_v - rest_u).cross(n), 0.5 * (-rest_v).cross(n), 0.5 * rest_u.cross(n))
+            }
+        };
 
         let denom = w0 * grad0.length_squared() +
                     w1 * grad1.length_squared() +
@@ -126,11 +327,17 @@ impl AreaConstraint {
 
         if denom < 1e-9 { return; }
 
-        let delta_lambda = -c / (denom + alpha);
-        let lambda_omega = delta_lambda * omega;
+        let delta_lambda = -c / (denom + real(alpha));
+        let lambda_omega = delta_lambda * real(omega);
 
-        if w0 > 0.0 { state.positions[i0] += Vec4::from((grad0 * (lambda_omega * w0), 0.0)); }
-        if w1 > 0.0 { state.positions[i1] += Vec4::from((grad1 * (lambda_omega * w1), 0.0)); }
-        if w2 > 0.0 { state.positions[i2] += Vec4::from((grad2 * (lambda_omega * w2), 0.0)); }
+        if w0 > 0.0 {
+            state.positions[i0] = Vec4::from((from_real3(p0 + grad0 * (lambda_omega * w0)), 0.0));
+        }
+        if w1 > 0.0 {
+            state.positions[i1] = Vec4::from((from_real3(p1 + grad1 * (lambda_omega * w1)), 0.0));
+        }
+        if w2 > 0.0 {
+            state.positions[i2] = Vec4::from((from_real3(p2 + grad2 * (lambda_omega * w2)), 0.0));
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,112 @@
+// physics/src/systems/constraints/attachment.rs
+
+use glam::{Vec3, Vec4};
+use crate::collision::collider::MeshCollider;
+use crate::engine::state::PhysicsState;
+use super::mouse::stiffness_to_compliance;
+
+/// Sticks particles to the collider surface (waistbands, belts, clipped hems).
+/// Each attachment records the closest collider triangle, the barycentric coordinates
+/// of the closest point and the particle's signed offset along the face normal.
+/// Every substep the particle is pulled back to that (possibly animated) surface point,
+/// so it rides the body instead of sliding over it.
+pub struct AttachmentConstraint {
+    /// Internal particle indices.
+    pub particles: Vec<usize>,
+    /// Collider triangle each particle is bound to.
+    pub triangles: Vec<usize>,
+    pub barycentrics: Vec<[f32; 3]>,
+    /// Signed distance from the surface along the face normal, kept so the particle
+    /// stays at its contact gap instead of being pulled into the collider.
+    pub offsets: Vec<f32>,
+    /// XPBD compliance of the attachment. 0.0 = rigid.
+    pub compliance: f32,
+}
+
+impl Default for AttachmentConstraint {
+    fn default() -> Self {
+        Self {
+            particles: Vec::new(),
+            triangles: Vec::new(),
+            barycentrics: Vec::new(),
+            offsets: Vec::new(),
+            compliance: 0.0,
+        }
+    }
+}
+
+impl AttachmentConstraint {
+    /// Binds `particles` (internal indices) to their closest collider triangles at
+    /// their current positions. Does nothing for an empty collider.
+    pub fn new(state: &PhysicsState, collider: &MeshCollider, particles: &[usize]) -> Self {
+        let mut attachment = Self::default();
+        if collider.triangles.is_empty() {
+            return attachment;
+        }
+
+        for &i in particles {
+            let p = state.positions[i].truncate();
+
+            // One-off lookup, so a linear scan is fine (and has no radius to tune)
+            let mut best = (f32::MAX, 0, [1.0, 0.0, 0.0], Vec3::ZERO);
+            for (t, tri) in collider.triangles.iter().enumerate() {
+                let (closest, bary) = tri.closest_point(p);
+                let dist_sq = closest.distance_squared(p);
+                if dist_sq < best.0 {
+                    best = (dist_sq, t, bary, closest);
+                }
+            }
+
+            let (_, t, bary, closest) = best;
+            attachment.particles.push(i);
+            attachment.triangles.push(t);
+            attachment.barycentrics.push(bary);
+            attachment.offsets.push((p - closest).dot(collider.triangles[t].normal()));
+        }
+        attachment
+    }
+
+    /// Sets the attachment stiffness in `[0, 1]`. 1.0 = rigid; lower values let the
+    /// particle stretch away from the surface (same mapping as the grab springs).
+    pub fn set_stiffness(&mut self, stiffness: f32) {
+        self.compliance = stiffness_to_compliance(stiffness);
+    }
+
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    /// Rewrites the particle indices after the particles were reordered.
+    pub fn remap(&mut self, map: impl Fn(usize) -> usize) {
+        for i in self.particles.iter_mut() {
+            *i = map(*i);
+        }
+    }
+
+    /// Current target position of attachment `k` on the collider surface.
+    pub fn target(&self, collider: &MeshCollider, k: usize) -> Vec3 {
+        let tri = &collider.triangles[self.triangles[k]];
+        let [u, v, w] = self.barycentrics[k];
+        tri.v0 * u + tri.v1 * v + tri.v2 * w + tri.normal() * self.offsets[k]
+    }
+
+    /// Pulls every attached particle to its target. `collider` must be the one the
+    /// attachments were bound to.
+    pub fn solve(&self, state: &mut PhysicsState, collider: &MeshCollider, dt: f32) {
+        let alpha = self.compliance / (dt * dt);
+
+        for k in 0..self.particles.len() {
+            let idx = self.particles[k];
+            let w = state.inv_mass[idx];
+            if w == 0.0 { continue; }
+
+            let difference = self.target(collider, k) - state.positions[idx].truncate();
+            let multiplier = w / (w + alpha);
+            state.positions[idx] += Vec4::from((difference * multiplier, 0.0));
+        }
+    }
+}
@@ -1,7 +1,7 @@
 // physics/src/systems/constraints/bending/mod.rs
 
 //! Enforces dihedral angle preservation (Bend Resistance).
-//! Connects vertices that are two edges apart (bends).
+//! Connects the vertices on either side of every interior edge (bends).
 //! Uses limits and reduced compliance for "folding" behavior along anatomical creases.
 //!
 //! OPTIMIZATION: Uses true SIMD vectorization to process 4 constraints in parallel.
@@ -10,10 +10,10 @@ mod solver;
 
 use crate::engine::state::PhysicsState;
 use crate::utils::coloring;
-use std::collections::HashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 /// Enforces dihedral angle preservation (Bend Resistance).
-/// Connects vertices that are two edges apart (bends).
+/// Connects the vertices on either side of every interior edge (bends).
 /// Uses limits and reduced compliance for "folding" behavior along anatomical creases.
 ///
 /// OPTIMIZATION: Uses true SIMD vectorization to process 4 constraints in parallel.
@@ -22,60 +22,42 @@ pub struct BendingConstraint {
     pub rest_lengths: Vec<f32>,
     pub compliances: Vec<f32>,
     pub batch_offsets: Vec<usize>,
+    /// Accumulated XPBD multipliers (in `constraints` order), kept across substeps for
+    /// warm starting. Zeroed on construction, so a topology rebuild starts cold.
+    pub lambdas: Vec<f32>,
 }
 
 impl BendingConstraint {
-    /// Identifies bending pairs (neighbors of neighbors) and initializes constraints.
+    /// Identifies bending pairs (the vertices on either side of every interior edge)
+    /// and initializes constraints.
     pub fn new(state: &PhysicsState, compliance_factor: f32) -> Self {
-        let mut raw_constraints = Vec::new();
-        let mut raw_rest_lengths = Vec::new();
-        let mut raw_compliances = Vec::new();
+        let mut pairs = bending_pairs(state);
+        pairs.sort_unstable();
+        pairs.dedup();
 
-        let mut adj = vec![HashSet::new(); state.count];
-        let num_triangles = state.indices.len() / 3;
+        let mut raw_constraints = Vec::with_capacity(pairs.len());
+        let mut raw_rest_lengths = Vec::with_capacity(pairs.len());
+        let mut raw_compliances = Vec::with_capacity(pairs.len());
 
-        for i in 0..num_triangles {
-            let idx0 = state.indices[i * 3] as usize;
-            let idx1 = state.indices[i * 3 + 1] as usize;
-            let idx2 = state.indices[i * 3 + 2] as usize;
+        for [i, far_neighbor] in pairs {
+            let p1 = state.rest_positions[i];
+            let p2 = state.rest_positions[far_neighbor];
 
-            adj[idx0].insert(idx1); adj[idx0].insert(idx2);
-            adj[idx1].insert(idx0); adj[idx1].insert(idx2);
-            adj[idx2].insert(idx0); adj[idx2].insert(idx1);
-        }
-
-        let mut processed = HashSet::new();
-
-        for i in 0..state.count {
-            for &neighbor in &adj[i] {
-                for &far_neighbor in &adj[neighbor] {
-                    if i == far_neighbor { continue; }
-                    if adj[i].contains(&far_neighbor) { continue; }
-
-                    let pair = if i < far_neighbor { (i, far_neighbor) } else { (far_neighbor, i) };
-                    if processed.contains(&pair) { continue; }
-                    processed.insert(pair);
-
-                    let p1 = state.positions[i];
-                    let p2 = state.positions[far_neighbor];
-
-                    raw_constraints.push([i, far_neighbor]);
-                    raw_rest_lengths.push(p1.distance(p2));
+            raw_constraints.push([i, far_neighbor]);
+            raw_rest_lengths.push(p1.distance(p2));
 
-                    let uv1 = state.uvs[i];
-                    let uv2 = state.uvs[far_neighbor];
+            let uv1 = state.uvs[i];
+            let uv2 = state.uvs[far_neighbor];
 
-                    let du = (uv1.x - uv2.x).abs();
-                    let dv = (uv1.y - uv2.y).abs();
-                    let is_axis_aligned = du > 2.0 * dv || dv > 2.0 * du;
+            let du = (uv1.x - uv2.x).abs();
+            let dv = (uv1.y - uv2.y).abs();
+            let is_axis_aligned = du > 2.0 * dv || dv > 2.0 * du;
 
-                    // SOFTENED: 0.5 allows the cloth to fold.
-                    if is_axis_aligned {
-                        raw_compliances.push(0.5 * compliance_factor);
-                    } else {
-                        raw_compliances.push(1.0 * compliance_factor);
-                    }
-                }
+            // SOFTENED: 0.5 allows the cloth to fold.
+            if is_axis_aligned {
+                raw_compliances.push(0.5 * compliance_factor);
+            } else {
+                raw_compliances.push(1.0 * compliance_factor);
             }
         }
 
@@ -92,6 +74,7 @@ impl BendingConstraint {
         }
 
         Self {
+            lambdas: vec![0.0; constraints.len()],
             constraints,
             rest_lengths,
             compliances,
@@ -99,3 +82,70 @@ impl BendingConstraint {
         }
     }
 }
+
+/// One quad's view of a side edge: each endpoint with its neighbor away from the edge.
+type QuadSide = [(usize, usize); 2];
+
+/// Collects bending pairs `[min, max]` (possibly repeated) across interior edges only:
+/// an edge bends when exactly two faces share it, so boundary edges (hems) and
+/// non-manifold edges (unrelated flaps meeting at a seam) contribute nothing.
+///
+/// - Triangle edges pair the two wing vertices opposite the edge.
+/// - Quad edges (meshes with `state.quads`) pair, for each endpoint, its neighbors across
+///   the edge in the two quads: the straight 2-ring lines of the grid. Quad diagonals never
+///   hinge, so the triangulation's split direction does not bias the bending.
+fn bending_pairs(state: &PhysicsState) -> Vec<[usize; 2]> {
+    let topology = &state.topology;
+    let key = |a: usize, b: usize| (a.min(b), a.max(b));
+    let mut pairs = Vec::new();
+
+    let mut quad_sides: FxHashMap<(usize, usize), Vec<QuadSide>> = FxHashMap::default();
+    let mut quad_diagonals: FxHashSet<(usize, usize)> = FxHashSet::default();
+    for q in &state.quads {
+        for k in 0..4 {
+            let (v0, v1) = (q[k], q[(k + 1) % 4]);
+            quad_sides
+                .entry(key(v0, v1))
+                .or_default()
+                .push([(v0, q[(k + 3) % 4]), (v1, q[(k + 2) % 4])]);
+        }
+        quad_diagonals.insert(key(q[0], q[2]));
+        quad_diagonals.insert(key(q[1], q[3]));
+    }
+
+    for (e, &[e0, e1]) in topology.edges.iter().enumerate() {
+        if topology.edge_face_counts[e] != 2 || quad_diagonals.contains(&(e0, e1)) {
+            continue;
+        }
+
+        if let Some(sides) = quad_sides.get(&(e0, e1)) {
+            if let [first, second] = sides.as_slice() {
+                for &(v, across) in first {
+                    if let Some(&(_, other)) = second.iter().find(|&&(w, _)| w == v)
+                        && across != other
+                        && !topology.are_adjacent(across, other)
+                    {
+                        pairs.push([across.min(other), across.max(other)]);
+                    }
+                }
+            }
+            continue;
+        }
+
+        let [fa, fb] = topology.edge_faces[e];
+        let wing = |f: u32| {
+            let t = f as usize * 3;
+            state.indices[t..t + 3]
+                .iter()
+                .map(|&v| v as usize)
+                .find(|&v| v != e0 && v != e1)
+        };
+        if let (Some(a), Some(b)) = (wing(fa), wing(fb))
+            && a != b
+            && !topology.are_adjacent(a, b)
+        {
+            pairs.push([a.min(b), a.max(b)]);
+        }
+    }
+    pairs
+}
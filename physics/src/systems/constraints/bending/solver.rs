@@ -3,44 +3,125 @@
 //! SIMD-accelerated bending constraint solver.
 
 use crate::engine::state::PhysicsState;
+use crate::utils::real::{from_real, from_real3, real, to_real3, SIMD_ENABLED};
 use crate::utils::simd::{F32x4, Vec3x4};
+use glam::Vec4;
 use super::BendingConstraint;
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 impl BendingConstraint {
     /// Solves bending constraints using SIMD vectorization.
     /// OPTIMIZATION: Uses 4-wide SIMD for parallel processing.
-    #[inline(never)]
+    /// With the `parallel` feature, each color batch is split across threads.
     pub fn solve(&self, state: &mut PhysicsState, omega: f32, dt: f32) {
+        self.solve_batches(state, omega, dt, 0);
+    }
+
+    /// Full XPBD variant of `solve`: accumulates each constraint's Lagrange multiplier in
+    /// `lambdas` (including the `-alpha * lambda` term), so the next substep can warm-start from it.
+    pub fn solve_accumulate(&mut self, state: &mut PhysicsState, omega: f32, dt: f32) {
+        let lambdas = self.lambdas.as_mut_ptr() as usize;
+        self.solve_batches(state, omega, dt, lambdas);
+    }
+
+    /// Serial pass over `subset` (constraint indices, in batch order) with omega = 1 and
+    /// no multiplier accumulation. Used for the solver's extra per-region sweeps.
+    pub fn solve_subset(&self, state: &mut PhysicsState, subset: &[usize], dt: f32) {
         let dt_sq_inv = 1.0 / (dt * dt);
+        for &k in subset {
+            self.solve_single(state, k, dt_sq_inv, 1.0, 0);
+        }
+    }
+
+    /// Batch loop shared by `solve` and `solve_accumulate`.
+    /// `lambdas` is the address of the multiplier buffer, or 0 to skip accumulation
+    /// (passed as an integer so the parallel closures stay `Send`, like `StatePtr`).
+    #[inline(never)]
+    fn solve_batches(&self, state: &mut PhysicsState, omega: f32, dt: f32, lambdas: usize) {
+        let dt_sq_inv = 1.0 / (dt * dt);
+
+        // Safety: Graph coloring guarantees that constraints in the same batch
+        // do not share particles, so their position updates are disjoint.
+
+        #[cfg(feature = "parallel")]
+        {
+            struct StatePtr(pub usize);
+            unsafe impl Send for StatePtr {}
+            unsafe impl Sync for StatePtr {}
+            let state_ptr = StatePtr(state as *mut _ as usize);
+
+            for b in 0..(self.batch_offsets.len() - 1) {
+                let start = self.batch_offsets[b];
+                let end = self.batch_offsets[b + 1];
+                let count = end - start;
+
+                let num_chunks = count / 4;
+
+                (0..num_chunks).into_par_iter().for_each(move |chunk_idx| {
+                    let base = start + chunk_idx * 4;
+                    let state_ref = unsafe { &mut *(state_ptr.0 as *mut PhysicsState) };
+                    self.solve_chunk_4(state_ref, base, dt_sq_inv, omega, lambdas);
+                });
+
+                // Handle remainder sequentially
+                let remainder_start = start + num_chunks * 4;
+                let state_ref = unsafe { &mut *(state_ptr.0 as *mut PhysicsState) };
+                for k in remainder_start..end {
+                    self.solve_single(state_ref, k, dt_sq_inv, omega, lambdas);
+                }
+            }
+        }
 
-        for b in 0..(self.batch_offsets.len() - 1) {
-            let start = self.batch_offsets[b];
-            let end = self.batch_offsets[b + 1];
-            let count = end - start;
+        #[cfg(not(feature = "parallel"))]
+        {
+            for b in 0..(self.batch_offsets.len() - 1) {
+                let start = self.batch_offsets[b];
+                let end = self.batch_offsets[b + 1];
+                let count = end - start;
 
-            let chunks = count / 4;
-            let remainder = count % 4;
+                let chunks = count / 4;
+                let remainder = count % 4;
 
-            for chunk in 0..chunks {
-                let base = start + chunk * 4;
-                self.solve_simd_4(state, base, dt_sq_inv, omega);
+                for chunk in 0..chunks {
+                    let base = start + chunk * 4;
+                    self.solve_chunk_4(state, base, dt_sq_inv, omega, lambdas);
+                }
+
+                for k in (start + chunks * 4)..(start + chunks * 4 + remainder) {
+                    self.solve_single(state, k, dt_sq_inv, omega, lambdas);
+                }
             }
+        }
+    }
 
-            for k in (start + chunks * 4)..(start + chunks * 4 + remainder) {
-                self.solve_single(state, k, dt_sq_inv, omega);
+    /// Runs one 4-wide chunk: the SIMD kernel, or the scalar kernel under the `f64` feature.
+    #[inline(always)]
+    fn solve_chunk_4(&self, state: &mut PhysicsState, base: usize, dt_sq_inv: f32, omega: f32, lambdas: usize) {
+        if SIMD_ENABLED {
+            self.solve_simd_4(state, base, dt_sq_inv, omega, lambdas);
+        } else {
+            for k in base..base + 4 {
+                self.solve_single(state, k, dt_sq_inv, omega, lambdas);
             }
         }
     }
 
     /// SIMD-accelerated solver for 4 bending constraints at once.
     #[inline(always)]
-    fn solve_simd_4(&self, state: &mut PhysicsState, base: usize, dt_sq_inv: f32, omega: f32) {
+    fn solve_simd_4(&self, state: &mut PhysicsState, base: usize, dt_sq_inv: f32, omega: f32, lambdas: usize) {
         // Load indices
         let [i1_0, i2_0] = self.constraints[base];
         let [i1_1, i2_1] = self.constraints[base + 1];
         let [i1_2, i2_2] = self.constraints[base + 2];
         let [i1_3, i2_3] = self.constraints[base + 3];
 
+        // Skip chunks whose particles are all asleep
+        if state.all_asleep(&[i1_0, i2_0, i1_1, i2_1, i1_2, i2_2, i1_3, i2_3]) {
+            return;
+        }
+
         // Load inverse masses
         let w1 = F32x4::new(
             state.inv_mass[i1_0],
@@ -95,7 +176,14 @@ impl BendingConstraint {
         // delta_lambda = -C / (w_sum + alpha)
         let denom = w_sum.add(alpha);
         let safe_denom = denom.max(F32x4::splat(1e-8));
-        let delta_lambda = c.neg().div(safe_denom);
+        // Multipliers accumulated so far this substep (zero unless accumulating)
+        let lambda = if lambdas == 0 {
+            F32x4::splat(0.0)
+        } else {
+            let l = lambdas as *const f32;
+            unsafe { F32x4::new(*l.add(base), *l.add(base + 1), *l.add(base + 2), *l.add(base + 3)) }
+        };
+        let delta_lambda = c.neg().sub(alpha.mul(lambda)).div(safe_denom);
 
         // Normalize delta
         let safe_len = len.max(F32x4::splat(1e-8));
@@ -104,6 +192,14 @@ impl BendingConstraint {
         // Correction vector
         let omega_vec = F32x4::splat(omega);
         let correction_mag = delta_lambda.mul(omega_vec);
+        if lambdas != 0 {
+            // Each constraint index belongs to exactly one chunk, so these writes are disjoint
+            let total = lambda.add(correction_mag);
+            let l = lambdas as *mut f32;
+            for lane in 0..4 {
+                unsafe { *l.add(base + lane) = total.lane(lane) };
+            }
+        }
         let correction = direction.mul_scalar(correction_mag);
 
         // Apply corrections
@@ -148,27 +244,63 @@ impl BendingConstraint {
 
     /// Scalar fallback for remainder constraints.
     #[inline(always)]
-    fn solve_single(&self, state: &mut PhysicsState, k: usize, dt_sq_inv: f32, omega: f32) {
+    fn solve_single(&self, state: &mut PhysicsState, k: usize, dt_sq_inv: f32, omega: f32, lambdas: usize) {
         let [i1, i2] = self.constraints[k];
-        let w1 = state.inv_mass[i1];
-        let w2 = state.inv_mass[i2];
+        if state.all_asleep(&[i1, i2]) {
+            return;
+        }
+        let w1 = real(state.inv_mass[i1]);
+        let w2 = real(state.inv_mass[i2]);
         let w_sum = w1 + w2;
         if w_sum == 0.0 { return; }
 
-        let p1 = state.positions[i1];
-        let p2 = state.positions[i2];
+        let p1 = to_real3(state.positions[i1].truncate());
+        let p2 = to_real3(state.positions[i2].truncate());
         let delta = p1 - p2;
         let len = delta.length();
         if len < 1e-6 { return; }
 
-        let c = len - self.rest_lengths[k];
-        let alpha = self.compliances[k] * dt_sq_inv;
-        let delta_lambda = -c / (w_sum + alpha);
+        let c = len - real(self.rest_lengths[k]);
+        let alpha = real(self.compliances[k]) * real(dt_sq_inv);
+        let lambda = if lambdas == 0 { 0.0 } else { real(unsafe { *(lambdas as *const f32).add(k) }) };
+        let delta_lambda = (-c - alpha * lambda) / (w_sum + alpha);
+        if lambdas != 0 {
+            unsafe { *(lambdas as *mut f32).add(k) = from_real(lambda + delta_lambda * real(omega)) };
+        }
 
         let correction_vector = (delta / len) * delta_lambda;
-        let accelerated_correction = correction_vector * omega;
+        let accelerated_correction = correction_vector * real(omega);
+
+        if w1 > 0.0 {
+            state.positions[i1] = Vec4::from((from_real3(p1 + accelerated_correction * w1), 0.0));
+        }
+        if w2 > 0.0 {
+            state.positions[i2] = Vec4::from((from_real3(p2 - accelerated_correction * w2), 0.0));
+        }
+    }
+
+    /// Pre-applies the multipliers of the previous substep, scaled by `decay`, so the
+    /// iterations start close to the last solution instead of from rest.
+    /// The scaled values stay in `lambdas` as the starting point of `solve_accumulate`.
+    pub fn warm_start(&mut self, state: &mut PhysicsState, decay: f32) {
+        for (lambda, &[i1, i2]) in self.lambdas.iter_mut().zip(&self.constraints) {
+            *lambda *= decay;
+            if *lambda == 0.0 || state.all_asleep(&[i1, i2]) {
+                continue;
+            }
+            let delta = (state.positions[i1] - state.positions[i2]).truncate();
+            let len = delta.length();
+            if len < 1e-6 {
+                continue;
+            }
+            let correction = delta * (*lambda / len);
+            state.positions[i1] += Vec4::from((correction * state.inv_mass[i1], 0.0));
+            state.positions[i2] -= Vec4::from((correction * state.inv_mass[i2], 0.0));
+        }
+    }
 
-        if w1 > 0.0 { state.positions[i1] += accelerated_correction * w1; }
-        if w2 > 0.0 { state.positions[i2] -= accelerated_correction * w2; }
+    /// Forgets the accumulated multipliers (e.g. after the substep length changed).
+    pub fn reset_lambdas(&mut self) {
+        self.lambdas.fill(0.0);
     }
 }
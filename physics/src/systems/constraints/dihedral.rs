@@ -0,0 +1,208 @@
+// physics/src/systems/constraints/dihedral.rs
+
+//! Enforces the dihedral angle across every interior edge (true hinge bending).
+//! The rest angle is captured from the initial mesh, so authored pleats, darts and
+//! creases are preserved instead of being driven towards flat.
+//! Alternatively each hinge can fold freely between two angle stops (see `solve`).
+
+use crate::engine::state::PhysicsState;
+use crate::engine::topology::NO_FACE;
+use crate::utils::coloring;
+use glam::{Vec3, Vec4};
+use std::f32::consts::{PI, TAU};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Dihedral (hinge) bending constraint across interior edges.
+/// Each constraint is `[e0, e1, a, b]`: the shared edge `e0-e1` and the opposite
+/// ("wing") vertices of the two adjacent triangles.
+pub struct DihedralConstraint {
+    pub constraints: Vec<[usize; 4]>,
+    /// Signed rest angle (radians, 0 = flat) captured from the initial mesh.
+    pub rest_angles: Vec<f32>,
+    pub batch_offsets: Vec<usize>,
+}
+
+/// Signed dihedral angle of the hinge `[e0, e1, a, b]` (0 = flat, range (-PI, PI]) and its
+/// gradient w.r.t. each of the four points (Bridson et al. 2003 bending modes).
+/// Returns `None` for degenerate (zero-area or zero-length) configurations.
+pub fn dihedral_angle_and_gradients(p: [Vec3; 4]) -> Option<(f32, [Vec3; 4])> {
+    let [x0, x1, xa, xb] = p;
+    let e = x1 - x0;
+    let e_len = e.length();
+    if e_len < 1e-9 {
+        return None;
+    }
+    let e_hat = e / e_len;
+
+    let n1 = (xa - x0).cross(xa - x1);
+    let n2 = (xb - x1).cross(xb - x0);
+    let n1_sq = n1.length_squared();
+    let n2_sq = n2.length_squared();
+    if n1_sq < 1e-18 || n2_sq < 1e-18 {
+        return None;
+    }
+
+    let n1_hat = n1 / n1_sq.sqrt();
+    let n2_hat = n2 / n2_sq.sqrt();
+    let angle = n1_hat.cross(n2_hat).dot(e_hat).atan2(n1_hat.dot(n2_hat));
+
+    let m1 = n1 / n1_sq;
+    let m2 = n2 / n2_sq;
+    let grad_a = -e_len * m1;
+    let grad_b = -e_len * m2;
+    let grad_0 = -((xa - x1).dot(e_hat) * m1 + (xb - x1).dot(e_hat) * m2);
+    let grad_1 = (xa - x0).dot(e_hat) * m1 + (xb - x0).dot(e_hat) * m2;
+
+    Some((angle, [grad_0, grad_1, grad_a, grad_b]))
+}
+
+/// Wraps an angle difference into (-PI, PI].
+#[inline]
+fn wrap_angle(a: f32) -> f32 {
+    let mut a = a % TAU;
+    if a > PI {
+        a -= TAU;
+    } else if a <= -PI {
+        a += TAU;
+    }
+    a
+}
+
+impl DihedralConstraint {
+    /// Builds one hinge per interior edge (exactly two adjacent triangles).
+    /// Boundary and non-manifold edges are skipped.
+    pub fn new(state: &PhysicsState) -> Self {
+        let topology = &state.topology;
+        let mut raw_constraints = Vec::new();
+        let mut raw_rest_angles = Vec::new();
+
+        for (e, &[e0, e1]) in topology.edges.iter().enumerate() {
+            if topology.edge_face_counts[e] != 2 {
+                continue;
+            }
+            let [fa, fb] = topology.edge_faces[e];
+            debug_assert!(fa != NO_FACE && fb != NO_FACE);
+
+            let wing = |f: u32| {
+                let t = f as usize * 3;
+                state.indices[t..t + 3]
+                    .iter()
+                    .map(|&v| v as usize)
+                    .find(|&v| v != e0 && v != e1)
+            };
+            let (Some(a), Some(b)) = (wing(fa), wing(fb)) else { continue };
+            if a == b {
+                continue;
+            }
+
+            let hinge = [e0, e1, a, b];
+            let points = hinge.map(|i| state.rest_positions[i].truncate());
+            let Some((angle, _)) = dihedral_angle_and_gradients(points) else { continue };
+
+            raw_constraints.push(hinge);
+            raw_rest_angles.push(angle);
+        }
+
+        let (sorted_indices, batch_offsets) =
+            coloring::color_constraints_4(&raw_constraints, state.count);
+
+        let mut constraints = Vec::with_capacity(raw_constraints.len());
+        let mut rest_angles = Vec::with_capacity(raw_constraints.len());
+        for idx in sorted_indices {
+            constraints.push(raw_constraints[idx]);
+            rest_angles.push(raw_rest_angles[idx]);
+        }
+
+        Self {
+            constraints,
+            rest_angles,
+            batch_offsets,
+        }
+    }
+
+    /// Solves all hinges batch by batch (XPBD).
+    /// `limits` = `Some((min, max))` leaves a hinge free while its angle is within the stops
+    /// and only pushes it back to the nearest one; `None` holds every hinge at its rest angle.
+    /// With the `parallel` feature, each color batch is split across threads.
+    #[inline(never)]
+    pub fn solve(
+        &self,
+        state: &mut PhysicsState,
+        compliance: f32,
+        limits: Option<(f32, f32)>,
+        omega: f32,
+        dt: f32,
+    ) {
+        let alpha = compliance / (dt * dt);
+
+        // Safety: `color_constraints_4` guarantees that hinges in the same batch
+        // do not share particles, so their position updates are disjoint.
+
+        #[cfg(feature = "parallel")]
+        {
+            struct StatePtr(pub usize);
+            unsafe impl Send for StatePtr {}
+            unsafe impl Sync for StatePtr {}
+            let state_ptr = StatePtr(state as *mut _ as usize);
+
+            for b in 0..self.batch_offsets.len().saturating_sub(1) {
+                let start = self.batch_offsets[b];
+                let end = self.batch_offsets[b + 1];
+
+                (start..end).into_par_iter().for_each(|k| {
+                    let state_ref = unsafe { &mut *(state_ptr.0 as *mut PhysicsState) };
+                    self.solve_single(state_ref, k, alpha, limits, omega);
+                });
+            }
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            for k in 0..self.constraints.len() {
+                self.solve_single(state, k, alpha, limits, omega);
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn solve_single(
+        &self,
+        state: &mut PhysicsState,
+        k: usize,
+        alpha: f32,
+        limits: Option<(f32, f32)>,
+        omega: f32,
+    ) {
+        let hinge = self.constraints[k];
+        if state.all_asleep(&hinge) { return; }
+
+        let w = hinge.map(|i| state.inv_mass[i]);
+        if w.iter().all(|&wi| wi == 0.0) { return; }
+
+        let points = hinge.map(|i| state.positions[i].truncate());
+        let Some((angle, grads)) = dihedral_angle_and_gradients(points) else { return };
+
+        let c = match limits {
+            Some((min, _)) if angle < min => angle - min,
+            Some((_, max)) if angle > max => angle - max,
+            Some(_) => return,
+            None => wrap_angle(angle - self.rest_angles[k]),
+        };
+        if c.abs() < 1e-6 { return; }
+
+        let mut denom = alpha;
+        for j in 0..4 {
+            denom += w[j] * grads[j].length_squared();
+        }
+        if denom < 1e-12 { return; }
+
+        let lambda_omega = (-c / denom) * omega;
+        for j in 0..4 {
+            if w[j] > 0.0 {
+                state.positions[hinge[j]] += Vec4::from((grads[j] * (lambda_omega * w[j]), 0.0));
+            }
+        }
+    }
+}
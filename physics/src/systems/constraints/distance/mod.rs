@@ -10,7 +10,6 @@ mod solver;
 
 use crate::engine::state::PhysicsState;
 use crate::utils::coloring;
-use std::collections::HashMap;
 
 /// Enforces edge length preservation (Stretch Resistance).
 /// Uses XPBD (Extended Position Based Dynamics) to handle stiffness compliance.
@@ -22,47 +21,28 @@ pub struct DistanceConstraint {
     pub rest_lengths: Vec<f32>,
     pub compliances: Vec<f32>,
     pub batch_offsets: Vec<usize>,
+    /// Accumulated XPBD multipliers (in `constraints` order), kept across substeps for
+    /// warm starting. Zeroed on construction, so a topology rebuild starts cold.
+    pub lambdas: Vec<f32>,
 }
 
 impl DistanceConstraint {
-    /// Builds distance constraints for every unique edge in the mesh.
+    /// Builds distance constraints for every unique edge in the mesh
+    /// (plus the missing diagonal of every source quad).
+    /// Edges are taken in `(min, max)` order, then the sorted diagonals, so the constraint
+    /// order (and with it the coloring and the solve order) is the same on every run.
     pub fn new(state: &PhysicsState, compliance: f32) -> Self {
+        debug_assert!(
+            state.topology.edges.windows(2).all(|w| w[0] < w[1]),
+            "MeshTopology::edges must be sorted and unique"
+        );
         let mut raw_constraints = Vec::new();
         let mut raw_rest_lengths = Vec::new();
         let mut raw_compliances = Vec::new();
 
-        let mut edge_counts = HashMap::new();
-        let num_triangles = state.indices.len() / 3;
-
-        for i in 0..num_triangles {
-            let idx0 = state.indices[i * 3] as usize;
-            let idx1 = state.indices[i * 3 + 1] as usize;
-            let idx2 = state.indices[i * 3 + 2] as usize;
-
-            let e1 = if idx0 < idx1 {
-                (idx0, idx1)
-            } else {
-                (idx1, idx0)
-            };
-            let e2 = if idx1 < idx2 {
-                (idx1, idx2)
-            } else {
-                (idx2, idx1)
-            };
-            let e3 = if idx2 < idx0 {
-                (idx2, idx0)
-            } else {
-                (idx0, idx2)
-            };
-
-            *edge_counts.entry(e1).or_insert(0) += 1;
-            *edge_counts.entry(e2).or_insert(0) += 1;
-            *edge_counts.entry(e3).or_insert(0) += 1;
-        }
-
-        for ((i1, i2), _count) in edge_counts {
-            let p1 = state.positions[i1];
-            let p2 = state.positions[i2];
+        for &[i1, i2] in &state.topology.edges {
+            let p1 = state.rest_positions[i1];
+            let p2 = state.rest_positions[i2];
             let dist = p1.distance(p2);
 
             raw_constraints.push([i1, i2]);
@@ -70,6 +50,22 @@ impl DistanceConstraint {
             raw_compliances.push(compliance);
         }
 
+        // Quad meshes: constrain the diagonal the triangulation left out, so shear
+        // resistance is symmetric instead of following the split direction
+        let mut diagonals: Vec<[usize; 2]> = state
+            .quads
+            .iter()
+            .flat_map(|&[a, b, c, d]| [[a.min(c), a.max(c)], [b.min(d), b.max(d)]])
+            .filter(|&[i1, i2]| i1 != i2 && state.topology.edge_index(i1, i2).is_none())
+            .collect();
+        diagonals.sort_unstable();
+        diagonals.dedup();
+        for [i1, i2] in diagonals {
+            raw_constraints.push([i1, i2]);
+            raw_rest_lengths.push(state.rest_positions[i1].distance(state.rest_positions[i2]));
+            raw_compliances.push(compliance);
+        }
+
         let (sorted_indices, batch_offsets) =
             coloring::color_constraints(&raw_constraints, state.count);
 
@@ -84,10 +80,30 @@ impl DistanceConstraint {
         }
 
         Self {
+            lambdas: vec![0.0; constraints.len()],
             constraints,
             rest_lengths,
             compliances,
             batch_offsets,
         }
     }
+
+    /// Sets each edge's compliance from a per-particle stiffness map (0 = soft, 1 = stiff):
+    /// `compliance = (1 - avg(stiffness[i], stiffness[j])) * scale`, never below `min_compliance`.
+    /// `stiffness` is indexed by (internal) particle index.
+    pub fn apply_stiffness_map(&mut self, stiffness: &[f32], scale: f32, min_compliance: f32) {
+        for (c, &[i1, i2]) in self.compliances.iter_mut().zip(&self.constraints) {
+            let s = (0.5 * (stiffness[i1] + stiffness[i2])).clamp(0.0, 1.0);
+            *c = ((1.0 - s) * scale).max(min_compliance);
+        }
+    }
+
+    /// Total violation `sum(|len - rest|)` over all edges (for convergence diagnostics).
+    pub fn residual(&self, state: &PhysicsState) -> f32 {
+        self.constraints
+            .iter()
+            .zip(&self.rest_lengths)
+            .map(|(&[i1, i2], &rest)| (state.positions[i1].distance(state.positions[i2]) - rest).abs())
+            .sum()
+    }
 }
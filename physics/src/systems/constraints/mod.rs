@@ -5,9 +5,13 @@ pub mod bending;
 pub mod tether;
 pub mod mouse;
 pub mod area;
+pub mod dihedral;
+pub mod attachment;
 
 pub use distance::DistanceConstraint;
 pub use bending::BendingConstraint;
-pub use tether::TetherConstraint;
-pub use mouse::MouseConstraint;
-pub use area::AreaConstraint;
\ No newline at end of file
+pub use tether::{TetherConstraint, TetherGroup, TetherMode};
+pub use mouse::{GroupGrabConstraint, MouseConstraint};
+pub use area::AreaConstraint;
+pub use dihedral::DihedralConstraint;
+pub use attachment::AttachmentConstraint;
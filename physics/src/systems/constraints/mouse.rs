@@ -1,16 +1,39 @@
 // physics/src/systems/constraints/mouse.rs
 
 use glam::{Vec3, Vec4};
+use crate::collision::collider::MeshCollider;
 use crate::engine::state::PhysicsState;
 
+/// Compliance of a grab or attachment spring at stiffness 0.5 (m/N).
+pub const HALF_STIFFNESS_COMPLIANCE: f32 = 1.0e-4;
+
+/// Maps a `[0, 1]` stiffness to XPBD compliance: 1.0 = rigid (compliance 0), 0.5 =
+/// `HALF_STIFFNESS_COMPLIANCE`, and the spring goes smoothly limp (infinite compliance,
+/// no pull at all) as it approaches 0. Out-of-range values are clamped.
+pub fn stiffness_to_compliance(stiffness: f32) -> f32 {
+    let s = stiffness.clamp(0.0, 1.0);
+    if s == 0.0 {
+        f32::INFINITY
+    } else {
+        HALF_STIFFNESS_COMPLIANCE * (1.0 - s) / s
+    }
+}
+
 /// Handles user interaction forces (Grabbing and Dragging).
 /// Applies a spring force between a grabbed particle and the mouse cursor's 3D projection.
 pub struct MouseConstraint {
     pub grabbed_index: Option<usize>,
     pub target_position: Vec3,
+    /// XPBD compliance of the grab spring. 0.0 = rigid (particle snaps to the cursor).
     pub compliance: f32,
 }
 
+impl Default for MouseConstraint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MouseConstraint {
     pub fn new() -> Self {
         Self {
@@ -20,6 +43,12 @@ impl MouseConstraint {
         }
     }
 
+    /// Sets the grab spring stiffness in `[0, 1]`. 1.0 = rigid snap; lower values drag
+    /// more elastically (see `stiffness_to_compliance`).
+    pub fn set_stiffness(&mut self, stiffness: f32) {
+        self.compliance = stiffness_to_compliance(stiffness);
+    }
+
     pub fn grab(&mut self, index: usize, position: Vec3) {
         self.grabbed_index = Some(index);
         self.target_position = position;
@@ -52,4 +81,91 @@ impl MouseConstraint {
             state.positions[idx] += Vec4::from((difference * multiplier, 0.0));
         }
     }
-}
\ No newline at end of file
+
+    /// Continuous collision for the grabbed particle: sweeps its motion since `from`
+    /// against the collider and clamps it to the first hit, `thickness` off the surface.
+    /// Run right after `solve`, before the other constraints, so a fast drag cannot
+    /// teleport the particle through the body.
+    pub fn sweep(&self, state: &mut PhysicsState, collider: &MeshCollider, from: Vec3, thickness: f32) {
+        if let Some(idx) = self.grabbed_index {
+            if idx >= state.count || state.inv_mass[idx] == 0.0 { return; }
+
+            let to = state.positions[idx].truncate();
+            if let Some((hit_point, normal, _)) = collider.sweep_segment(from, to) {
+                state.positions[idx] = Vec4::from((hit_point + normal * thickness, 0.0));
+            }
+        }
+    }
+}
+
+/// Drags a cluster of particles together (e.g. a hand grabbing a fistful of cloth).
+/// Each particle keeps its position relative to the others: the targets are the grab-time
+/// positions plus one shared translation, so the cluster moves rigidly.
+pub struct GroupGrabConstraint {
+    pub indices: Vec<usize>,
+    /// Positions at grab time, one per entry of `indices`.
+    pub anchors: Vec<Vec3>,
+    /// Accumulated translation since the grab.
+    pub offset: Vec3,
+    /// XPBD compliance of the grab springs. 0.0 = rigid.
+    pub compliance: f32,
+}
+
+impl Default for GroupGrabConstraint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GroupGrabConstraint {
+    pub fn new() -> Self {
+        Self {
+            indices: Vec::new(),
+            anchors: Vec::new(),
+            offset: Vec3::ZERO,
+            compliance: 0.0,
+        }
+    }
+
+    /// Same mapping as `MouseConstraint::set_stiffness`.
+    pub fn set_stiffness(&mut self, stiffness: f32) {
+        self.compliance = stiffness_to_compliance(stiffness);
+    }
+
+    /// Grabs `indices` (internal) at their current positions, replacing any previous group.
+    pub fn grab(&mut self, state: &PhysicsState, indices: &[usize]) {
+        self.indices = indices.iter().copied().filter(|&i| i < state.count).collect();
+        self.anchors = self.indices.iter().map(|&i| state.positions[i].truncate()).collect();
+        self.offset = Vec3::ZERO;
+    }
+
+    /// Moves the whole group by `delta` on top of the previous updates.
+    pub fn translate(&mut self, delta: Vec3) {
+        self.offset += delta;
+    }
+
+    pub fn release(&mut self) {
+        self.indices.clear();
+        self.anchors.clear();
+        self.offset = Vec3::ZERO;
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.indices.is_empty()
+    }
+
+    pub fn solve(&self, state: &mut PhysicsState, dt: f32) {
+        let alpha = self.compliance / (dt * dt);
+
+        for (&idx, &anchor) in self.indices.iter().zip(&self.anchors) {
+            if idx >= state.count { continue; }
+
+            let w = state.inv_mass[idx];
+            if w == 0.0 { continue; }
+
+            let difference = anchor + self.offset - state.positions[idx].truncate();
+            let multiplier = w / (w + alpha);
+            state.positions[idx] += Vec4::from((difference * multiplier, 0.0));
+        }
+    }
+}
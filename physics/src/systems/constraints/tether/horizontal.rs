@@ -1,7 +1,8 @@
 // physics/src/systems/constraints/tether/horizontal.rs
 
+use super::facing_normal;
 use crate::engine::state::PhysicsState;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 /// Generates horizontal tethers (Shoulder-to-Shoulder, Torso-to-Torso).
 /// Scans the mesh in Z-slices and connects particles on the left side to the right side.
@@ -11,20 +12,21 @@ pub fn generate(state: &PhysicsState) -> (Vec<[usize; 2]>, Vec<f32>) {
     let mut rest_lengths = Vec::new();
 
     let mut max_y = f32::MIN;
-    for p in &state.positions {
+    for p in &state.rest_positions {
         if p.y > max_y { max_y = p.y; }
     }
 
     let shoulder_threshold = max_y - 0.15;
     let z_cell_size = 0.04;
-    let mut rows: HashMap<i32, Vec<usize>> = HashMap::new();
+    // Ordered map: a hashed one would shuffle the tethers between runs
+    let mut rows: BTreeMap<i32, Vec<usize>> = BTreeMap::new();
 
     for i in 0..state.count {
-        let p = state.positions[i];
+        let p = state.rest_positions[i];
         if p.y < shoulder_threshold { continue; }
 
         let cell_z = (p.z / z_cell_size).floor() as i32;
-        rows.entry(cell_z).or_insert_with(Vec::new).push(i);
+        rows.entry(cell_z).or_default().push(i);
     }
 
     for (_, indices) in rows {
@@ -32,7 +34,7 @@ pub fn generate(state: &PhysicsState) -> (Vec<[usize; 2]>, Vec<f32>) {
 
         let mut sorted = indices.clone();
         sorted.sort_by(|&a, &b| {
-            state.positions[a].x.partial_cmp(&state.positions[b].x).unwrap()
+            state.rest_positions[a].x.partial_cmp(&state.rest_positions[b].x).unwrap()
         });
 
         let count = sorted.len();
@@ -42,13 +44,13 @@ pub fn generate(state: &PhysicsState) -> (Vec<[usize; 2]>, Vec<f32>) {
             let left = sorted[i];
             let right = sorted[count - 1 - i];
 
-            let p1 = state.positions[left];
-            let p2 = state.positions[right];
+            let p1 = state.rest_positions[left];
+            let p2 = state.rest_positions[right];
             let dist = p1.distance(p2);
 
             if dist > 0.15 {
-                let n1 = state.normals[left];
-                let n2 = state.normals[right];
+                let n1 = facing_normal(state, left);
+                let n2 = facing_normal(state, right);
 
                 if n1.dot(n2) > 0.5 {
                     constraints.push([left, right]);
@@ -5,7 +5,66 @@ mod horizontal;
 
 use crate::engine::state::PhysicsState;
 use crate::utils::coloring;
+use crate::utils::real::{from_real3, real, to_real3, SIMD_ENABLED};
 use crate::utils::simd::{F32x4, Vec3x4};
+use glam::{Vec3, Vec4};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Which generator produced a tether; each group gets its own compliance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TetherGroup {
+    /// Anti-sag columns hanging from the top of the garment.
+    Vertical,
+    /// Anti-widen bridges across the garment (e.g. shoulders).
+    Horizontal,
+}
+
+impl TetherGroup {
+    /// Group from its index (0 = Vertical, 1 = Horizontal), for JS.
+    pub fn from_index(index: u8) -> Option<Self> {
+        match index {
+            0 => Some(TetherGroup::Vertical),
+            1 => Some(TetherGroup::Horizontal),
+            _ => None,
+        }
+    }
+}
+
+/// Which length violations a tether corrects.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TetherMode {
+    /// Only pulls back when longer than rest (anti-sag; the cloth may still bunch up).
+    #[default]
+    Extension,
+    /// Only pushes apart when shorter than rest (e.g. boning that must not fold in).
+    Compression,
+    /// Holds the rest length both ways (a rigid strut).
+    Bilateral,
+}
+
+impl TetherMode {
+    /// Mode from its index (0 = Extension, 1 = Compression, 2 = Bilateral), for JS.
+    pub fn from_index(index: u8) -> Option<Self> {
+        match index {
+            0 => Some(TetherMode::Extension),
+            1 => Some(TetherMode::Compression),
+            2 => Some(TetherMode::Bilateral),
+            _ => None,
+        }
+    }
+
+    /// Allowed range of the constraint value `C = len - rest`; zero outside it.
+    #[inline(always)]
+    fn bounds(self) -> (f32, f32) {
+        match self {
+            TetherMode::Extension => (0.0, f32::INFINITY),
+            TetherMode::Compression => (f32::NEG_INFINITY, 0.0),
+            TetherMode::Bilateral => (f32::NEG_INFINITY, f32::INFINITY),
+        }
+    }
+}
 
 /// Enforces global length limits (Long-Range Attachment).
 /// "Tethers" particles to stable anchor points to prevent excessive stretching
@@ -15,70 +74,214 @@ use crate::utils::simd::{F32x4, Vec3x4};
 pub struct TetherConstraint {
     pub constraints: Vec<[usize; 2]>,
     pub rest_lengths: Vec<f32>,
+    /// Generator of each constraint (same order as `constraints`).
+    pub groups: Vec<TetherGroup>,
+    /// Which violations each constraint corrects (same order as `constraints`).
+    /// Generated tethers start as `Extension`.
+    pub modes: Vec<TetherMode>,
     pub batch_offsets: Vec<usize>,
 }
 
+/// Normal used by the generators' facing checks. On an open boundary (a scarf edge,
+/// a cape rim) the vertex normal averages few, possibly opposing faces and can collapse
+/// to zero, so a boundary vertex borrows the mean normal of its interior neighbors.
+fn facing_normal(state: &PhysicsState, i: usize) -> Vec3 {
+    let own = state.normals[i].truncate();
+    let topology = &state.topology;
+    if !topology.is_boundary(i) {
+        return own;
+    }
+    let interior: Vec3 = topology
+        .neighbors(i)
+        .iter()
+        .filter(|&&n| !topology.is_boundary(n as usize))
+        .map(|&n| state.normals[n as usize].truncate())
+        .sum();
+    interior.try_normalize().unwrap_or(own)
+}
+
 impl TetherConstraint {
     /// Generates Vertical and Horizontal tethers based on UV coordinates or topology.
     pub fn new(state: &PhysicsState) -> Self {
         let (v_constraints, v_lengths) = vertical::generate(state);
         let (h_constraints, h_lengths) = horizontal::generate(state);
 
+        let mut raw_groups = vec![TetherGroup::Vertical; v_constraints.len()];
+        raw_groups.resize(v_constraints.len() + h_constraints.len(), TetherGroup::Horizontal);
+
         let mut raw_constraints = v_constraints;
         raw_constraints.extend(h_constraints);
 
         let mut raw_rest_lengths = v_lengths;
         raw_rest_lengths.extend(h_lengths);
 
-        let (sorted_indices, batch_offsets) = coloring::color_constraints(&raw_constraints, state.count);
+        let raw_modes = vec![TetherMode::Extension; raw_constraints.len()];
+        let mut tether = Self {
+            constraints: raw_constraints,
+            rest_lengths: raw_rest_lengths,
+            groups: raw_groups,
+            modes: raw_modes,
+            batch_offsets: Vec::new(),
+        };
+        tether.recolor(state.count);
+        tether
+    }
 
-        let mut constraints = Vec::with_capacity(raw_constraints.len());
-        let mut rest_lengths = Vec::with_capacity(raw_constraints.len());
+    /// Appends tethers between `pairs` (internal indices) at their rest-shape distance,
+    /// solved with `group`'s compliance and correcting what `mode` allows (e.g. a
+    /// `Bilateral` strut for corset boning), then recolors the batches.
+    pub fn add(&mut self, state: &PhysicsState, pairs: &[[usize; 2]], group: TetherGroup, mode: TetherMode) {
+        for &[a, b] in pairs {
+            let rest = state.rest_positions[a].distance(state.rest_positions[b]);
+            self.constraints.push([a, b]);
+            self.rest_lengths.push(rest);
+            self.groups.push(group);
+            self.modes.push(mode);
+        }
+        self.recolor(state.count);
+    }
 
-        for idx in sorted_indices {
-            constraints.push(raw_constraints[idx]);
-            rest_lengths.push(raw_rest_lengths[idx]);
+    /// Sets the mode of every tether between particles `a` and `b` (internal indices,
+    /// either order). Returns how many tethers were tagged.
+    pub fn set_mode(&mut self, a: usize, b: usize, mode: TetherMode) -> usize {
+        let mut tagged = 0;
+        for (k, &[i, j]) in self.constraints.iter().enumerate() {
+            if (i, j) == (a, b) || (i, j) == (b, a) {
+                self.modes[k] = mode;
+                tagged += 1;
+            }
         }
+        tagged
+    }
 
-        Self {
-            constraints,
-            rest_lengths,
-            batch_offsets,
+    /// Sets the mode of every tether in `group`.
+    pub fn set_group_mode(&mut self, group: TetherGroup, mode: TetherMode) {
+        for (m, &g) in self.modes.iter_mut().zip(&self.groups) {
+            if g == group {
+                *m = mode;
+            }
         }
     }
 
+    /// Re-runs the graph coloring and sorts every per-constraint array by batch.
+    fn recolor(&mut self, particle_count: usize) {
+        let (sorted_indices, batch_offsets) = coloring::color_constraints(&self.constraints, particle_count);
+        self.constraints = sorted_indices.iter().map(|&k| self.constraints[k]).collect();
+        self.rest_lengths = sorted_indices.iter().map(|&k| self.rest_lengths[k]).collect();
+        self.groups = sorted_indices.iter().map(|&k| self.groups[k]).collect();
+        self.modes = sorted_indices.iter().map(|&k| self.modes[k]).collect();
+        self.batch_offsets = batch_offsets;
+    }
+
     /// Solves tether constraints using SIMD vectorization.
+    /// `compliances` = XPBD compliance of the `[Vertical, Horizontal]` groups (0.0 = inextensible);
+    /// each constraint's `modes` entry picks which violations it corrects.
     /// OPTIMIZATION: Processes 4 constraints at a time.
+    /// With the `parallel` feature, each color batch is split across threads.
     #[inline(never)]
-    pub fn solve(&self, state: &mut PhysicsState, omega: f32, _dt: f32) {
-        for b in 0..(self.batch_offsets.len() - 1) {
-            let start = self.batch_offsets[b];
-            let end = self.batch_offsets[b + 1];
-            let count = end - start;
-
-            let chunks = count / 4;
-            let remainder = count % 4;
-
-            for chunk in 0..chunks {
-                let base = start + chunk * 4;
-                self.solve_simd_4(state, base, omega);
+    pub fn solve(
+        &self,
+        state: &mut PhysicsState,
+        compliances: [f32; 2],
+        omega: f32,
+        dt: f32,
+    ) {
+        let params = GroupParams {
+            alphas: compliances.map(|c| c / (dt * dt)),
+        };
+
+        // Safety: Graph coloring guarantees that constraints in the same batch
+        // do not share particles, so their position updates are disjoint.
+
+        #[cfg(feature = "parallel")]
+        {
+            struct StatePtr(pub usize);
+            unsafe impl Send for StatePtr {}
+            unsafe impl Sync for StatePtr {}
+            let state_ptr = StatePtr(state as *mut _ as usize);
+
+            for b in 0..(self.batch_offsets.len() - 1) {
+                let start = self.batch_offsets[b];
+                let end = self.batch_offsets[b + 1];
+                let count = end - start;
+
+                let num_chunks = count / 4;
+
+                (0..num_chunks).into_par_iter().for_each(move |chunk_idx| {
+                    let base = start + chunk_idx * 4;
+                    let state_ref = unsafe { &mut *(state_ptr.0 as *mut PhysicsState) };
+                    self.solve_chunk_4(state_ref, base, &params, omega);
+                });
+
+                // Handle remainder sequentially
+                let remainder_start = start + num_chunks * 4;
+                let state_ref = unsafe { &mut *(state_ptr.0 as *mut PhysicsState) };
+                for k in remainder_start..end {
+                    self.solve_single(state_ref, k, &params, omega);
+                }
+            }
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            for b in 0..(self.batch_offsets.len() - 1) {
+                let start = self.batch_offsets[b];
+                let end = self.batch_offsets[b + 1];
+                let count = end - start;
+
+                let chunks = count / 4;
+                let remainder = count % 4;
+
+                for chunk in 0..chunks {
+                    let base = start + chunk * 4;
+                    self.solve_chunk_4(state, base, &params, omega);
+                }
+
+                for k in (start + chunks * 4)..(start + chunks * 4 + remainder) {
+                    self.solve_single(state, k, &params, omega);
+                }
             }
+        }
+    }
 
-            for k in (start + chunks * 4)..(start + chunks * 4 + remainder) {
-                self.solve_single(state, k, omega);
+    /// Runs one 4-wide chunk: the SIMD kernel, or the scalar kernel under the `f64` feature.
+    #[inline(always)]
+    fn solve_chunk_4(&self, state: &mut PhysicsState, base: usize, params: &GroupParams, omega: f32) {
+        if SIMD_ENABLED {
+            self.solve_simd_4(state, base, params, omega);
+        } else {
+            for k in base..base + 4 {
+                self.solve_single(state, k, params, omega);
             }
         }
     }
 
+    /// Time-scaled compliance of constraint `k` (alpha / dt^2 of its group).
+    #[inline(always)]
+    fn alpha(&self, k: usize, params: &GroupParams) -> f32 {
+        params.alphas[self.groups[k] as usize]
+    }
+
+    /// Allowed `C` range of constraint `k` (from its mode).
+    #[inline(always)]
+    fn bounds(&self, k: usize) -> (f32, f32) {
+        self.modes[k].bounds()
+    }
+
     /// SIMD-accelerated tether solver for 4 constraints.
     #[inline(always)]
-    fn solve_simd_4(&self, state: &mut PhysicsState, base: usize, omega: f32) {
+    fn solve_simd_4(&self, state: &mut PhysicsState, base: usize, params: &GroupParams, omega: f32) {
         // Load indices
         let [i1_0, i2_0] = self.constraints[base];
         let [i1_1, i2_1] = self.constraints[base + 1];
         let [i1_2, i2_2] = self.constraints[base + 2];
         let [i1_3, i2_3] = self.constraints[base + 3];
 
+        // Skip chunks whose particles are all asleep
+        if state.all_asleep(&[i1_0, i2_0, i1_1, i2_1, i1_2, i2_2, i1_3, i2_3]) {
+            return;
+        }
+
         // Load inverse masses
         let w1 = F32x4::new(
             state.inv_mass[i1_0],
@@ -120,15 +323,24 @@ impl TetherConstraint {
             self.rest_lengths[base + 3],
         );
 
-        // Tether only activates if len > rest
-        // C = max(0, len - rest)
-        let c = len.sub(rest).max(F32x4::splat(0.0));
+        // Clamp C = len - rest to what each lane's mode corrects:
+        // Extension = max(0, C), Compression = min(0, C), Bilateral = C
+        let bounds = [base, base + 1, base + 2, base + 3].map(|k| self.bounds(k));
+        let lower = F32x4::new(bounds[0].0, bounds[1].0, bounds[2].0, bounds[3].0);
+        let upper = F32x4::new(bounds[0].1, bounds[1].1, bounds[2].1, bounds[3].1);
+        let c = len.sub(rest).max(lower).min(upper);
 
         // Skip if no constraint violation (handled per-lane in scalar)
         // For SIMD, we process all but zero violations naturally produce zero correction
 
-        // delta_lambda = -C / w_sum (alpha = 0 for infinite stiffness)
-        let safe_w_sum = w_sum.max(F32x4::splat(1e-8));
+        // delta_lambda = -C / (w_sum + alpha) (alpha = 0 for infinite stiffness)
+        let alpha = F32x4::new(
+            self.alpha(base, params),
+            self.alpha(base + 1, params),
+            self.alpha(base + 2, params),
+            self.alpha(base + 3, params),
+        );
+        let safe_w_sum = w_sum.add(alpha).max(F32x4::splat(1e-8));
         let delta_lambda = c.neg().div(safe_w_sum);
 
         // Normalize delta
@@ -179,27 +391,35 @@ impl TetherConstraint {
 
     /// Scalar fallback for remainder constraints.
     #[inline(always)]
-    fn solve_single(&self, state: &mut PhysicsState, k: usize, omega: f32) {
+    fn solve_single(&self, state: &mut PhysicsState, k: usize, params: &GroupParams, omega: f32) {
         let [i1, i2] = self.constraints[k];
-        let w1 = state.inv_mass[i1];
-        let w2 = state.inv_mass[i2];
+        if state.all_asleep(&[i1, i2]) { return; }
+        let w1 = real(state.inv_mass[i1]);
+        let w2 = real(state.inv_mass[i2]);
         let w_sum = w1 + w2;
         if w_sum == 0.0 { return; }
 
-        let p1 = state.positions[i1];
-        let p2 = state.positions[i2];
+        let p1 = to_real3(state.positions[i1].truncate());
+        let p2 = to_real3(state.positions[i2].truncate());
         let delta = p1 - p2;
         let len = delta.length();
         if len < 1e-6 { return; }
 
-        let rest = self.rest_lengths[k];
-        if len <= rest { return; }
+        let (lower, upper) = self.bounds(k);
+        let c = (len - real(self.rest_lengths[k])).clamp(real(lower), real(upper));
+        if c == 0.0 { return; }
 
-        let c = len - rest;
-        let delta_lambda = -c / w_sum;
-        let correction_vector = (delta / len) * delta_lambda * omega;
+        let delta_lambda = -c / (w_sum + real(self.alpha(k, params)));
+        let correction_vector = (delta / len) * delta_lambda * real(omega);
 
-        if w1 > 0.0 { state.positions[i1] += correction_vector * w1; }
-        if w2 > 0.0 { state.positions[i2] -= correction_vector * w2; }
+        if w1 > 0.0 { state.positions[i1] = Vec4::from((from_real3(p1 + correction_vector * w1), 0.0)); }
+        if w2 > 0.0 { state.positions[i2] = Vec4::from((from_real3(p2 - correction_vector * w2), 0.0)); }
     }
-}
\ No newline at end of file
+}
+
+/// Per-group solve parameters, indexed by `TetherGroup`.
+#[derive(Clone, Copy)]
+struct GroupParams {
+    /// Compliance / dt^2.
+    alphas: [f32; 2],
+}
@@ -12,6 +12,7 @@ pub struct Integrator;
 impl Integrator {
     /// Updates position based on velocity and external forces (Gravity + Aerodynamics).
     /// P(new) = P(curr) + V * dt + 0.5 * A * dt^2
+    /// The step is clamped to `config.max_displacement` when that is set.
     ///
     /// OPTIMIZATION: Uses 4x loop unrolling for instruction-level parallelism.
     pub fn integrate(
@@ -19,6 +20,17 @@ impl Integrator {
         config: &PhysicsConfig,
         external_forces: &[Vec3],
         dt: f32
+    ) {
+        Self::integrate_with_gravity(state, config, external_forces, config.gravity, dt);
+    }
+
+    /// Same as `integrate`, with `gravity` in place of `config.gravity` (e.g. ramped in).
+    pub fn integrate_with_gravity(
+        state: &mut PhysicsState,
+        config: &PhysicsConfig,
+        external_forces: &[Vec3],
+        gravity: Vec3,
+        dt: f32
     ) {
         let dt_sq = dt * dt;
 
@@ -29,15 +41,15 @@ impl Integrator {
 
         for chunk in 0..chunks {
             let base = chunk * 4;
-            Self::integrate_single(state, config, external_forces, dt_sq, base);
-            Self::integrate_single(state, config, external_forces, dt_sq, base + 1);
-            Self::integrate_single(state, config, external_forces, dt_sq, base + 2);
-            Self::integrate_single(state, config, external_forces, dt_sq, base + 3);
+            Self::integrate_single(state, config, external_forces, gravity, dt_sq, base);
+            Self::integrate_single(state, config, external_forces, gravity, dt_sq, base + 1);
+            Self::integrate_single(state, config, external_forces, gravity, dt_sq, base + 2);
+            Self::integrate_single(state, config, external_forces, gravity, dt_sq, base + 3);
         }
 
         // Handle remainder
         for i in (chunks * 4)..(chunks * 4 + remainder) {
-            Self::integrate_single(state, config, external_forces, dt_sq, i);
+            Self::integrate_single(state, config, external_forces, gravity, dt_sq, i);
         }
     }
 
@@ -47,10 +59,11 @@ impl Integrator {
         state: &mut PhysicsState,
         config: &PhysicsConfig,
         external_forces: &[Vec3],
+        gravity: Vec3,
         dt_sq: f32,
         i: usize
     ) {
-        if state.inv_mass[i] == 0.0 { return; }
+        if state.inv_mass[i] == 0.0 || !state.awake[i] { return; }
 
         let pos = state.positions[i];
         let prev = state.prev_positions[i];
@@ -59,14 +72,24 @@ impl Integrator {
         // Gravity is constant acceleration.
         // Aerodynamics is a Force, so we multiply by inv_mass.
         let f_aero = external_forces[i];
-        let acceleration = config.gravity + (f_aero * state.inv_mass[i]);
+        let acceleration = gravity + (f_aero * state.inv_mass[i]);
 
         // Verlet integration with damping
         // velocity = (pos - prev) * damping
         // next_pos = pos + velocity + acceleration * dt^2
         let velocity_term = (pos - prev) * config.damping;
         let acceleration_term = glam::Vec4::from((acceleration * dt_sq, 0.0));
-        let next_pos = pos + velocity_term + acceleration_term;
+        let mut next_pos = pos + velocity_term + acceleration_term;
+
+        // Safety net against tunneling: cap the step length (this also caps the
+        // velocity carried into the next substep, since prev becomes `pos`)
+        if config.max_displacement > 0.0 {
+            let step = next_pos - pos;
+            let len_sq = step.length_squared();
+            if len_sq > config.max_displacement * config.max_displacement {
+                next_pos = pos + step * (config.max_displacement / len_sq.sqrt());
+            }
+        }
 
         state.prev_positions[i] = pos;
         state.positions[i] = next_pos;
@@ -4,4 +4,4 @@ pub mod integrator;
 pub mod solver;
 
 pub use integrator::Integrator;
-pub use solver::Solver;
\ No newline at end of file
+pub use solver::{ConstraintPlugin, MeshStats, Solver};
\ No newline at end of file
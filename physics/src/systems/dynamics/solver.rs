@@ -4,10 +4,52 @@ use crate::collision::CollisionResolver;
 use crate::engine::config::PhysicsConfig;
 use crate::engine::state::PhysicsState;
 use crate::systems::constraints::{
-    AreaConstraint, BendingConstraint, DistanceConstraint, TetherConstraint,
+    AreaConstraint, BendingConstraint, DihedralConstraint, DistanceConstraint, TetherConstraint,
 };
 use crate::utils::profiler::{ProfileCategory, Profiler};
 
+/// Upper bound on the fraction of last substep's multipliers re-applied when warm starting.
+/// The fraction actually used is `WARM_START_DECAY * (1 - 1 / iterations)`: the stored
+/// multipliers include redundant (self-stress) components that a few Gauss-Seidel sweeps
+/// cannot absorb, and re-applying them in full makes low iteration counts diverge.
+/// A single iteration therefore gets no warm start at all.
+const WARM_START_DECAY: f32 = 0.6;
+
+/// Rest-shape quality summary of the garment mesh, taken from the constraint builders.
+/// Zero-length edges or sliver triangles explain otherwise mysterious instability.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MeshStats {
+    pub min_edge_length: f32,
+    pub max_edge_length: f32,
+    pub mean_edge_length: f32,
+    /// Smallest area among the constrained triangles (degenerate ones are counted separately).
+    pub min_triangle_area: f32,
+    pub max_triangle_area: f32,
+    /// Triangles with (near) zero rest area, which the area constraint skips.
+    pub degenerate_triangles: usize,
+}
+
+/// A custom constraint solved by the `Solver` alongside the built-in ones, so the
+/// solver can be extended without forking it.
+/// Particle indices are internal (see `PhysicsState::internal_index`); `Send` keeps the
+/// owning `Simulation` movable across threads.
+pub trait ConstraintPlugin: Send {
+    /// Called once per solver iteration, after the built-in cloth constraints and
+    /// before collision resolution (so contacts still win). `iteration` counts from 0
+    /// within the substep; `dt` is the substep length.
+    fn solve(&mut self, state: &mut PhysicsState, dt: f32, iteration: usize);
+}
+
+/// Extra sweeps over the constraints touching one particle region.
+struct RegionSweep {
+    /// Sweeps added after each regular pass (the region's multiplier - 1).
+    extra: usize,
+    /// Distance constraint indices touching the region, in batch order.
+    distance: Vec<usize>,
+    /// Bending constraint indices touching the region, in batch order.
+    bending: Vec<usize>,
+}
+
 /// The XPBD (Extended Position Based Dynamics) Solver.
 /// Manages and solves all internal constraints of the cloth system.
 ///
@@ -17,6 +59,16 @@ pub struct Solver {
     pub bending_constraint: BendingConstraint,
     pub tether_constraint: TetherConstraint,
     pub area_constraint: AreaConstraint,
+    pub dihedral_constraint: DihedralConstraint,
+    /// Distance residual after each iteration, summed over the frame's substeps
+    /// (filled only when `config.track_convergence` is set).
+    pub convergence: Vec<f32>,
+    /// Substep length the stored multipliers were computed with (0.0 = none stored).
+    lambda_dt: f32,
+    /// Regions with an iteration multiplier above 1.
+    region_sweeps: Vec<RegionSweep>,
+    /// User constraints, solved in order every iteration (see `ConstraintPlugin`).
+    pub plugins: Vec<Box<dyn ConstraintPlugin>>,
 }
 
 impl Solver {
@@ -27,25 +79,98 @@ impl Solver {
         let bending_constraint = BendingConstraint::new(state, tuned_compliance);
         let tether_constraint = TetherConstraint::new(state);
         let area_constraint = AreaConstraint::new(state);
+        let dihedral_constraint = DihedralConstraint::new(state);
 
         Self {
             distance_constraint,
             bending_constraint,
             tether_constraint,
             area_constraint,
+            dihedral_constraint,
+            convergence: Vec::new(),
+            lambda_dt: 0.0,
+            region_sweeps: Vec::new(),
+            plugins: Vec::new(),
+        }
+    }
+
+    /// Gives particle regions (`state.regions`) an iteration multiplier: region `r` has its
+    /// distance and bending constraints solved `multipliers[r]` times per iteration instead
+    /// of once (missing entries and values <= 1 mean no extra work). Spends the iteration
+    /// budget where convergence is slow, e.g. a long hem under a stiff bodice.
+    /// The extra sweeps are plain serial Gauss-Seidel over the constraints touching the region.
+    /// Must be called again after the constraints or the regions change.
+    pub fn set_region_iterations(&mut self, state: &PhysicsState, multipliers: &[u32]) {
+        let touching = |constraints: &[[usize; 2]], region: u8| -> Vec<usize> {
+            constraints
+                .iter()
+                .enumerate()
+                .filter(|&(_, &[a, b])| state.regions[a] == region || state.regions[b] == region)
+                .map(|(k, _)| k)
+                .collect()
+        };
+
+        self.region_sweeps = multipliers
+            .iter()
+            .enumerate()
+            .take(256)
+            .filter(|&(_, &m)| m > 1)
+            .map(|(region, &m)| RegionSweep {
+                extra: m as usize - 1,
+                distance: touching(&self.distance_constraint.constraints, region as u8),
+                bending: touching(&self.bending_constraint.constraints, region as u8),
+            })
+            .filter(|sweep| !sweep.distance.is_empty() || !sweep.bending.is_empty())
+            .collect();
+    }
+
+    /// Edge length and triangle area statistics of the rest shape (all zero for an empty mesh).
+    pub fn mesh_stats(&self) -> MeshStats {
+        let range = |values: &[f32]| -> (f32, f32) {
+            if values.is_empty() {
+                return (0.0, 0.0);
+            }
+            values
+                .iter()
+                .fold((f32::MAX, f32::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)))
+        };
+        let lengths = &self.distance_constraint.rest_lengths;
+        let areas = self.area_constraint.rest_areas();
+        let (min_edge_length, max_edge_length) = range(lengths);
+        let (min_triangle_area, max_triangle_area) = range(areas);
+        let mean_edge_length = if lengths.is_empty() {
+            0.0
+        } else {
+            lengths.iter().sum::<f32>() / lengths.len() as f32
+        };
+
+        MeshStats {
+            min_edge_length,
+            max_edge_length,
+            mean_edge_length,
+            min_triangle_area,
+            max_triangle_area,
+            degenerate_triangles: self.area_constraint.degenerate_count(),
         }
     }
 
+    /// Zeroes the convergence buffer at the start of a frame (one slot per iteration).
+    pub fn reset_convergence(&mut self, iterations: usize) {
+        self.convergence.clear();
+        self.convergence.resize(iterations, 0.0);
+    }
+
     /// Main simulation loop iteration.
     /// Uses "Sub-stepping" with Chebyshev acceleration (Omega) for faster convergence.
     /// - Iterates `config.solver_iterations` times.
-    /// - Adjusts `omega` dynamically for stability.
+    /// - Adjusts `omega` dynamically for stability (fixed at 1.0 when `config.use_chebyshev` is off).
+    /// - Scales `omega` per constraint type by the `config.*_relaxation` factors.
     /// - Resolves constraints and collisions in order.
     ///
     /// OPTIMIZATION: All constraints use SIMD vectorization for 4-wide parallel processing.
     /// PROFILING: Each constraint type is measured individually.
     pub fn solve(
-        &self,
+        &mut self,
         state: &mut PhysicsState,
         resolver: &CollisionResolver,
         config: &PhysicsConfig,
@@ -54,37 +179,102 @@ impl Solver {
         let mut omega = 1.0;
         let rho = config.spectral_radius;
 
+        // Multipliers scale with dt^2, so they only carry over between equal substeps
+        if config.warm_start {
+            if self.lambda_dt == dt {
+                let decay = WARM_START_DECAY * (1.0 - 1.0 / config.solver_iterations as f32);
+                self.distance_constraint.warm_start(state, decay);
+                self.bending_constraint.warm_start(state, decay);
+            } else {
+                self.distance_constraint.reset_lambdas();
+                self.bending_constraint.reset_lambdas();
+                self.lambda_dt = dt;
+            }
+        } else {
+            self.lambda_dt = 0.0;
+        }
+
         for i in 0..config.solver_iterations {
-            if i == 0 {
+            if i == 0 || !config.use_chebyshev {
                 omega = 1.0;
             } else if i == 1 {
                 omega = 2.0 / (2.0 - rho * rho);
             } else {
                 omega = 4.0 / (4.0 - rho * rho * omega);
             }
+            // Per-constraint relaxation scales the correction on top of the global omega
+            let distance_omega = omega * config.distance_relaxation;
+            let bending_omega = omega * config.bending_relaxation;
 
             // Accelerate Internal Constraints (SIMD-vectorized)
+            // Each type can be switched off at runtime to isolate artifacts
             Profiler::start(ProfileCategory::DistanceConstraint);
-            self.distance_constraint.solve(state, omega, dt);
+            if config.distance_enabled {
+                if config.warm_start {
+                    self.distance_constraint.solve_accumulate(state, distance_omega, dt);
+                } else {
+                    self.distance_constraint.solve(state, distance_omega, dt);
+                }
+                for sweep in &self.region_sweeps {
+                    for _ in 0..sweep.extra {
+                        self.distance_constraint.solve_subset(state, &sweep.distance, dt);
+                    }
+                }
+            }
             Profiler::end(ProfileCategory::DistanceConstraint);
 
             Profiler::start(ProfileCategory::BendingConstraint);
-            self.bending_constraint.solve(state, omega, dt);
+            if config.bending_enabled {
+                if config.warm_start {
+                    self.bending_constraint.solve_accumulate(state, bending_omega, dt);
+                } else {
+                    self.bending_constraint.solve(state, bending_omega, dt);
+                }
+                for sweep in &self.region_sweeps {
+                    for _ in 0..sweep.extra {
+                        self.bending_constraint.solve_subset(state, &sweep.bending, dt);
+                    }
+                }
+            }
+            if config.dihedral_enabled {
+                self.dihedral_constraint
+                    .solve(state, config.dihedral_compliance, config.dihedral_limits(), bending_omega, dt);
+            }
             Profiler::end(ProfileCategory::BendingConstraint);
 
             Profiler::start(ProfileCategory::TetherConstraint);
-            self.tether_constraint.solve(state, omega, dt);
+            if config.tether_enabled {
+                self.tether_constraint.solve(
+                    state,
+                    [config.tether_vertical_compliance, config.tether_horizontal_compliance],
+                    omega * config.tether_relaxation,
+                    dt,
+                );
+            }
             Profiler::end(ProfileCategory::TetherConstraint);
 
             Profiler::start(ProfileCategory::AreaConstraint);
-            self.area_constraint
-                .solve(state, config.area_compliance, omega, dt);
+            if config.area_enabled {
+                self.area_constraint
+                    .solve(state, config.area_compliance, omega * config.area_relaxation, dt);
+            }
             Profiler::end(ProfileCategory::AreaConstraint);
 
+            for plugin in &mut self.plugins {
+                plugin.solve(state, dt, i);
+            }
+
             // FIX: Do NOT accelerate Collisions
             Profiler::start(ProfileCategory::CollisionResolve);
             resolver.resolve_contacts(state, config, dt);
             Profiler::end(ProfileCategory::CollisionResolve);
+
+            if config.track_convergence {
+                let residual = self.distance_constraint.residual(state);
+                if let Some(slot) = self.convergence.get_mut(i) {
+                    *slot += residual;
+                }
+            }
         }
     }
 }
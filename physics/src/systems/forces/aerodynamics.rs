@@ -1,13 +1,33 @@
 // physics/src/system/forces/aerodynamics.rs
 
 use glam::Vec3;
+use crate::collision::MeshCollider;
 use crate::engine::state::PhysicsState;
 use crate::engine::config::PhysicsConfig;
+use crate::utils::noise::perlin3;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use rustc_hash::FxHashSet;
+
+/// Spatial frequency of gusts (noise cycles per meter).
+const GUST_SPATIAL_FREQ: f32 = 1.5;
+/// How fast the gust field evolves (noise cycles per second).
+const GUST_TIME_FREQ: f32 = 0.8;
+/// Length (meters) of the upwind ray cast from each triangle for `config.wind_occlusion`;
+/// comfortably spans a torso, while distant geometry casts no shadow.
+const WIND_SHADOW_RANGE: f32 = 1.0;
+/// Fraction of the wind still reaching a shadowed triangle (the flow wraps around the body).
+const WIND_SHADOW_EXPOSURE: f32 = 0.2;
 
 /// Calculates aerodynamic forces (Lift and Drag) acting on the garment.
-/// Uses a simplified triangle-based model assuming a uniform wind vector.
+/// Uses a simplified triangle-based model with a uniform wind vector, optionally
+/// perturbed per triangle by time-varying noise (`config.wind_turbulence`).
 pub struct Aerodynamics {
     force_buffer: Vec<Vec3>,
+    /// Accumulated simulation time driving the turbulence field.
+    time: f32,
+    /// Per-triangle fraction of the wind reaching it (empty = every triangle fully exposed).
+    exposure: Vec<f32>,
 }
 
 impl Aerodynamics {
@@ -15,6 +35,77 @@ impl Aerodynamics {
         Self {
             // Eagerly allocate the entire buffer to prevent runtime resizing
             force_buffer: vec![Vec3::ZERO; particle_count],
+            time: 0.0,
+            exposure: Vec::new(),
+        }
+    }
+
+    /// Advances the turbulence clock. Called once per frame with the frame `dt`
+    /// (`apply` receives the substep `dt` for velocities, so it can't advance time itself).
+    pub fn advance_time(&mut self, dt: f32) {
+        self.time += dt;
+    }
+
+    /// Current turbulence clock (seconds).
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Wind at `p`: the base wind plus a noise gust of amplitude `config.wind_turbulence`.
+    /// Each axis samples a decorrelated noise channel; deterministic for a given seed and time.
+    pub fn wind_at(&self, p: Vec3, config: &PhysicsConfig) -> Vec3 {
+        if config.wind_turbulence <= 0.0 {
+            return config.wind;
+        }
+        let q = p * GUST_SPATIAL_FREQ + Vec3::splat(self.time * GUST_TIME_FREQ);
+        let seed = config.wind_seed;
+        let gust = Vec3::new(
+            perlin3(q, seed),
+            perlin3(q, seed.wrapping_add(1)),
+            perlin3(q, seed.wrapping_add(2)),
+        );
+        config.wind + gust * config.wind_turbulence
+    }
+
+    /// Recomputes which triangles the body shades from the base wind: a ray is cast
+    /// upwind from each triangle's centroid, and a hit leaves `WIND_SHADOW_EXPOSURE` of the
+    /// wind. Clears the shading when `config.wind_occlusion` is off or there is no wind.
+    pub fn update_occlusion(&mut self, state: &PhysicsState, config: &PhysicsConfig, collider: &MeshCollider) {
+        self.exposure.clear();
+        let speed = config.wind.length();
+        if !config.wind_occlusion
+            || !config.aerodynamics_enabled
+            || speed < 1e-6
+            || collider.triangles.is_empty()
+        {
+            return;
+        }
+
+        let upwind = -config.wind / speed * WIND_SHADOW_RANGE;
+        let shade = |tri: &[u32], candidates: &mut Vec<usize>, dedup: &mut FxHashSet<usize>| {
+            let centroid = (state.positions[tri[0] as usize]
+                + state.positions[tri[1] as usize]
+                + state.positions[tri[2] as usize])
+                .truncate()
+                / 3.0;
+            if collider.segment_hits(centroid, centroid + upwind, candidates, dedup) {
+                WIND_SHADOW_EXPOSURE
+            } else {
+                1.0
+            }
+        };
+
+        // One ray per triangle; each worker reuses its own query buffers (`map_init`)
+        #[cfg(feature = "parallel")]
+        self.exposure.par_extend(state.indices.par_chunks_exact(3).map_init(
+            || (Vec::with_capacity(64), FxHashSet::default()),
+            |(candidates, dedup), tri| shade(tri, candidates, dedup),
+        ));
+        #[cfg(not(feature = "parallel"))]
+        {
+            let (mut candidates, mut dedup) = (Vec::with_capacity(64), FxHashSet::default());
+            self.exposure
+                .extend(state.indices.chunks_exact(3).map(|tri| shade(tri, &mut candidates, &mut dedup)));
         }
     }
 
@@ -26,6 +117,11 @@ impl Aerodynamics {
         // Zero out the buffer using a fast fill instead of clearing/pushing
         self.force_buffer.fill(Vec3::ZERO);
 
+        // Pure-gravity mode: no per-triangle work, damping alone settles the cloth
+        if !config.aerodynamics_enabled {
+            return &self.force_buffer;
+        }
+
         let num_triangles = state.indices.len() / 3;
         for i in 0..num_triangles {
             let idx0 = state.indices[i * 3] as usize;
@@ -41,7 +137,9 @@ impl Aerodynamics {
             let v2 = (p2 - state.prev_positions[idx2].truncate()) / dt;
             let tri_vel = (v0 + v1 + v2) / 3.0;
 
-            let rel_vel: Vec3 = tri_vel - config.wind;
+            let exposure = self.exposure.get(i).copied().unwrap_or(1.0);
+            let wind = self.wind_at((p0 + p1 + p2) / 3.0, config) * exposure;
+            let rel_vel: Vec3 = tri_vel - wind;
             let vel_sq = rel_vel.length_squared();
 
             if vel_sq < 1e-6 { continue; }
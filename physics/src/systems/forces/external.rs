@@ -0,0 +1,47 @@
+// physics/src/systems/forces/external.rs
+
+use glam::Vec3;
+
+/// Per-particle forces injected by the host for one frame (scripted gusts, magnets,
+/// a fan aimed at the hem). They are added on top of the aerodynamic forces, applied
+/// for every substep of the next frame and then cleared.
+pub struct ExternalForces {
+    force_buffer: Vec<Vec3>,
+    /// Any force queued since the last clear (lets the common empty frame skip all work).
+    pending: bool,
+}
+
+impl ExternalForces {
+    pub fn new(particle_count: usize) -> Self {
+        Self {
+            force_buffer: vec![Vec3::ZERO; particle_count],
+            pending: false,
+        }
+    }
+
+    /// Accumulates `force` (Newtons) on particle `index` for the next frame.
+    pub fn add(&mut self, index: usize, force: Vec3) {
+        self.force_buffer[index] += force;
+        self.pending = true;
+    }
+
+    /// Returns the per-particle force for this frame: `base` (e.g. the aerodynamic
+    /// forces) plus everything queued. With nothing queued, `base` is returned untouched.
+    pub fn combine<'a>(&'a mut self, base: &'a [Vec3]) -> &'a [Vec3] {
+        if !self.pending {
+            return base;
+        }
+        for (f, b) in self.force_buffer.iter_mut().zip(base) {
+            *f += *b;
+        }
+        &self.force_buffer
+    }
+
+    /// Drops the queued forces (called once the frame has consumed them).
+    pub fn clear(&mut self) {
+        if self.pending {
+            self.force_buffer.fill(Vec3::ZERO);
+            self.pending = false;
+        }
+    }
+}
@@ -1,4 +1,6 @@
 // physics/src/systems/forces/mod.rs
 
 pub mod aerodynamics;
-pub use aerodynamics::Aerodynamics;
\ No newline at end of file
+pub mod external;
+pub use aerodynamics::Aerodynamics;
+pub use external::ExternalForces;
\ No newline at end of file
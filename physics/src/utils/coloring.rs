@@ -9,71 +9,68 @@ pub fn color_constraints(
     constraints: &[[usize; 2]],
     particle_count: usize
 ) -> (Vec<usize>, Vec<usize>) {
-    // 1. Build Adjacency (CSR Format)
-    let mut degree = vec![0usize; particle_count];
-    for &[p1, p2] in constraints {
-        degree[p1] += 1;
-        degree[p2] += 1;
-    }
+    color_greedy(constraints, particle_count)
+}
 
-    let mut offset = vec![0usize; particle_count + 1];
-    for i in 0..particle_count {
-        offset[i + 1] = offset[i] + degree[i];
-    }
+pub fn color_constraints_3(
+    constraints: &[[usize; 3]],
+    particle_count: usize
+) -> (Vec<usize>, Vec<usize>) {
+    color_greedy(constraints, particle_count)
+}
 
-    let mut adj = vec![0usize; offset[particle_count]];
-    let mut counter = offset.clone();
-    for (i, &[p1, p2]) in constraints.iter().enumerate() {
-        adj[counter[p1]] = i;
-        counter[p1] += 1;
-        adj[counter[p2]] = i;
-        counter[p2] += 1;
-    }
+pub fn color_constraints_4(
+    constraints: &[[usize; 4]],
+    particle_count: usize
+) -> (Vec<usize>, Vec<usize>) {
+    color_greedy(constraints, particle_count)
+}
 
-    // 2. Greedy Coloring with bitmask for speed
-    let mut constraint_colors: Vec<Option<usize>> = vec![None; constraints.len()];
-    let mut batch_indices: Vec<Vec<usize>> = Vec::new();
+/// Colors already taken by the constraints sharing a particle with the current one.
+/// The first 64 colors live in a bitmask (the common case, and the fast path);
+/// only pathological high-valence vertices need more, which spill into `overflow`.
+struct UsedColors {
+    mask: u64,
+    /// `overflow[k]` = color `64 + k` is taken.
+    overflow: Vec<bool>,
+}
 
-    for i in 0..constraints.len() {
-        let [p1, p2] = constraints[i];
-        let mut used_colors = 0u64;
+impl UsedColors {
+    fn clear(&mut self) {
+        self.mask = 0;
+        self.overflow.fill(false);
+    }
 
-        // Find used colors among neighbors
-        for &c_idx in &adj[offset[p1]..offset[p1+1]] {
-            if let Some(c) = constraint_colors[c_idx] {
-                // FIX: Explicitly use u64 literal for bitwise operation
-                used_colors |= 1u64 << c;
+    fn insert(&mut self, color: usize) {
+        if color < 64 {
+            self.mask |= 1u64 << color;
+        } else {
+            let k = color - 64;
+            if k >= self.overflow.len() {
+                self.overflow.resize(k + 1, false);
             }
+            self.overflow[k] = true;
         }
-        for &c_idx in &adj[offset[p2]..offset[p2+1]] {
-            if let Some(c) = constraint_colors[c_idx] {
-                // FIX: Explicitly use u64 literal for bitwise operation
-                used_colors |= 1u64 << c;
-            }
-        }
-
-        // Find the first unset bit (the first available color)
-        let color = (!used_colors).trailing_zeros() as usize;
-        constraint_colors[i] = Some(color);
+    }
 
-        if color >= batch_indices.len() {
-            batch_indices.resize(color + 1, Vec::new());
+    /// Lowest color not taken.
+    fn first_free(&self) -> usize {
+        if self.mask != u64::MAX {
+            return self.mask.trailing_ones() as usize;
         }
-        batch_indices[color].push(i);
+        64 + self.overflow.iter().position(|&used| !used).unwrap_or(self.overflow.len())
     }
-
-    flatten_batches(batch_indices, constraints.len())
 }
 
-pub fn color_constraints_3(
-    constraints: &[[usize; 3]],
+/// Greedy coloring shared by the 2-, 3- and 4-particle variants.
+fn color_greedy<const N: usize>(
+    constraints: &[[usize; N]],
     particle_count: usize
 ) -> (Vec<usize>, Vec<usize>) {
+    // 1. Build Adjacency (CSR Format)
     let mut degree = vec![0usize; particle_count];
-    for &[p1, p2, p3] in constraints {
-        degree[p1] += 1;
-        degree[p2] += 1;
-        degree[p3] += 1;
+    for c in constraints {
+        for &p in c { degree[p] += 1; }
     }
 
     let mut offset = vec![0usize; particle_count + 1];
@@ -83,30 +80,27 @@ pub fn color_constraints_3(
 
     let mut adj = vec![0usize; offset[particle_count]];
     let mut counter = offset.clone();
-    for (i, &[p1, p2, p3]) in constraints.iter().enumerate() {
-        adj[counter[p1]] = i; counter[p1] += 1;
-        adj[counter[p2]] = i; counter[p2] += 1;
-        adj[counter[p3]] = i; counter[p3] += 1;
+    for (i, c) in constraints.iter().enumerate() {
+        for &p in c { adj[counter[p]] = i; counter[p] += 1; }
     }
 
+    // 2. Greedy Coloring with bitmask for speed
     let mut constraint_colors: Vec<Option<usize>> = vec![None; constraints.len()];
     let mut batch_indices: Vec<Vec<usize>> = Vec::new();
+    let mut used_colors = UsedColors { mask: 0, overflow: Vec::new() };
 
     for i in 0..constraints.len() {
-        let [p1, p2, p3] = constraints[i];
-        let mut used_colors = 0u64;
+        used_colors.clear();
 
-        for &c_idx in &adj[offset[p1]..offset[p1+1]] {
-            if let Some(c) = constraint_colors[c_idx] { used_colors |= 1u64 << c; }
-        }
-        for &c_idx in &adj[offset[p2]..offset[p2+1]] {
-            if let Some(c) = constraint_colors[c_idx] { used_colors |= 1u64 << c; }
-        }
-        for &c_idx in &adj[offset[p3]..offset[p3+1]] {
-            if let Some(c) = constraint_colors[c_idx] { used_colors |= 1u64 << c; }
+        // Find used colors among neighbors
+        for &p in &constraints[i] {
+            for &c_idx in &adj[offset[p]..offset[p + 1]] {
+                if let Some(c) = constraint_colors[c_idx] { used_colors.insert(c); }
+            }
         }
 
-        let color = (!used_colors).trailing_zeros() as usize;
+        // The first available color
+        let color = used_colors.first_free();
         constraint_colors[i] = Some(color);
 
         if color >= batch_indices.len() {
@@ -115,7 +109,56 @@ pub fn color_constraints_3(
         batch_indices[color].push(i);
     }
 
-    flatten_batches(batch_indices, constraints.len())
+    if batch_indices.len() > 64 {
+        crate::console_log!(
+            "[physics] Constraint coloring needed {} colors ; a very high-valence vertex makes the solver run many small batches",
+            batch_indices.len()
+        );
+    }
+
+    let batches = flatten_batches(batch_indices, constraints.len());
+    // The parallel solvers write every batch concurrently; an overlap would be a data race
+    #[cfg(debug_assertions)]
+    if let Err(e) = validate_batches(constraints, &batches.0, &batches.1, particle_count) {
+        panic!("[physics] Constraint coloring produced an invalid batch: {}", e);
+    }
+    batches
+}
+
+/// Checks a coloring result: every constraint appears exactly once in `sorted` and no
+/// two constraints of the same batch (`sorted[offsets[k]..offsets[k + 1]]`) share a
+/// particle. Run automatically after coloring in debug builds.
+pub fn validate_batches<const N: usize>(
+    constraints: &[[usize; N]],
+    sorted: &[usize],
+    offsets: &[usize],
+    particle_count: usize
+) -> Result<(), String> {
+    let mut seen = vec![false; constraints.len()];
+    for &c in sorted {
+        match seen.get_mut(c) {
+            Some(slot) if !*slot => *slot = true,
+            Some(_) => return Err(format!("constraint {} appears twice", c)),
+            None => return Err(format!("constraint {} out of range", c)),
+        }
+    }
+    if let Some(c) = seen.iter().position(|&s| !s) {
+        return Err(format!("constraint {} is missing", c));
+    }
+
+    // Batch that last touched each particle (`usize::MAX` = none)
+    let mut owner = vec![usize::MAX; particle_count];
+    for (batch, range) in offsets.windows(2).enumerate() {
+        for &c in &sorted[range[0]..range[1]] {
+            for &p in &constraints[c] {
+                if owner[p] == batch {
+                    return Err(format!("batch {} touches particle {} twice", batch, p));
+                }
+                owner[p] = batch;
+            }
+        }
+    }
+    Ok(())
 }
 
 fn flatten_batches(batch_indices: Vec<Vec<usize>>, total_count: usize) -> (Vec<usize>, Vec<usize>) {
@@ -131,4 +174,4 @@ fn flatten_batches(batch_indices: Vec<Vec<usize>>, total_count: usize) -> (Vec<u
     batch_offsets.push(current_offset);
 
     (sorted_indices, batch_offsets)
-}
\ No newline at end of file
+}
@@ -1,20 +1,32 @@
 // physics/src/utils/mod.rs
 
 pub mod coloring;
+pub mod noise;
 pub mod normals;
 pub mod profiler;
+pub mod real;
 pub mod simd;
 
+#[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
 pub fn set_panic_hook() {
     console_error_panic_hook::set_once();
 }
 
+#[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 extern "C" {
-    #[wasm_bindgen(js_namespace = console)]
-    pub fn log(s: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = log)]
+    fn console_log(s: &str);
+}
+
+/// Logs to the browser console (stderr on native targets, e.g. tests).
+pub fn log(s: &str) {
+    #[cfg(target_arch = "wasm32")]
+    console_log(s);
+    #[cfg(not(target_arch = "wasm32"))]
+    eprintln!("{}", s);
 }
 
 #[macro_export]
@@ -0,0 +1,77 @@
+// physics/src/utils/noise.rs
+
+//! Cheap, seedable 3D gradient (Perlin) noise.
+//! Deterministic: the same point and seed always give the same value on every platform.
+
+use glam::Vec3;
+
+/// Integer hash of a lattice point (a few multiply/xor rounds, no tables).
+#[inline(always)]
+fn hash(x: i32, y: i32, z: i32, seed: u32) -> u32 {
+    let mut h = seed
+        ^ (x as u32).wrapping_mul(0x8da6_b343)
+        ^ (y as u32).wrapping_mul(0xd816_3841)
+        ^ (z as u32).wrapping_mul(0xcb1a_b31f);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x2c1b_3c6d);
+    h ^= h >> 12;
+    h = h.wrapping_mul(0x297a_2d39);
+    h ^= h >> 15;
+    h
+}
+
+/// Dot product of the offset with one of the 12 cube-edge gradients picked by `h`.
+#[inline(always)]
+fn grad(h: u32, d: Vec3) -> f32 {
+    match h % 12 {
+        0 => d.x + d.y,
+        1 => -d.x + d.y,
+        2 => d.x - d.y,
+        3 => -d.x - d.y,
+        4 => d.x + d.z,
+        5 => -d.x + d.z,
+        6 => d.x - d.z,
+        7 => -d.x - d.z,
+        8 => d.y + d.z,
+        9 => -d.y + d.z,
+        10 => d.y - d.z,
+        _ => -d.y - d.z,
+    }
+}
+
+/// Quintic fade curve `6t^5 - 15t^4 + 10t^3`.
+#[inline(always)]
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+/// 3D Perlin noise in roughly [-1, 1]; zero at integer lattice points.
+pub fn perlin3(p: Vec3, seed: u32) -> f32 {
+    let cell = p.floor();
+    let (x, y, z) = (cell.x as i32, cell.y as i32, cell.z as i32);
+    let f = p - cell;
+    let (u, v, w) = (fade(f.x), fade(f.y), fade(f.z));
+
+    let corner = |dx: i32, dy: i32, dz: i32| {
+        let h = hash(x + dx, y + dy, z + dz, seed);
+        grad(h, f - Vec3::new(dx as f32, dy as f32, dz as f32))
+    };
+
+    let c000 = corner(0, 0, 0);
+    let c100 = corner(1, 0, 0);
+    let c010 = corner(0, 1, 0);
+    let c110 = corner(1, 1, 0);
+    let c001 = corner(0, 0, 1);
+    let c101 = corner(1, 0, 1);
+    let c011 = corner(0, 1, 1);
+    let c111 = corner(1, 1, 1);
+
+    let x00 = c000 + u * (c100 - c000);
+    let x10 = c010 + u * (c110 - c010);
+    let x01 = c001 + u * (c101 - c001);
+    let x11 = c011 + u * (c111 - c011);
+
+    let y0 = x00 + v * (x10 - x00);
+    let y1 = x01 + v * (x11 - x01);
+    y0 + w * (y1 - y0)
+}
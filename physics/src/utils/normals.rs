@@ -1,6 +1,7 @@
 // physics/src/utils/normals.rs
 
-use glam::Vec4;
+use crate::engine::topology::{MeshTopology, NO_FACE};
+use glam::{Vec3, Vec4};
 
 /// Computes vertex normals from triangle mesh data.
 /// This is optimized to run in WASM instead of JavaScript, avoiding main-thread blocking.
@@ -17,6 +18,18 @@ pub fn compute_vertex_normals(
     positions: &[Vec4],
     indices: &[u32],
     normals: &mut [Vec4]
+) {
+    compute_vertex_normals_weighted(positions, indices, normals, false);
+}
+
+/// Same as `compute_vertex_normals`, with angle-weighted (Thürmer) accumulation when
+/// `angle_weighted` is set: each face contributes its unit normal times the corner angle,
+/// so a vertex normal no longer leans towards the largest neighboring faces.
+pub fn compute_vertex_normals_weighted(
+    positions: &[Vec4],
+    indices: &[u32],
+    normals: &mut [Vec4],
+    angle_weighted: bool,
 ) {
     let count = normals.len();
 
@@ -42,19 +55,11 @@ pub fn compute_vertex_normals(
         let p1 = positions[i1].truncate();
         let p2 = positions[i2].truncate();
 
-        // Edge vectors
-        let e1 = p1 - p0;
-        let e2 = p2 - p0;
-
-        // Face normal (not normalized - magnitude is proportional to area)
-        // This gives area-weighted normals which is more accurate for curved surfaces
-        let face_normal = e1.cross(e2);
-        let face_normal_v4 = Vec4::from((face_normal, 0.0));
-
         // Accumulate to each vertex of the triangle
-        normals[i0] += face_normal_v4;
-        normals[i1] += face_normal_v4;
-        normals[i2] += face_normal_v4;
+        let [n0, n1, n2] = corner_normals(p0, p1, p2, angle_weighted);
+        normals[i0] += Vec4::from((n0, 0.0));
+        normals[i1] += Vec4::from((n1, 0.0));
+        normals[i2] += Vec4::from((n2, 0.0));
     }
 
     // 3. Normalize all vertex normals
@@ -71,3 +76,113 @@ pub fn compute_vertex_normals(
         }
     }
 }
+
+/// Contribution of triangle `p0 p1 p2` to the normal of each of its corners.
+/// Area-weighted: the raw edge cross product (magnitude proportional to area) for all three.
+/// Angle-weighted: the unit face normal times the interior angle at that corner.
+/// A degenerate triangle contributes nothing in angle-weighted mode.
+pub fn corner_normals(p0: Vec3, p1: Vec3, p2: Vec3, angle_weighted: bool) -> [Vec3; 3] {
+    let face_normal = (p1 - p0).cross(p2 - p0);
+    if !angle_weighted {
+        return [face_normal; 3];
+    }
+
+    let unit = face_normal.normalize_or_zero();
+    if unit == Vec3::ZERO {
+        return [Vec3::ZERO; 3];
+    }
+    [
+        unit * (p1 - p0).angle_between(p2 - p0),
+        unit * (p2 - p1).angle_between(p0 - p1),
+        unit * (p0 - p2).angle_between(p1 - p2),
+    ]
+}
+
+/// Temporal smoothing: blends freshly computed `normals` towards last frame's `previous`
+/// (`smoothing` = weight of the previous normal, 0 = off) and renormalizes.
+/// Damps shading flicker on jittery, settling cloth at the cost of slight lag.
+pub fn blend_normals(normals: &mut [Vec4], previous: &[Vec4], smoothing: f32) {
+    if smoothing <= 0.0 {
+        return;
+    }
+
+    for (n, prev) in normals.iter_mut().zip(previous) {
+        let blended = n.truncate().lerp(prev.truncate(), smoothing);
+        let len_sq = blended.length_squared();
+
+        // Opposing normals can cancel out; keep the fresh one then
+        if len_sq > 1e-12 {
+            *n = Vec4::from((blended / len_sq.sqrt(), 0.0));
+        }
+    }
+}
+
+/// Returns a copy of `indices` with consistent triangle winding, for normals on open or
+/// double-sided garments (scarves, capes) whose authored winding may be mixed.
+/// Windings are made consistent by flood fill across manifold edges; each connected
+/// component is then flipped as a whole so its area-weighted normal faces `up`.
+/// Non-manifold edges (more than two triangles) do not propagate orientation.
+pub fn orient_indices(positions: &[Vec4], indices: &[u32], topology: &MeshTopology, up: Vec3) -> Vec<u32> {
+    let num_triangles = indices.len() / 3;
+    let tri = |t: usize| [indices[t * 3] as usize, indices[t * 3 + 1] as usize, indices[t * 3 + 2] as usize];
+    // True if triangle `t` (as authored) walks the edge a -> b
+    let walks = |t: usize, a: usize, b: usize| {
+        let v = tri(t);
+        (0..3).any(|k| v[k] == a && v[(k + 1) % 3] == b)
+    };
+
+    let mut flipped = vec![false; num_triangles];
+    let mut visited = vec![false; num_triangles];
+    let mut component = Vec::new();
+    let mut stack = Vec::new();
+
+    for seed in 0..num_triangles {
+        if visited[seed] {
+            continue;
+        }
+        visited[seed] = true;
+        component.clear();
+        stack.push(seed);
+
+        while let Some(t) = stack.pop() {
+            component.push(t);
+            let v = tri(t);
+            for k in 0..3 {
+                let (a, b) = (v[k], v[(k + 1) % 3]);
+                let Some(e) = topology.edge_index(a, b) else { continue };
+                if topology.edge_face_counts[e] != 2 {
+                    continue;
+                }
+                let [f0, f1] = topology.edge_faces[e];
+                let other = if f0 as usize == t { f1 } else { f0 };
+                if other == NO_FACE || visited[other as usize] {
+                    continue;
+                }
+                let other = other as usize;
+                // Consistent neighbors walk a shared edge in opposite directions
+                visited[other] = true;
+                flipped[other] = walks(other, a, b) != flipped[t];
+                stack.push(other);
+            }
+        }
+
+        let mut normal_sum = Vec3::ZERO;
+        for &t in &component {
+            let [i0, i1, i2] = tri(t);
+            let (p0, p1, p2) = (positions[i0].truncate(), positions[i1].truncate(), positions[i2].truncate());
+            let n = (p1 - p0).cross(p2 - p0);
+            normal_sum += if flipped[t] { -n } else { n };
+        }
+        if normal_sum.dot(up) < 0.0 {
+            for &t in &component {
+                flipped[t] = !flipped[t];
+            }
+        }
+    }
+
+    let mut oriented = indices.to_vec();
+    for (t, _) in flipped.iter().enumerate().filter(|&(_, &f)| f) {
+        oriented.swap(t * 3 + 1, t * 3 + 2);
+    }
+    oriented
+}
@@ -1,13 +1,9 @@
 //! High-resolution performance profiling for the physics engine.
 //! Use the Web Performance API to measure timing with microsecond precision.
-
-// use wasm_bindgen::prelude::*;
-
-// Thread-local profiler state (only compiled with profiling feature)
-// #[cfg(feature = "profiling")]
-// thread_local! {
-//     static PROFILER: RefCell<ProfilerState> = RefCell::new(ProfilerState::new());
-// }
+//!
+//! State is thread-local and only compiled with the `profiling` feature;
+//! without it every call is a no-op. Even with the feature, timing starts disabled:
+//! the host opts in with `Profiler::set_enabled(true)`.
 
 /// Profiling categories for organized reporting
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -60,6 +56,68 @@ impl ProfileCategory {
     }
 }
 
+/// Integer counters reported alongside timings (last value set during the frame).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum ProfileCounter {
+    /// Self-collision pairs detected.
+    CollisionPairs = 0,
+    /// Body contacts resolved (mesh + SDF).
+    Contacts = 1,
+    /// Graph-colored batches for self-collision resolution.
+    SelfCollisionBatches = 2,
+    /// Particles that got no collider candidates because `max_candidates` was reached.
+    DroppedCandidates = 3,
+}
+
+impl ProfileCounter {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProfileCounter::CollisionPairs => "CollisionPairs",
+            ProfileCounter::Contacts => "Contacts",
+            ProfileCounter::SelfCollisionBatches => "SelfCollisionBatches",
+            ProfileCounter::DroppedCandidates => "DroppedCandidates",
+        }
+    }
+
+    pub const fn count() -> usize {
+        4
+    }
+}
+
+#[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+const ALL_COUNTERS: [ProfileCounter; ProfileCounter::count()] = [
+    ProfileCounter::CollisionPairs,
+    ProfileCounter::Contacts,
+    ProfileCounter::SelfCollisionBatches,
+    ProfileCounter::DroppedCandidates,
+];
+
+/// All categories in report order.
+#[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+const ALL_CATEGORIES: [ProfileCategory; ProfileCategory::count()] = [
+    ProfileCategory::Frame,
+    ProfileCategory::Integration,
+    ProfileCategory::BroadPhase,
+    ProfileCategory::NarrowPhase,
+    ProfileCategory::Constraints,
+    ProfileCategory::DistanceConstraint,
+    ProfileCategory::BendingConstraint,
+    ProfileCategory::TetherConstraint,
+    ProfileCategory::AreaConstraint,
+    ProfileCategory::CollisionResolve,
+    ProfileCategory::SelfCollision,
+    ProfileCategory::SelfCollisionDetect,
+    ProfileCategory::SelfCollisionColor,
+    ProfileCategory::SelfCollisionResolve,
+    ProfileCategory::Normals,
+    ProfileCategory::Aerodynamics,
+    ProfileCategory::MouseConstraint,
+];
+
+/// Number of recent samples kept per category for percentile estimates.
+pub const SAMPLE_WINDOW: usize = 256;
+
 /// Timing statistics for a single category
 #[derive(Clone, Debug, Default)]
 pub struct TimingStats {
@@ -75,6 +133,10 @@ pub struct TimingStats {
     pub avg_ms: f64,
     /// Last recorded time
     pub last_ms: f64,
+    /// Ring buffer of the last `SAMPLE_WINDOW` samples (for p95/p99).
+    samples: Vec<f64>,
+    /// Next write position in `samples` once the window is full.
+    cursor: usize,
 }
 
 impl TimingStats {
@@ -100,6 +162,33 @@ impl TimingStats {
         } else {
             self.avg_ms = ALPHA * duration_ms + (1.0 - ALPHA) * self.avg_ms;
         }
+
+        if self.samples.len() < SAMPLE_WINDOW {
+            self.samples.push(duration_ms);
+        } else {
+            self.samples[self.cursor] = duration_ms;
+            self.cursor = (self.cursor + 1) % SAMPLE_WINDOW;
+        }
+    }
+
+    /// Returns the `p`-th percentile (0-100) of the recent sample window (nearest-rank).
+    /// Returns 0.0 when no samples have been recorded.
+    pub fn percentile(&self, p: f64) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+
+        let mut sorted = self.samples.clone();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    }
+
+    /// Minimum recorded time, or `None` before the first sample
+    /// (`min_ms` holds the `f64::MAX` sentinel until then).
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.min_ms)
     }
 
     pub fn reset(&mut self) {
@@ -107,9 +196,257 @@ impl TimingStats {
     }
 }
 
+#[cfg(feature = "profiling")]
+mod backend {
+    use super::{ProfileCategory, ProfileCounter, TimingStats};
+    use std::cell::RefCell;
+
+    pub(super) struct ProfilerState {
+        pub enabled: bool,
+        pub frame_count: u32,
+        pub stats: Vec<TimingStats>,
+        pub start_times: Vec<f64>,
+        pub counters: Vec<u64>,
+        /// Per-substep frame breakdown (index = substep within the frame); opt-in.
+        pub substep_breakdown: bool,
+        pub substeps: Vec<TimingStats>,
+        pub substep_start: f64,
+    }
+
+    impl ProfilerState {
+        fn new() -> Self {
+            Self {
+                enabled: false,
+                frame_count: 0,
+                stats: vec![TimingStats::new(); ProfileCategory::count()],
+                start_times: vec![0.0; ProfileCategory::count()],
+                counters: vec![0; ProfileCounter::count()],
+                substep_breakdown: false,
+                substeps: Vec::new(),
+                substep_start: 0.0,
+            }
+        }
+    }
+
+    thread_local! {
+        pub(super) static PROFILER: RefCell<ProfilerState> = RefCell::new(ProfilerState::new());
+    }
+
+    /// Current time in milliseconds.
+    /// Uses `performance.now()` from the global scope (works in both Window and Worker contexts).
+    #[cfg(target_arch = "wasm32")]
+    pub(super) fn now_ms() -> f64 {
+        use wasm_bindgen::JsCast;
+
+        thread_local! {
+            static PERFORMANCE: Option<web_sys::Performance> =
+                js_sys::Reflect::get(&js_sys::global(), &"performance".into())
+                    .ok()
+                    .and_then(|p| p.dyn_into::<web_sys::Performance>().ok());
+        }
+
+        PERFORMANCE.with(|p| p.as_ref().map_or(0.0, |p| p.now()))
+    }
+
+    /// Current time in milliseconds.
+    /// Native builds have no Web Performance API, so this is a monotonic `Instant`
+    /// measured from the first call (only differences are ever used).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn now_ms() -> f64 {
+        use std::sync::OnceLock;
+        use std::time::Instant;
+
+        static EPOCH: OnceLock<Instant> = OnceLock::new();
+        EPOCH.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0
+    }
+}
+
 /// Public profiler interface
 pub struct Profiler;
 
+#[cfg(feature = "profiling")]
+impl Profiler {
+    /// Start timing a category
+    #[inline]
+    pub fn start(category: ProfileCategory) {
+        backend::PROFILER.with(|p| {
+            let mut p = p.borrow_mut();
+            if p.enabled {
+                p.start_times[category as usize] = backend::now_ms();
+            }
+        });
+    }
+
+    /// End timing a category
+    #[inline]
+    pub fn end(category: ProfileCategory) {
+        backend::PROFILER.with(|p| {
+            let mut p = p.borrow_mut();
+            if p.enabled {
+                let elapsed = backend::now_ms() - p.start_times[category as usize];
+                p.stats[category as usize].record(elapsed);
+            }
+        });
+    }
+
+    /// Set an integer counter (e.g. pair/contact counts) for the report
+    #[inline]
+    pub fn set_counter(counter: ProfileCounter, value: u64) {
+        backend::PROFILER.with(|p| {
+            let mut p = p.borrow_mut();
+            if p.enabled {
+                p.counters[counter as usize] = value;
+            }
+        });
+    }
+
+    /// Mark the start of a new frame
+    #[inline]
+    pub fn begin_frame() {
+        Self::start(ProfileCategory::Frame);
+    }
+
+    /// Mark the end of a frame
+    #[inline]
+    pub fn end_frame() {
+        Self::end(ProfileCategory::Frame);
+        backend::PROFILER.with(|p| {
+            let mut p = p.borrow_mut();
+            if p.enabled {
+                p.frame_count = p.frame_count.wrapping_add(1);
+            }
+        });
+    }
+
+    /// Mark the start of a substep (only timed with the substep breakdown enabled)
+    #[inline]
+    pub fn begin_substep() {
+        backend::PROFILER.with(|p| {
+            let mut p = p.borrow_mut();
+            if p.enabled && p.substep_breakdown {
+                p.substep_start = backend::now_ms();
+            }
+        });
+    }
+
+    /// Mark the end of substep `index` of the current frame
+    #[inline]
+    pub fn end_substep(index: usize) {
+        backend::PROFILER.with(|p| {
+            let mut p = p.borrow_mut();
+            if p.enabled && p.substep_breakdown {
+                let elapsed = backend::now_ms() - p.substep_start;
+                if p.substeps.len() <= index {
+                    p.substeps.resize(index + 1, TimingStats::new());
+                }
+                p.substeps[index].record(elapsed);
+            }
+        });
+    }
+
+    /// Enable or disable profiling
+    pub fn set_enabled(enabled: bool) {
+        backend::PROFILER.with(|p| p.borrow_mut().enabled = enabled);
+    }
+
+    /// Whether profiling is currently enabled
+    pub fn is_enabled() -> bool {
+        backend::PROFILER.with(|p| p.borrow().enabled)
+    }
+
+    /// Enable or disable the per-substep breakdown (off by default).
+    /// Disabling it also drops the collected substep statistics.
+    pub fn set_substep_breakdown(enabled: bool) {
+        backend::PROFILER.with(|p| {
+            let mut p = p.borrow_mut();
+            p.substep_breakdown = enabled;
+            if !enabled {
+                p.substeps.clear();
+            }
+        });
+    }
+
+    /// Reset all statistics
+    pub fn reset() {
+        backend::PROFILER.with(|p| {
+            let mut p = p.borrow_mut();
+            p.frame_count = 0;
+            for stats in p.stats.iter_mut() {
+                stats.reset();
+            }
+            p.counters.fill(0);
+            p.substeps.clear();
+        });
+    }
+
+    /// Get profiling report as JSON string
+    /// Layout: `{"frameCount": N, "categories": {"Name": {"avg", "min", "max", "last", "count", "p95", "p99"}}, "counters": {"Name": n}, "substeps": [{...}]}`
+    /// Categories with no samples yet report `"count": 0` and `null` for `min`, `p95` and `p99`,
+    /// so they cannot be mistaken for genuine near-zero timings.
+    /// `substeps[i]` holds the stats of substep `i` (same fields as a category); the array
+    /// is empty unless the breakdown is enabled with `set_substep_breakdown`.
+    pub fn get_report_json() -> String {
+        backend::PROFILER.with(|p| {
+            let p = p.borrow();
+            let categories: Vec<String> = ALL_CATEGORIES
+                .iter()
+                .map(|&category| {
+                    format!("\"{}\":{}", category.name(), stats_json(&p.stats[category as usize]))
+                })
+                .collect();
+
+            let counters: Vec<String> = ALL_COUNTERS
+                .iter()
+                .map(|&counter| format!("\"{}\":{}", counter.name(), p.counters[counter as usize]))
+                .collect();
+
+            let substeps: Vec<String> = p.substeps.iter().map(stats_json).collect();
+
+            format!(
+                "{{\"frameCount\":{},\"categories\":{{{}}},\"counters\":{{{}}},\"substeps\":[{}]}}",
+                p.frame_count,
+                categories.join(","),
+                counters.join(","),
+                substeps.join(",")
+            )
+        })
+    }
+
+    /// Get timing for a specific category
+    pub fn get_timing(category: ProfileCategory) -> TimingStats {
+        backend::PROFILER.with(|p| p.borrow().stats[category as usize].clone())
+    }
+
+    /// Get the current value of a counter
+    pub fn get_counter(counter: ProfileCounter) -> u64 {
+        backend::PROFILER.with(|p| p.borrow().counters[counter as usize])
+    }
+
+    /// Get timing for substep `index` (`None` if it was never recorded)
+    pub fn get_substep_timing(index: usize) -> Option<TimingStats> {
+        backend::PROFILER.with(|p| p.borrow().substeps.get(index).cloned())
+    }
+}
+
+/// JSON object for one set of stats (unsampled fields are `null`).
+#[cfg(feature = "profiling")]
+fn stats_json(s: &TimingStats) -> String {
+    let sampled = |v: f64| {
+        if s.count > 0 { format!("{:.4}", v) } else { "null".to_string() }
+    };
+    format!(
+        "{{\"avg\":{:.4},\"min\":{},\"max\":{:.4},\"last\":{:.4},\"count\":{},\"p95\":{},\"p99\":{}}}",
+        s.avg_ms,
+        sampled(s.min_ms),
+        s.max_ms,
+        s.last_ms,
+        s.count,
+        sampled(s.percentile(95.0)),
+        sampled(s.percentile(99.0)),
+    )
+}
+
+#[cfg(not(feature = "profiling"))]
 impl Profiler {
     /// Start timing a category
     #[inline]
@@ -123,6 +460,12 @@ impl Profiler {
         // No-op
     }
 
+    /// Set an integer counter (e.g. pair/contact counts) for the report
+    #[inline]
+    pub fn set_counter(_counter: ProfileCounter, _value: u64) {
+        // No-op
+    }
+
     /// Mark the start of a new frame
     #[inline]
     pub fn begin_frame() {
@@ -135,11 +478,33 @@ impl Profiler {
         // No-op
     }
 
+    /// Mark the start of a substep
+    #[inline]
+    pub fn begin_substep() {
+        // No-op
+    }
+
+    /// Mark the end of substep `index` of the current frame
+    #[inline]
+    pub fn end_substep(_index: usize) {
+        // No-op
+    }
+
     /// Enable or disable profiling
     pub fn set_enabled(_enabled: bool) {
         // No-op
     }
 
+    /// Whether profiling is currently enabled
+    pub fn is_enabled() -> bool {
+        false
+    }
+
+    /// Enable or disable the per-substep breakdown
+    pub fn set_substep_breakdown(_enabled: bool) {
+        // No-op
+    }
+
     /// Reset all statistics
     pub fn reset() {
         // No-op
@@ -154,6 +519,16 @@ impl Profiler {
     pub fn get_timing(_category: ProfileCategory) -> TimingStats {
         TimingStats::new()
     }
+
+    /// Get the current value of a counter
+    pub fn get_counter(_counter: ProfileCounter) -> u64 {
+        0
+    }
+
+    /// Get timing for substep `index`
+    pub fn get_substep_timing(_index: usize) -> Option<TimingStats> {
+        None
+    }
 }
 
 /// RAII timer for scoped profiling
@@ -239,4 +614,119 @@ mod tests {
         // EMA should converge to 10.0
         assert!((stats.avg_ms - 10.0).abs() < 0.01);
     }
+
+    #[test]
+    fn test_timing_stats_percentiles() {
+        let mut stats = TimingStats::new();
+        assert_eq!(stats.percentile(95.0), 0.0);
+
+        // 1..=100 ms: p95 = 95, p99 = 99
+        for i in 1..=100 {
+            stats.record(i as f64);
+        }
+        assert_eq!(stats.percentile(95.0), 95.0);
+        assert_eq!(stats.percentile(99.0), 99.0);
+
+        // A single spike shows up in p99 even though the EMA barely moves
+        let mut spiky = TimingStats::new();
+        for _ in 0..99 {
+            spiky.record(1.0);
+        }
+        spiky.record(50.0);
+        assert_eq!(spiky.percentile(99.0), 1.0);
+        assert_eq!(spiky.percentile(100.0), 50.0);
+    }
+
+    #[test]
+    fn test_timing_stats_window_wraps() {
+        let mut stats = TimingStats::new();
+        for _ in 0..SAMPLE_WINDOW {
+            stats.record(100.0);
+        }
+        // Old samples are overwritten by newer ones
+        for _ in 0..SAMPLE_WINDOW {
+            stats.record(1.0);
+        }
+        assert_eq!(stats.percentile(99.0), 1.0);
+        assert_eq!(stats.max_ms, 100.0);
+    }
+
+    #[cfg(feature = "profiling")]
+    #[test]
+    fn test_disabled_until_host_opts_in() {
+        // Profiler state is thread-local, so a new thread sees the initial state
+        let count = std::thread::spawn(|| {
+            assert!(!Profiler::is_enabled());
+            Profiler::start(ProfileCategory::Frame);
+            Profiler::end(ProfileCategory::Frame);
+            Profiler::get_timing(ProfileCategory::Frame).count
+        });
+        assert_eq!(count.join().unwrap(), 0);
+    }
+
+    #[cfg(feature = "profiling")]
+    #[test]
+    fn test_counters_in_report() {
+        Profiler::set_enabled(true);
+        Profiler::set_counter(ProfileCounter::CollisionPairs, 42);
+        Profiler::set_counter(ProfileCounter::Contacts, 7);
+
+        let json = Profiler::get_report_json();
+        assert!(json.contains("\"counters\":{\"CollisionPairs\":42,\"Contacts\":7,\"SelfCollisionBatches\":0,\"DroppedCandidates\":0}"));
+
+        Profiler::reset();
+        assert_eq!(Profiler::get_counter(ProfileCounter::CollisionPairs), 0);
+    }
+
+    #[test]
+    fn test_timing_stats_min_unset_until_sampled() {
+        let mut stats = TimingStats::new();
+        assert_eq!(stats.min(), None);
+        stats.record(0.0);
+        assert_eq!(stats.min(), Some(0.0));
+        stats.reset();
+        assert_eq!(stats.min(), None);
+    }
+
+    #[cfg(feature = "profiling")]
+    #[test]
+    fn test_report_marks_unsampled_categories() {
+        Profiler::set_enabled(true);
+        Profiler::reset();
+
+        let json = Profiler::get_report_json();
+        assert!(json.contains("\"Frame\":{\"avg\":0.0000,\"min\":null,\"max\":0.0000,\"last\":0.0000,\"count\":0,\"p95\":null,\"p99\":null}"));
+        assert!(serde_json::from_str::<serde_json::Value>(&json).is_ok());
+    }
+
+    #[cfg(feature = "profiling")]
+    #[test]
+    fn test_substep_breakdown() {
+        Profiler::set_enabled(true);
+        Profiler::reset();
+
+        // Off by default: nothing is recorded
+        Profiler::begin_substep();
+        Profiler::end_substep(0);
+        assert!(Profiler::get_substep_timing(0).is_none());
+        assert!(Profiler::get_report_json().ends_with("\"substeps\":[]}"));
+
+        Profiler::set_substep_breakdown(true);
+        for _ in 0..3 {
+            for i in 0..4 {
+                Profiler::begin_substep();
+                Profiler::end_substep(i);
+            }
+        }
+        assert_eq!(Profiler::get_substep_timing(3).map(|s| s.count), Some(3));
+        assert!(Profiler::get_substep_timing(4).is_none());
+
+        let json = Profiler::get_report_json();
+        let report: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(report["substeps"].as_array().map(|a| a.len()), Some(4));
+        assert_eq!(report["substeps"][0]["count"], 3);
+
+        Profiler::set_substep_breakdown(false);
+        assert!(Profiler::get_substep_timing(0).is_none());
+    }
 }
@@ -0,0 +1,66 @@
+// physics/src/utils/real.rs
+
+//! Scalar type used by the scalar constraint kernels (`solve_single`).
+//!
+//! `f32` by default. With the `f64` feature the scalar kernels do their math in
+//! double precision and the 4-wide SIMD kernels are bypassed, which makes it easy to
+//! tell rounding drift from logic bugs when debugging natively.
+//! Particle buffers stay `f32` (they are shared with JS), so values are rounded
+//! once per constraint write-back.
+
+use glam::Vec3;
+
+#[cfg(not(feature = "f64"))]
+pub type Real = f32;
+#[cfg(not(feature = "f64"))]
+pub type RealVec3 = glam::Vec3;
+
+#[cfg(feature = "f64")]
+pub type Real = f64;
+#[cfg(feature = "f64")]
+pub type RealVec3 = glam::DVec3;
+
+/// True when the SIMD kernels are usable (they are `f32`-only).
+pub const SIMD_ENABLED: bool = !cfg!(feature = "f64");
+
+#[inline(always)]
+pub fn real(x: f32) -> Real {
+    Real::from(x)
+}
+
+#[cfg(not(feature = "f64"))]
+#[inline(always)]
+pub fn from_real(x: Real) -> f32 {
+    x
+}
+
+#[cfg(feature = "f64")]
+#[inline(always)]
+pub fn from_real(x: Real) -> f32 {
+    x as f32
+}
+
+#[cfg(not(feature = "f64"))]
+#[inline(always)]
+pub fn to_real3(v: Vec3) -> RealVec3 {
+    v
+}
+
+#[cfg(not(feature = "f64"))]
+#[inline(always)]
+pub fn from_real3(v: RealVec3) -> Vec3 {
+    v
+}
+
+#[cfg(feature = "f64")]
+#[inline(always)]
+pub fn to_real3(v: Vec3) -> RealVec3 {
+    v.as_dvec3()
+}
+
+#[cfg(feature = "f64")]
+#[inline(always)]
+pub fn from_real3(v: RealVec3) -> Vec3 {
+    v.as_vec3()
+}
+
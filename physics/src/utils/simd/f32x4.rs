@@ -18,6 +18,7 @@ pub struct F32x4 {
     data: [f32; 4],
 }
 
+#[allow(clippy::should_implement_trait)]
 impl F32x4 {
     /// Create from 4 scalar values.
     #[inline(always)]
@@ -140,6 +141,26 @@ impl F32x4 {
         }
     }
 
+    /// Element-wise absolute value.
+    #[inline(always)]
+    pub fn abs(self) -> Self {
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        {
+            Self { data: f32x4_abs(self.data) }
+        }
+        #[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
+        {
+            Self {
+                data: [
+                    self.data[0].abs(),
+                    self.data[1].abs(),
+                    self.data[2].abs(),
+                    self.data[3].abs(),
+                ],
+            }
+        }
+    }
+
     /// Element-wise square root.
     #[inline(always)]
     pub fn sqrt(self) -> Self {
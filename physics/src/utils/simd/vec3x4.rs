@@ -16,6 +16,7 @@ pub struct Vec3x4 {
     pub z: F32x4,
 }
 
+#[allow(clippy::should_implement_trait)]
 impl Vec3x4 {
     /// Create from 4 glam Vec4s (uses xyz, ignores w).
     #[inline(always)]
@@ -77,6 +78,24 @@ impl Vec3x4 {
         }
     }
 
+    /// Dot product for each of the 4 vector pairs.
+    #[inline(always)]
+    pub fn dot(self, rhs: Self) -> F32x4 {
+        self.x.mul(rhs.x)
+            .add(self.y.mul(rhs.y))
+            .add(self.z.mul(rhs.z))
+    }
+
+    /// Cross product for each of the 4 vector pairs.
+    #[inline(always)]
+    pub fn cross(self, rhs: Self) -> Self {
+        Self {
+            x: self.y.mul(rhs.z).sub(self.z.mul(rhs.y)),
+            y: self.z.mul(rhs.x).sub(self.x.mul(rhs.z)),
+            z: self.x.mul(rhs.y).sub(self.y.mul(rhs.x)),
+        }
+    }
+
     /// Compute length squared for each of the 4 vectors.
     #[inline(always)]
     pub fn length_squared(self) -> F32x4 {
@@ -0,0 +1,101 @@
+use glam::Vec4;
+use vestra_physics::collision::{CollisionResolver, MeshCollider};
+use vestra_physics::engine::config::PhysicsConfig;
+use vestra_physics::engine::state::PhysicsState;
+
+#[test]
+fn test_broad_phase_covers_whole_frame_motion() {
+    let mut collider = MeshCollider::new(
+        vec![-1.0, 0.0, -1.0, 1.0, 0.0, -1.0, 0.0, 0.0, 1.0],
+        vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+        vec![0, 2, 1],
+        0,
+        0.0,
+    );
+    // The BVH filters by exact triangle bounds (the grid reports whole 10cm cells)
+    collider.use_bvh(true);
+    // 9cm above the floor, falling 1cm per substep
+    let mut state = PhysicsState::new(&[0.0, 0.09, 0.0], &[0, 0, 0], &[0.0, 0.0]);
+    state.prev_positions[0] = Vec4::new(0.0, 0.1, 0.0, 0.0);
+    let config = PhysicsConfig::default();
+    let mut resolver = CollisionResolver::new(1);
+
+    // One substep of motion plus the margin does not reach the floor...
+    resolver.broad_phase(&state, &mut collider, &config, 1);
+    assert!(resolver.candidates(0).is_empty());
+
+    // ...but the eight substeps of the frame do
+    resolver.broad_phase(&state, &mut collider, &config, 8);
+    assert_eq!(resolver.candidates(0), &[0]);
+
+    // The margin comes from the config
+    let wide = PhysicsConfig { broad_phase_margin: 0.1, ..Default::default() };
+    resolver.broad_phase(&state, &mut collider, &wide, 1);
+    assert_eq!(resolver.candidates(0), &[0]);
+}
+
+#[test]
+fn test_max_candidates_caps_the_candidate_buffer() {
+    // Two triangles under three particles resting on them
+    let mut collider = MeshCollider::new(
+        vec![-1.0, 0.0, -1.0, 1.0, 0.0, -1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0],
+        vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+        vec![0, 2, 1, 1, 2, 3],
+        0,
+        0.0,
+    );
+    let state = PhysicsState::new(
+        &[0.0, 0.005, 0.0, 0.01, 0.005, 0.0, 0.02, 0.005, 0.0],
+        &[0, 1, 2],
+        &[0.0; 6],
+    );
+    let mut resolver = CollisionResolver::new(3);
+
+    let config = PhysicsConfig::default();
+    resolver.broad_phase(&state, &mut collider, &config, 1);
+    let per_particle = resolver.candidates(0).len();
+    assert!(per_particle > 0);
+    assert!(resolver.candidates(2).len() == per_particle);
+
+    // Room for two particles' candidates: one particle gets none. Serially it is the
+    // last one; the parallel gather keeps whichever two reserve first.
+    let capped = PhysicsConfig { max_candidates: per_particle * 2, ..Default::default() };
+    resolver.broad_phase(&state, &mut collider, &capped, 1);
+    let counts: Vec<usize> = (0..3).map(|i| resolver.candidates(i).len()).collect();
+    assert_eq!(counts, vec![per_particle, per_particle, 0]);
+}
+
+#[test]
+fn test_candidate_cap_is_applied_in_particle_order() {
+    // Ten stacked tiny triangles at the origin and one big triangle at x = 5
+    let mut positions = Vec::new();
+    let mut indices = Vec::new();
+    for k in 0..10 {
+        positions.extend([0.0, 0.0, 0.0, 0.0, 0.0, 0.01, 0.01, 0.0, 0.0]);
+        indices.extend([3 * k, 3 * k + 1, 3 * k + 2]);
+    }
+    positions.extend([5.0, 0.0, -1.0, 5.0, 0.0, 1.0, 6.0, 0.0, -1.0]);
+    indices.extend([30, 31, 32]);
+    let mut collider = MeshCollider::new(positions, vec![], indices, 0, 0.0);
+    collider.use_bvh(true);
+
+    // One candidate, ten candidates, one candidate
+    let state = PhysicsState::new(
+        &[5.2, 0.005, 0.0, 0.002, 0.005, 0.002, 5.3, 0.005, 0.0],
+        &[0, 1, 2],
+        &[0.0; 6],
+    );
+    let mut resolver = CollisionResolver::new(3);
+    let counts = |resolver: &CollisionResolver| (0..3).map(|i| resolver.candidates(i).len()).collect::<Vec<_>>();
+    resolver.broad_phase(&state, &mut collider, &PhysicsConfig::default(), 1);
+    assert_eq!(counts(&resolver), vec![1, 10, 1]);
+
+    // The overflowing middle particle is dropped; the last one still fits. The same with
+    // and without the `parallel` feature, on every run.
+    let capped = PhysicsConfig { max_candidates: 5, ..Default::default() };
+    for _ in 0..20 {
+        resolver.broad_phase(&state, &mut collider, &capped, 1);
+        assert_eq!(counts(&resolver), vec![1, 0, 1]);
+        assert_eq!(resolver.candidates(2), &[10]);
+    }
+}
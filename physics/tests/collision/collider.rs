@@ -1,4 +1,6 @@
 use vestra_physics::collision::MeshCollider;
+use glam::Vec3;
+use rustc_hash::FxHashSet;
 
 #[test]
 fn test_collider_initialization() {
@@ -33,3 +35,165 @@ fn test_collider_update() {
     // If we have a bounding box accessor:
     // assert!(collider.aabb.min.y >= 1.0);
 }
+
+#[test]
+fn test_sweep_segment_returns_first_hit() {
+    // Two stacked floor triangles at y = 0 and y = -0.5
+    let positions = vec![
+        -5.0, 0.0, -5.0, 15.0, 0.0, -5.0, -5.0, 0.0, 15.0,
+        -5.0, -0.5, -5.0, 15.0, -0.5, -5.0, -5.0, -0.5, 15.0,
+    ];
+    let indices = vec![0, 2, 1, 3, 5, 4];
+    let collider = MeshCollider::new(positions, vec![], indices, 0, 0.0);
+
+    let (point, normal, t) = collider
+        .sweep_segment(Vec3::new(0.0, 0.5, 0.0), Vec3::new(0.0, -1.0, 0.0))
+        .expect("Segment crosses the floor");
+    assert!(point.y.abs() < 1e-5, "First hit should be the upper floor: {:?}", point);
+    assert!(normal.y > 0.99, "Normal faces back towards the start");
+    assert!((t - 1.0 / 3.0).abs() < 1e-5);
+
+    assert!(collider
+        .sweep_segment(Vec3::new(0.0, 0.5, 0.0), Vec3::new(0.0, 0.1, 0.0))
+        .is_none());
+}
+
+#[test]
+fn test_flat_buffers_expose_processed_surface() {
+    let positions = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    let normals = vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0];
+    let collider = MeshCollider::new(positions.clone(), normals, vec![0, 1, 2], 0, 0.01);
+
+    let flat = collider.flat_vertices();
+    let flat_normals = collider.flat_normals();
+    assert_eq!(flat.len(), 9);
+    assert_eq!(flat_normals.len(), 9);
+    for i in 0..3 {
+        let n = Vec3::from_slice(&flat_normals[i * 3..]);
+        assert!((n.length() - 1.0).abs() < 1e-5);
+        // Inflation pushes each vertex along its normal
+        let moved = Vec3::from_slice(&flat[i * 3..]) - Vec3::from_slice(&positions[i * 3..]);
+        assert!((moved - n * 0.01).length() < 1e-5, "vertex {} moved by {:?}", i, moved);
+    }
+}
+
+#[test]
+fn test_set_inflation_offsets_along_normals() {
+    let positions = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    let normals = vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0];
+    let mut collider = MeshCollider::new(positions, normals, vec![0, 1, 2], 0, 0.01);
+    assert!((collider.vertices[0].z - 0.01).abs() < 1e-6);
+
+    collider.set_inflation(0.03);
+    assert_eq!(collider.inflation_amount, 0.03);
+    for v in &collider.vertices {
+        assert!((v.z - 0.03).abs() < 1e-6);
+    }
+    // Triangles (used by the narrow phase) follow the new surface
+    assert!((collider.triangles[0].v1.z - 0.03).abs() < 1e-6);
+
+    collider.set_inflation(0.0);
+    assert!(collider.vertices[0].z.abs() < 1e-6);
+}
+
+#[test]
+fn test_bvh_lookup_finds_same_hits() {
+    let positions = vec![
+        -5.0, 0.0, -5.0, 15.0, 0.0, -5.0, -5.0, 0.0, 15.0,
+        -5.0, -0.5, -5.0, 15.0, -0.5, -5.0, -5.0, -0.5, 15.0,
+    ];
+    let mut collider = MeshCollider::new(positions, vec![], vec![0, 2, 1, 3, 5, 4], 0, 0.0);
+    let (from, to) = (Vec3::new(0.0, 0.5, 0.0), Vec3::new(0.0, -1.0, 0.0));
+    let grid_hit = collider.sweep_segment(from, to);
+
+    #[allow(deprecated)]
+    let had_grid = collider.spatial_hash().is_some();
+    assert!(had_grid);
+
+    collider.use_bvh(true);
+    assert_eq!(collider.sweep_segment(from, to), grid_hit);
+    #[allow(deprecated)]
+    let has_grid = collider.spatial_hash().is_some();
+    assert!(!has_grid);
+
+    // Still valid after the body moves
+    collider.update(&[
+        -5.0, 0.2, -5.0, 15.0, 0.2, -5.0, -5.0, 0.2, 15.0,
+        -5.0, -0.5, -5.0, 15.0, -0.5, -5.0, -5.0, -0.5, 15.0,
+    ]);
+    let (point, _, _) = collider.sweep_segment(from, to).unwrap();
+    assert!((point.y - 0.2).abs() < 1e-5);
+}
+
+#[test]
+fn test_angle_weighted_normals_match_rebuild() {
+    // Tiny +Y face and huge +X face sharing vertex 0
+    let positions = vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.1, 0.1, 0.0, 0.0, 0.0, 10.0, 0.0, 0.0, 0.0, 10.0];
+    let indices = vec![0, 1, 2, 0, 3, 4];
+    let mut collider = MeshCollider::new(positions.clone(), vec![0.0; 15], indices.clone(), 0, 0.01);
+    assert!(collider.normals[0].x > 0.99);
+
+    collider.set_angle_weighted_normals(true);
+    let half = std::f32::consts::FRAC_1_SQRT_2;
+    assert!(collider.normals[0].distance(Vec3::new(half, half, 0.0)) < 1e-5);
+    // Inflation follows the new normal
+    assert!(collider.vertices[0].distance(Vec3::new(half, half, 0.0) * 0.01) < 1e-6);
+
+    // Same surface as re-processing the raw mesh with the flag already set
+    let mut rebuilt = MeshCollider::new(positions.clone(), vec![0.0; 15], indices, 0, 0.01);
+    rebuilt.angle_weighted_normals = true;
+    rebuilt.update(&positions);
+    for (a, b) in collider.vertices.iter().zip(&rebuilt.vertices) {
+        assert!(a.distance(*b) < 1e-6);
+    }
+}
+
+#[test]
+fn test_cell_size_is_derived_and_overridable() {
+    // Edges of 0.1, 0.1 and ~0.141: mean ~0.114, cells of twice that
+    let positions = vec![0.0, 0.0, 0.0, 0.1, 0.0, 0.0, 0.0, 0.0, 0.1];
+    let mut collider = MeshCollider::new(positions, vec![], vec![0, 2, 1], 0, 0.0);
+    let mean_edge = (0.2 + 0.02f32.sqrt()) / 3.0;
+    assert!((collider.cell_size - 2.0 * mean_edge).abs() < 1e-5);
+
+    let (from, to) = (Vec3::new(0.02, 0.5, 0.02), Vec3::new(0.02, -0.5, 0.02));
+    let hit = collider.sweep_segment(from, to);
+    assert!(hit.is_some());
+    let cells = collider.lookup.grid_stats().0;
+
+    // Finer cells, same answer
+    collider.set_cell_size(0.05);
+    assert_eq!(collider.cell_size, 0.05);
+    assert!(collider.lookup.grid_stats().0 > cells);
+    assert_eq!(collider.sweep_segment(from, to), hit);
+    assert!(collider.segment_hits(from, to, &mut Vec::new(), &mut FxHashSet::default()));
+
+    // 0 goes back to the derived size
+    collider.set_cell_size(0.0);
+    assert_eq!(collider.lookup.grid_stats().0, cells);
+}
+
+#[test]
+fn test_cell_size_keeps_bounds_padding_and_memory_in_check() {
+    // Small triangle: the derived cells hit the 2cm floor
+    let positions = vec![0.0, 0.0, 0.0, 0.005, 0.0, 0.0, 0.0, 0.0, 0.005];
+    let mut collider = MeshCollider::new(positions, vec![], vec![0, 2, 1], 0, 0.0);
+    assert_eq!(collider.cell_size, 0.02);
+    // The early-out still covers 20cm around the mesh
+    assert!(collider.lookup.contains(Vec3::new(0.0, 0.15, 0.0)));
+    assert!(!collider.lookup.contains(Vec3::new(0.0, 0.25, 0.0)));
+
+    // Manual sizes are clamped
+    collider.set_cell_size(1.0e-4);
+    assert_eq!(collider.cell_size, 0.02);
+
+    // Body-sized floor with the finest cells: the grid stays within its cell budget
+    let floor = vec![-5.0, 0.0, -5.0, 15.0, 0.0, -5.0, -5.0, 0.0, 15.0];
+    let mut collider = MeshCollider::new(floor, vec![], vec![0, 2, 1], 0, 0.0);
+    collider.set_cell_size(0.02);
+    assert!(collider.lookup.grid_stats().0 <= 1_000_000);
+    // Scratch buffers carry nothing over between rays
+    let (mut candidates, mut dedup) = (Vec::new(), FxHashSet::default());
+    assert!(collider.segment_hits(Vec3::new(1.0, 0.5, 1.0), Vec3::new(1.0, -0.5, 1.0), &mut candidates, &mut dedup));
+    assert!(!collider.segment_hits(Vec3::new(1.0, 0.5, 1.0), Vec3::new(1.0, 0.1, 1.0), &mut candidates, &mut dedup));
+}
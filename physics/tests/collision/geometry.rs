@@ -0,0 +1,67 @@
+use glam::Vec3;
+use vestra_physics::collision::geometry::Triangle;
+
+/// Right triangle in the XY plane spanning the unit square's lower-left half.
+fn unit_triangle() -> Triangle {
+    Triangle::new(Vec3::ZERO, Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), 0)
+}
+
+#[test]
+fn test_aabb_dist_sq_inside_is_zero() {
+    let tri = unit_triangle();
+    assert_eq!(tri.aabb_dist_sq(Vec3::new(0.5, 0.5, 0.0)), 0.0);
+    // Inside the box but outside the triangle still counts as inside
+    assert_eq!(tri.aabb_dist_sq(Vec3::new(0.9, 0.9, 0.0)), 0.0);
+    // On the boundary
+    assert_eq!(tri.aabb_dist_sq(Vec3::new(1.0, 0.0, 0.0)), 0.0);
+}
+
+#[test]
+fn test_aabb_dist_sq_face_edge_corner() {
+    let tri = unit_triangle();
+    // Face: straight out of the (flat) box along z
+    assert!((tri.aabb_dist_sq(Vec3::new(0.2, 0.2, 0.5)) - 0.25).abs() < 1e-6);
+    // Edge: outside in x and z
+    assert!((tri.aabb_dist_sq(Vec3::new(1.3, 0.5, 0.4)) - (0.09 + 0.16)).abs() < 1e-6);
+    // Corner: outside on all three axes
+    assert!((tri.aabb_dist_sq(Vec3::new(-0.1, -0.2, -0.2)) - (0.01 + 0.04 + 0.04)).abs() < 1e-6);
+}
+
+#[test]
+fn test_aabb_dist_sq_is_conservative() {
+    let tri = unit_triangle();
+    for k in 0..64 {
+        let p = Vec3::new(
+            (k % 4) as f32 * 0.6 - 0.5,
+            ((k / 4) % 4) as f32 * 0.6 - 0.5,
+            (k / 16) as f32 * 0.3 - 0.45,
+        );
+        let (closest, _) = tri.closest_point(p);
+        assert!(tri.aabb_dist_sq(p) <= closest.distance_squared(p) + 1e-6, "{:?}", p);
+    }
+}
+
+#[test]
+fn test_intersect_segment() {
+    let tri = unit_triangle();
+
+    // Crossing through the interior
+    let (point, normal, t) = tri
+        .intersect_segment(Vec3::new(0.25, 0.25, 1.0), Vec3::new(0.25, 0.25, -1.0))
+        .expect("Segment crosses the triangle");
+    assert!((point - Vec3::new(0.25, 0.25, 0.0)).length() < 1e-6);
+    assert!((t - 0.5).abs() < 1e-6);
+    // Normal faces the segment's start
+    assert!(normal.z > 0.99);
+
+    // Same line from the other side flips the normal
+    let (_, normal, _) = tri
+        .intersect_segment(Vec3::new(0.25, 0.25, -1.0), Vec3::new(0.25, 0.25, 1.0))
+        .unwrap();
+    assert!(normal.z < -0.99);
+
+    // Misses: stops short, outside the triangle, parallel
+    assert!(tri.intersect_segment(Vec3::new(0.25, 0.25, 1.0), Vec3::new(0.25, 0.25, 0.1)).is_none());
+    assert!(tri.intersect_segment(Vec3::new(0.8, 0.8, 1.0), Vec3::new(0.8, 0.8, -1.0)).is_none());
+    assert!(tri.intersect_segment(Vec3::new(0.1, 0.1, 0.5), Vec3::new(0.5, 0.1, 0.5)).is_none());
+}
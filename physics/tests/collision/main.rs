@@ -1,6 +1,9 @@
+mod broad;
 mod collider;
 mod exclusion;
+mod geometry;
 mod narrow;
 mod preprocessing;
+mod sdf;
 mod self_collision;
 mod spatial;
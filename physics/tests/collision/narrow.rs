@@ -2,7 +2,7 @@ use vestra_physics::collision::CollisionResolver;
 use vestra_physics::collision::MeshCollider;
 use vestra_physics::engine::config::PhysicsConfig;
 use vestra_physics::engine::state::PhysicsState;
-use glam::Vec4;
+use glam::{Vec3, Vec4};
 
 #[test]
 fn test_narrow_phase_integration() {
@@ -30,7 +30,7 @@ fn test_narrow_phase_integration() {
     // Debug loop
     println!("Initial Pos: {}", state.positions[0].y);
 
-    resolver.broad_phase(&state, &mut collider);
+    resolver.broad_phase(&state, &mut collider, &config, 1);
 
     // Iterative solving
     for i in 0..4 {
@@ -45,3 +45,160 @@ fn test_narrow_phase_integration() {
         state.positions[0].y
     );
 }
+
+#[test]
+fn test_two_sided_resolves_to_current_side() {
+    let run = |one_sided: bool| {
+        let mut state = PhysicsState::new(&[0.0, -0.002, 0.0], &[0, 0, 0], &[0.0, 0.0]);
+        let mut collider = MeshCollider::new(
+            vec![-1.0, 0.0, -1.0, 1.0, 0.0, -1.0, 0.0, 0.0, 1.0],
+            vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+            vec![0, 2, 1],
+            0,
+            0.0,
+        );
+        let mut resolver = CollisionResolver::new(1);
+        resolver.one_sided = one_sided;
+
+        // Resting 2mm behind the surface
+        state.prev_positions[0] = state.positions[0];
+        let config = PhysicsConfig::default();
+        resolver.broad_phase(&state, &mut collider, &config, 1);
+        resolver.narrow_phase(&mut state, &collider, &config, 0.016);
+        resolver.resolve_contacts(&mut state, &config, 0.016);
+        state.positions[0].y
+    };
+
+    // Body: back-face recovery pushes it to the front
+    assert!(run(true) > 0.0);
+    // Flag: stays behind, pushed out to the thickness on that side
+    let y = run(false);
+    assert!(y < -0.002, "Two-sided contact should push away from the surface on the back side: {}", y);
+}
+
+#[test]
+fn test_max_collision_correction_spreads_push_out() {
+    let resolve = |max_correction: f32| {
+        let mut state = PhysicsState::new(&[0.0, -0.015, 0.0], &[0, 0, 0], &[0.0, 0.0]);
+        let mut collider = MeshCollider::new(
+            vec![-1.0, 0.0, -1.0, 1.0, 0.0, -1.0, 0.0, 0.0, 1.0],
+            vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+            vec![0, 2, 1],
+            0,
+            0.0,
+        );
+        let mut resolver = CollisionResolver::new(1);
+
+        // Teleported 1.5cm inside the body
+        state.prev_positions[0] = state.positions[0];
+        let config = PhysicsConfig { max_collision_correction: max_correction, ..Default::default() };
+        resolver.broad_phase(&state, &mut collider, &config, 1);
+        resolver.narrow_phase(&mut state, &collider, &config, 0.016);
+        resolver.resolve_contacts(&mut state, &config, 0.016);
+        state.positions[0].y
+    };
+
+    // Unlimited: out in one resolve
+    assert!(resolve(0.0) > 0.0);
+    // Capped: moves exactly the cap toward the surface
+    let y = resolve(0.005);
+    assert!((y - -0.01).abs() < 1e-5, "Correction should be capped at 5mm: {}", y);
+}
+
+#[test]
+fn test_strongest_impact_reports_fastest_inbound_contact() {
+    // Particle 0 falls onto the floor at 1 m/s, particle 1 rests on it, particle 2 lifts off
+    let mut state = PhysicsState::new(&[0.0, 0.004, 0.0, 0.2, 0.004, 0.0, -0.2, 0.004, 0.0], &[0, 1, 2], &[0.0; 6]);
+    let dt = 0.002;
+    state.prev_positions[0].y += 1.0 * dt;
+    state.prev_positions[2].y -= 1.0 * dt;
+
+    let mut collider = MeshCollider::new(
+        vec![-1.0, 0.0, -1.0, 1.0, 0.0, -1.0, 0.0, 0.0, 1.0],
+        vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+        vec![0, 2, 1],
+        0,
+        0.0,
+    );
+    let mut resolver = CollisionResolver::new(3);
+    let config = PhysicsConfig::default();
+    resolver.broad_phase(&state, &mut collider, &config, 1);
+    resolver.narrow_phase(&mut state, &collider, &config, dt);
+
+    assert_eq!(resolver.contacts().len(), 3);
+    let (index, speed) = resolver.strongest_impact().expect("falling particle should register an impact");
+    assert_eq!(index, 0);
+    assert!((speed - 1.0).abs() < 1e-3, "Impact speed: {}", speed);
+}
+
+#[test]
+fn test_per_triangle_friction_scales_contact_friction() {
+    let slide = |friction: f32| {
+        let mut state = PhysicsState::new(&[0.001, -0.002, 0.0], &[0, 0, 0], &[0.0, 0.0]);
+        let mut collider = MeshCollider::new(
+            vec![-1.0, 0.0, -1.0, 1.0, 0.0, -1.0, 0.0, 0.0, 1.0],
+            vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+            vec![0, 2, 1],
+            0,
+            0.0,
+        );
+        collider.set_friction(vec![friction]).unwrap();
+        let mut resolver = CollisionResolver::new(1);
+
+        // Sliding 1mm/frame along the floor, slightly inside it
+        state.prev_positions[0] = Vec4::new(0.0, -0.002, 0.0, 0.0);
+        let config = PhysicsConfig::default();
+        resolver.broad_phase(&state, &mut collider, &config, 1);
+        resolver.narrow_phase(&mut state, &collider, &config, 0.016);
+        assert_eq!(resolver.contacts()[0].triangle, Some(0));
+        resolver.resolve_contacts(&mut state, &config, 0.016);
+        state.positions[0].x - state.prev_positions[0].x
+    };
+
+    // Frictionless triangle keeps the tangential motion, a grippy one stops it
+    assert!((slide(0.0) - 0.001).abs() < 1e-6);
+    assert!(slide(100.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_collider_friction_map_is_validated() {
+    let mut collider = MeshCollider::new(
+        vec![-1.0, 0.0, -1.0, 1.0, 0.0, -1.0, 0.0, 0.0, 1.0],
+        vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+        vec![0, 2, 1],
+        0,
+        0.0,
+    );
+    assert!(collider.set_friction(vec![1.0, 1.0]).is_err());
+    assert!(collider.set_friction(vec![-1.0]).is_err());
+    assert!(collider.set_friction(vec![0.5]).is_ok());
+    assert_eq!(collider.friction_at(0), 0.5);
+    assert!(collider.set_friction(Vec::new()).is_ok());
+    assert_eq!(collider.friction_at(0), 1.0);
+}
+
+#[test]
+fn test_cancelling_vertex_normals_fall_back_to_face_normal() {
+    let mut state = PhysicsState::new(&[-0.25, 0.002, -0.5], &[0, 0, 0], &[0.0, 0.0]);
+    let mut collider = MeshCollider::new(
+        vec![-1.0, 0.0, -1.0, 1.0, 0.0, -1.0, 0.0, 0.0, 1.0],
+        vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+        vec![0, 2, 1],
+        0,
+        0.0,
+    );
+    // Antiparallel vertex normals (a thin flap): they cancel exactly under the particle,
+    // which sits at barycentric (0.5, 0.25, 0.25)
+    collider.normals = vec![Vec3::Y, -Vec3::Y, -Vec3::Y];
+    let mut resolver = CollisionResolver::new(1);
+
+    let config = PhysicsConfig::default();
+    resolver.broad_phase(&state, &mut collider, &config, 1);
+    resolver.narrow_phase(&mut state, &collider, &config, 0.016);
+    assert_eq!(resolver.contacts().len(), 1);
+    assert_eq!(resolver.contacts()[0].normal, Vec3::Y);
+
+    resolver.resolve_contacts(&mut state, &config, 0.016);
+    assert!(state.positions[0].is_finite());
+    assert!(state.positions[0].y >= 0.002);
+}
@@ -10,7 +10,7 @@ fn test_mesh_conversion() {
     ];
     let indices = vec![0, 1, 2];
 
-    let processed = process_mesh(&raw_vertices, &indices, 0, 0.0);
+    let processed = process_mesh(&raw_vertices, &indices, 0, 0.5, 0.0);
 
     assert_eq!(processed.vertices.len(), 3);
     assert_eq!(processed.vertices[0], Vec3::new(0.0, 0.0, 0.0));
@@ -28,7 +28,7 @@ fn test_face_normals() {
     ];
     let indices = vec![0, 1, 2];
 
-    let processed = process_mesh(&raw_vertices, &indices, 0, 0.0);
+    let processed = process_mesh(&raw_vertices, &indices, 0, 0.5, 0.0);
 
     for n in processed.normals {
         assert_eq!(n, Vec3::Z);
@@ -48,7 +48,7 @@ fn test_smoothing() {
     let indices = vec![0, 1, 2];
 
     // Process with 1 iteration of smoothing
-    let processed = process_mesh(&raw_vertices, &indices, 1, 0.0);
+    let processed = process_mesh(&raw_vertices, &indices, 1, 0.5, 0.0);
 
     // Vertex 1: Neighbors 0 (0,0) and 2 (2,0). Avg neighbor pos = (1,0).
     // Original = (1,1). New = lerp((1,1), (1,0), 0.5) = (1, 0.5).
@@ -57,6 +57,26 @@ fn test_smoothing() {
     assert!(processed.vertices[1].y > 0.0);
 }
 
+#[test]
+fn test_smoothing_lambda_and_zero_iterations() {
+    let raw_vertices = vec![
+        0.0, 0.0, 0.0,
+        1.0, 1.0, 0.0, // Peak
+        2.0, 0.0, 0.0,
+    ];
+    let indices = vec![0, 1, 2];
+
+    // Peak moves `lambda` of the way towards the neighbor average (1, 0)
+    let gentle = process_mesh(&raw_vertices, &indices, 1, 0.25, 0.0);
+    assert!((gentle.vertices[1].y - 0.75).abs() < 1e-6);
+    let strong = process_mesh(&raw_vertices, &indices, 1, 1.0, 0.0);
+    assert!(strong.vertices[1].y.abs() < 1e-6);
+
+    // 0 iterations leaves the mesh untouched regardless of lambda
+    let untouched = process_mesh(&raw_vertices, &indices, 0, 1.0, 0.0);
+    assert_eq!(untouched.vertices[1].y, 1.0);
+}
+
 #[test]
 fn test_inflation() {
     let raw_vertices = vec![
@@ -67,7 +87,7 @@ fn test_inflation() {
     let indices = vec![0, 1, 2];
     let inflation = 0.1;
 
-    let processed = process_mesh(&raw_vertices, &indices, 0, inflation);
+    let processed = process_mesh(&raw_vertices, &indices, 0, 0.5, inflation);
 
     // Normals are +Z. Vertices should move by +0.1 in Z.
     assert!((processed.vertices[0].z - 0.1).abs() < 1e-6);
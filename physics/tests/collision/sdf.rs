@@ -0,0 +1,102 @@
+use glam::Vec3;
+use vestra_physics::collision::SdfCollider;
+use vestra_physics::collision::resolver::Contact;
+use vestra_physics::engine::config::PhysicsConfig;
+use vestra_physics::engine::state::PhysicsState;
+
+/// Builds an SDF grid for a sphere of `radius` centered at the origin.
+fn sphere_sdf(radius: f32, n: usize, extent: f32) -> SdfCollider {
+    let min = Vec3::splat(-extent);
+    let max = Vec3::splat(extent);
+    let step = (max - min) / (n - 1) as f32;
+
+    let mut data = Vec::with_capacity(n * n * n);
+    for z in 0..n {
+        for y in 0..n {
+            for x in 0..n {
+                let p = min + step * Vec3::new(x as f32, y as f32, z as f32);
+                data.push(p.length() - radius);
+            }
+        }
+    }
+
+    SdfCollider::new(data, [n, n, n], min, max)
+}
+
+#[test]
+fn test_sdf_sample_distance_and_gradient() {
+    let sdf = sphere_sdf(0.5, 41, 1.0);
+
+    let (d, grad) = sdf.sample(Vec3::new(0.7, 0.0, 0.0)).unwrap();
+    assert!((d - 0.2).abs() < 0.01, "Distance should be ~0.2, got {}", d);
+
+    let n = grad.normalize();
+    assert!(n.dot(Vec3::X) > 0.99, "Gradient should point outwards, got {:?}", n);
+
+    let (d_inside, _) = sdf.sample(Vec3::new(0.0, 0.3, 0.0)).unwrap();
+    assert!(d_inside < 0.0, "Points inside the body should be negative");
+
+    assert!(sdf.sample(Vec3::new(2.0, 0.0, 0.0)).is_none());
+}
+
+#[test]
+fn test_sdf_contact_generation() {
+    let sdf = sphere_sdf(0.5, 41, 1.0);
+
+    // Particle 0 penetrates the sphere, particle 1 is far outside.
+    let positions = vec![0.0, 0.49, 0.0, 0.0, 0.9, 0.0];
+    let indices = vec![0, 0, 1];
+    let uvs = vec![0.0; 4];
+    let state = PhysicsState::new(&positions, &indices, &uvs);
+
+    let config = PhysicsConfig::default();
+    let mut contacts = Vec::new();
+    sdf.collect_contacts(&state, &config, 0.016, &mut contacts);
+
+    assert_eq!(contacts.len(), 1);
+    let c = &contacts[0];
+    assert_eq!(c.particle_index, 0);
+    assert!(c.normal.dot(Vec3::Y) > 0.99);
+    assert!((c.surface_point.y - 0.5).abs() < 0.01);
+}
+
+#[test]
+fn test_sdf_try_new_rejects_malformed_grids() {
+    let (min, max) = (Vec3::splat(-1.0), Vec3::splat(1.0));
+    assert!(SdfCollider::try_new(vec![0.0; 8], [2, 2, 2], min, max).is_ok());
+
+    let err = SdfCollider::try_new(vec![0.0; 7], [2, 2, 2], min, max).err().unwrap();
+    assert!(err.contains("length"), "{}", err);
+    assert!(SdfCollider::try_new(vec![0.0; 4], [1, 2, 2], min, max).is_err());
+    assert!(SdfCollider::try_new(vec![0.0; 8], [2, 2, 2], max, min).is_err());
+    assert!(SdfCollider::try_new(vec![0.0; 8], [2, 2, 2], min, Vec3::new(1.0, f32::NAN, 1.0)).is_err());
+    assert!(SdfCollider::try_new(Vec::new(), [usize::MAX, 2, 2], min, max).is_err());
+}
+
+#[test]
+fn test_sdf_keeps_only_the_deeper_contact_per_particle() {
+    let sdf = sphere_sdf(0.5, 41, 1.0);
+    // Particle 0 is 0.1 inside the sphere, particle 1 0.01 inside
+    let positions = vec![0.0, 0.4, 0.0, 0.0, -0.49, 0.0];
+    let state = PhysicsState::new(&positions, &[0, 0, 1], &[0.0; 4]);
+    let config = PhysicsConfig::default();
+
+    // Mesh contacts: shallow for particle 0, deeper (0.05) for particle 1
+    let mesh_contact = |i: usize, surface_y: f32, normal: Vec3| Contact {
+        particle_index: i,
+        normal,
+        surface_point: Vec3::new(0.0, surface_y, 0.0),
+        approach_speed: 0.0,
+        triangle: Some(i),
+        friction: 1.0,
+    };
+    let mut contacts = vec![
+        mesh_contact(0, 0.39, Vec3::Y),
+        mesh_contact(1, -0.54, -Vec3::Y),
+    ];
+    sdf.collect_contacts(&state, &config, 0.016, &mut contacts);
+
+    assert_eq!(contacts.len(), 2);
+    assert_eq!(contacts[0].triangle, None, "SDF contact is deeper for particle 0");
+    assert_eq!(contacts[1].triangle, Some(1), "Mesh contact is deeper for particle 1");
+}
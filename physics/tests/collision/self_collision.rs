@@ -1,6 +1,6 @@
-use vestra_physics::collision::self_collision::{SelfCollision, SelfCollisionConfig};
+use vestra_physics::collision::self_collision::{Aabb, SelfCollision, SelfCollisionConfig};
 use vestra_physics::engine::state::PhysicsState;
-use glam::Vec4;
+use glam::{Vec3, Vec4};
 
 fn make_test_state() -> PhysicsState {
     // Create a simple 2-triangle mesh for testing
@@ -104,3 +104,241 @@ fn test_stiffness_effect() {
 
     assert!(delta_hard > delta_soft);
 }
+
+#[test]
+fn test_capped_pairs_are_deterministic() {
+    // Stack of disconnected triangles 2mm apart: every layer collides with its neighbors
+    let layers = 20;
+    let mut positions = Vec::new();
+    let mut indices = Vec::new();
+    for l in 0..layers {
+        let z = l as f32 * 0.002;
+        positions.extend_from_slice(&[0.0, 0.0, z, 0.003, 0.0, z, 0.0, 0.003, z]);
+        let base = (l * 3) as u32;
+        indices.extend_from_slice(&[base, base + 1, base + 2]);
+    }
+    let uvs = vec![0.0; layers * 6];
+
+    let run = || {
+        let mut state = PhysicsState::new(&positions, &indices, &uvs);
+        let config = SelfCollisionConfig {
+            thickness: 0.005,
+            max_pairs: 25, // well below the number of overlapping pairs
+            ..Default::default()
+        };
+        let mut self_coll = SelfCollision::new(&state, config);
+        for _ in 0..3 {
+            self_coll.solve(&mut state);
+        }
+        state.positions
+    };
+
+    let first = run();
+    for _ in 0..5 {
+        assert_eq!(run(), first, "Capped self-collision must not depend on thread scheduling");
+    }
+}
+
+#[test]
+fn test_vertex_triangle_catches_interior_pass_through() {
+    use vestra_physics::collision::self_collision::SelfCollisionMode;
+
+    // Large triangle (0..2) in z = 0, plus a separate triangle whose vertex 3
+    // hovers 2mm above the big triangle's interior, far from its corners.
+    let positions = vec![
+        0.0, 0.0, 0.0,
+        1.0, 0.0, 0.0,
+        0.0, 1.0, 0.0,
+        0.25, 0.25, 0.002,
+        0.6, 0.6, 0.5,
+        0.7, 0.5, 0.5,
+    ];
+    let indices = vec![0, 1, 2, 3, 4, 5];
+    let uvs = vec![0.0; 12];
+
+    let config = SelfCollisionConfig {
+        thickness: 0.005,
+        stiffness: 1.0,
+        ..Default::default()
+    };
+
+    // Point-point misses it: no vertex of the big triangle is nearby
+    let mut state = PhysicsState::new(&positions, &indices, &uvs);
+    let mut self_coll = SelfCollision::new(&state, config);
+    self_coll.solve(&mut state);
+    assert_eq!(state.positions[3].z, 0.002);
+
+    // Vertex-triangle pushes it out to the thickness
+    let mut state = PhysicsState::new(&positions, &indices, &uvs);
+    let mut self_coll = SelfCollision::new(&state, SelfCollisionConfig {
+        mode: SelfCollisionMode::VertexTriangle,
+        ..config
+    });
+    self_coll.solve(&mut state);
+
+    // Surface height under the vertex (barycentric 0.5 / 0.25 / 0.25)
+    let surface_z = 0.5 * state.positions[0].z + 0.25 * state.positions[1].z + 0.25 * state.positions[2].z;
+    let gap = state.positions[3].z - surface_z;
+    assert!((gap - 0.005).abs() < 1e-4, "Gap after resolution: {}", gap);
+    assert!(state.positions[0].z < 0.0, "Triangle corners take part of the correction");
+}
+
+#[test]
+fn test_layers_filter_and_bypass_exclusion() {
+    // One triangle whose edge 0-1 is squeezed to 1mm: normally excluded as topology neighbors
+    let make = || {
+        let mut state = make_test_state();
+        state.positions[0] = Vec4::new(0.0, 0.0, 0.0, 0.0);
+        state.positions[1] = Vec4::new(0.001, 0.0, 0.0, 0.0);
+        state
+    };
+    let config = SelfCollisionConfig {
+        thickness: 0.01,
+        ..Default::default()
+    };
+
+    // Different layers (e.g. welded shirt/jacket seam): exclusion no longer applies
+    let mut state = make();
+    state.layers = vec![0, 1, 0, 0];
+    let mut self_coll = SelfCollision::new(&state, config);
+    self_coll.solve(&mut state);
+    assert!(state.positions[0].x < 0.0, "Cross-layer neighbors should collide");
+
+    // ...unless those layers are masked off
+    let mut state = make();
+    state.layers = vec![0, 1, 0, 0];
+    let mut masked = config;
+    masked.set_layers_interact(0, 1, false);
+    assert!(!masked.layers_interact(1, 0));
+    let mut self_coll = SelfCollision::new(&state, masked);
+    self_coll.solve(&mut state);
+    assert_eq!(state.positions[0].x, 0.0);
+}
+
+#[test]
+fn test_set_thickness_updates_hash() {
+    let state = make_test_state();
+    let mut self_coll = SelfCollision::new(&state, SelfCollisionConfig::default());
+    assert_eq!(self_coll.cell_size(), 0.01);
+
+    self_coll.set_thickness(0.05);
+    assert_eq!(self_coll.config.thickness, 0.05);
+    assert_eq!(self_coll.cell_size(), 0.1);
+}
+
+#[test]
+fn test_pairs_emitted_once_across_cell_boundaries() {
+    let thickness = 0.01;
+    // Lattice at half the thickness: every fourth plane lies exactly on a fine-cell
+    // boundary (cell = 2 x thickness), so many particles straddle cells
+    let n = 6;
+    let spacing = thickness * 0.5;
+    let mut positions = Vec::new();
+    for z in 0..n {
+        for y in 0..n {
+            for x in 0..n {
+                positions.extend_from_slice(&[x as f32 * spacing, y as f32 * spacing, z as f32 * spacing]);
+            }
+        }
+    }
+    let count = n * n * n;
+    let mut state = PhysicsState::new(&positions, &[], &vec![0.0; count * 2]);
+    let initial = state.positions.clone();
+    let config = SelfCollisionConfig {
+        thickness,
+        max_pairs: 100_000,
+        ..Default::default()
+    };
+    let mut self_coll = SelfCollision::new(&state, config);
+    assert_eq!(self_coll.cell_size(), 2.0 * thickness);
+    self_coll.solve(&mut state);
+
+    let mut expected = Vec::new();
+    for i in 0..count {
+        for j in i + 1..count {
+            let d = initial[i].truncate().distance_squared(initial[j].truncate());
+            if d < thickness * thickness && d > 1e-9 {
+                expected.push((i, j));
+            }
+        }
+    }
+    let mut detected: Vec<(usize, usize)> = self_coll.pairs().collect();
+    assert_eq!(self_coll.pair_count(), expected.len());
+    // Sorting and comparing against the unique brute-force list catches duplicates
+    detected.sort_unstable();
+    assert_eq!(detected, expected);
+}
+
+#[test]
+fn test_pair_cap_keeps_deepest_overlaps() {
+    // Isolated particle pairs, far apart, with gaps shrinking along the mesh order:
+    // scan-order truncation would keep the shallowest ones
+    let gaps = [0.009, 0.007, 0.005, 0.003, 0.001];
+    let mut positions = Vec::new();
+    for (k, gap) in gaps.iter().enumerate() {
+        let x = k as f32;
+        positions.extend_from_slice(&[x, 0.0, 0.0, x, 0.0, *gap]);
+    }
+    let count = gaps.len() * 2;
+    let mut state = PhysicsState::new(&positions, &[], &vec![0.0; count * 2]);
+    let config = SelfCollisionConfig {
+        thickness: 0.01,
+        max_pairs: 2,
+        ..Default::default()
+    };
+    let mut self_coll = SelfCollision::new(&state, config);
+    self_coll.solve(&mut state);
+
+    // The two closest pairs (3mm and 1mm) survive the cap
+    let mut detected: Vec<(usize, usize)> = self_coll.pairs().collect();
+    detected.sort_unstable();
+    assert_eq!(detected, vec![(6, 7), (8, 9)]);
+}
+
+#[test]
+fn test_region_limits_detection() {
+    // Two isolated overlapping pairs: one at the "skirt" (y = 0), one at the "collar" (y = 1)
+    let positions = [0.0, 0.0, 0.0, 0.0, 0.0, 0.005, 0.0, 1.0, 0.0, 0.0, 1.0, 0.005];
+    let state = PhysicsState::new(&positions, &[], &[0.0; 8]);
+    let skirt = Aabb::new(Vec3::new(-1.0, -0.1, -1.0), Vec3::new(1.0, 0.1, 1.0));
+    let config = SelfCollisionConfig {
+        thickness: 0.01,
+        region: Some(skirt),
+        ..Default::default()
+    };
+    let mut self_coll = SelfCollision::new(&state, config);
+
+    self_coll.solve(&mut state.clone());
+    assert_eq!(self_coll.pairs().collect::<Vec<_>>(), vec![(0, 1)]);
+
+    // Moving the region drops the old particles from the hash
+    self_coll.config.region = Some(Aabb::new(Vec3::new(-1.0, 0.9, -1.0), Vec3::new(1.0, 1.1, 1.0)));
+    self_coll.solve(&mut state.clone());
+    assert_eq!(self_coll.pairs().collect::<Vec<_>>(), vec![(2, 3)]);
+
+    self_coll.config.region = None;
+    self_coll.solve(&mut state.clone());
+    assert_eq!(self_coll.pair_count(), 2);
+}
+
+#[test]
+fn test_nearest_particle_uses_hash() {
+    // A 10 x 10 grid of points 1cm apart
+    let mut positions = Vec::new();
+    for k in 0..100 {
+        positions.extend_from_slice(&[(k % 10) as f32 * 0.01, (k / 10) as f32 * 0.01, 0.0]);
+    }
+    let state = PhysicsState::new(&positions, &[], &[0.0; 200]);
+    let config = SelfCollisionConfig { thickness: 0.005, ..Default::default() };
+    let mut self_coll = SelfCollision::new(&state, config);
+
+    // Empty hash before the first detection pass
+    assert_eq!(self_coll.nearest_particle(&state, Vec3::ZERO), None);
+
+    self_coll.solve(&mut state.clone());
+    assert_eq!(self_coll.nearest_particle(&state, Vec3::new(0.031, 0.049, 0.002)), Some(53));
+    // Off the cloth: the growing radius still reaches the corner...
+    assert_eq!(self_coll.nearest_particle(&state, Vec3::new(0.12, 0.12, 0.0)), Some(99));
+    // ...up to a limit, past which callers scan linearly
+    assert_eq!(self_coll.nearest_particle(&state, Vec3::new(1.0, 1.0, 0.0)), None);
+}
@@ -74,3 +74,140 @@ fn test_boundary_conditions() {
     assert!(buffer.contains(&1));
     assert!(buffer.contains(&2));
 }
+
+#[test]
+fn test_update_point_moves_between_cells() {
+    let mut hash = HierarchicalSpatialHash::new(0.01);
+    hash.insert_point(0, Vec3::ZERO);
+    hash.insert_point(1, Vec3::new(0.005, 0.0, 0.0));
+
+    // Small move within the same fine cell
+    hash.update_point(1, Vec3::new(0.006, 0.0, 0.0));
+    // Large move to a distant cell
+    hash.update_point(0, Vec3::new(1.0, 0.0, 0.0));
+    // Untracked id is inserted
+    hash.update_point(2, Vec3::new(1.001, 0.0, 0.0));
+
+    let mut buffer = Vec::new();
+    let mut dedup_set = FxHashSet::default();
+
+    hash.query(Vec3::ZERO, 0.01, &mut buffer, &mut dedup_set);
+    assert_eq!(buffer, vec![1]);
+
+    hash.query(Vec3::new(1.0, 0.0, 0.0), 0.01, &mut buffer, &mut dedup_set);
+    buffer.sort();
+    assert_eq!(buffer, vec![0, 2]);
+
+    // Clear forgets tracked cells, so update re-inserts cleanly
+    hash.clear();
+    hash.update_point(0, Vec3::ZERO);
+    hash.query(Vec3::ZERO, 0.01, &mut buffer, &mut dedup_set);
+    assert_eq!(buffer, vec![0]);
+}
+
+#[test]
+fn test_set_collision_radius_regrids() {
+    let mut hash = HierarchicalSpatialHash::new(0.01);
+    hash.insert_point(0, Vec3::ZERO);
+    hash.insert_point(1, Vec3::new(0.05, 0.0, 0.0));
+
+    hash.set_collision_radius(0.05);
+    assert_eq!(hash.fine_cell_size(), 0.1);
+
+    // Old cells are gone until points are updated again
+    let mut buffer = Vec::new();
+    let mut dedup_set = FxHashSet::default();
+    hash.query(Vec3::ZERO, 0.05, &mut buffer, &mut dedup_set);
+    assert!(buffer.is_empty());
+
+    hash.update_point(0, Vec3::ZERO);
+    hash.update_point(1, Vec3::new(0.05, 0.0, 0.0));
+    hash.query(Vec3::ZERO, 0.05, &mut buffer, &mut dedup_set);
+    buffer.sort();
+    assert_eq!(buffer, vec![0, 1]);
+}
+
+#[test]
+fn test_update_point_small_moves_stay_consistent() {
+    // Fine cell size 0.02: nudge a point within its cell, then just across the boundary
+    let mut hash = HierarchicalSpatialHash::new(0.01);
+    hash.insert_point(0, Vec3::new(0.019, 0.0, 0.0));
+
+    let mut buffer = Vec::new();
+    let mut dedup_set = FxHashSet::default();
+
+    hash.update_point(0, Vec3::new(0.0195, 0.0, 0.0));
+    hash.query(Vec3::new(0.01, 0.0, 0.0), 0.001, &mut buffer, &mut dedup_set);
+    assert_eq!(buffer, vec![0]);
+
+    hash.update_point(0, Vec3::new(0.0201, 0.0, 0.0));
+    hash.query(Vec3::new(0.03, 0.0, 0.0), 0.001, &mut buffer, &mut dedup_set);
+    assert_eq!(buffer, vec![0], "Crossing into the next cell must re-hash");
+    hash.query(Vec3::new(0.01, 0.0, 0.0), 0.001, &mut buffer, &mut dedup_set);
+    assert!(buffer.is_empty(), "Old cell must no longer hold the point");
+}
+
+/// Tall, thin strip of triangles with uneven sizes (a crude "limb").
+fn limb_bounds() -> Vec<(Vec3, Vec3)> {
+    let mut bounds = Vec::new();
+    let mut y = 0.0;
+    for i in 0..400 {
+        let h = if i % 7 == 0 { 0.05 } else { 0.004 };
+        bounds.push((Vec3::new(0.0, y, 0.0), Vec3::new(0.03, y + h, 0.01)));
+        y += h * 0.5;
+    }
+    bounds
+}
+
+#[test]
+fn test_bvh_query_matches_brute_force() {
+    use vestra_physics::collision::spatial::TriangleBvh;
+
+    let bounds = limb_bounds();
+    let bvh = TriangleBvh::new(&bounds);
+    let mut buffer = Vec::new();
+
+    for k in 0..50 {
+        let p = Vec3::new(0.015, k as f32 * 0.03, 0.005 + (k % 3) as f32 * 0.01);
+        let radius = 0.01 + (k % 4) as f32 * 0.005;
+        bvh.query(p, radius, &mut buffer);
+
+        let mut expected: Vec<usize> = (0..bounds.len())
+            .filter(|&i| {
+                let (lo, hi) = bounds[i];
+                hi.cmpge(p - Vec3::splat(radius)).all() && lo.cmple(p + Vec3::splat(radius)).all()
+            })
+            .collect();
+        let mut got = buffer.clone();
+        got.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(got, expected, "query {} at {:?}", k, p);
+    }
+
+    assert!(bvh.contains(Vec3::new(0.015, 0.5, 0.0), 0.0));
+    assert!(!bvh.contains(Vec3::new(1.0, 0.5, 0.0), 0.1));
+}
+
+#[test]
+fn test_bvh_fewer_candidates_than_grid() {
+    use vestra_physics::collision::spatial::{StaticSpatialHash, TriangleBvh};
+
+    let bounds = limb_bounds();
+    let bvh = TriangleBvh::new(&bounds);
+    let mut grid = StaticSpatialHash::new(Vec3::ZERO, Vec3::new(0.03, 1.0, 0.01), 0.1);
+    for (i, &(lo, hi)) in bounds.iter().enumerate() {
+        grid.insert_aabb(i, lo, hi);
+    }
+
+    let (mut bvh_total, mut grid_total) = (0, 0);
+    let mut buffer = Vec::new();
+    let mut dedup = FxHashSet::default();
+    for k in 0..40 {
+        let p = Vec3::new(0.015, 0.01 + k as f32 * 0.02, 0.005);
+        bvh.query(p, 0.02, &mut buffer);
+        bvh_total += buffer.len();
+        grid.query(p, 0.02, &mut buffer, &mut dedup);
+        grid_total += buffer.len();
+    }
+    assert!(bvh_total * 2 < grid_total, "bvh {} vs grid {}", bvh_total, grid_total);
+}
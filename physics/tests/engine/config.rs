@@ -0,0 +1,92 @@
+use glam::Vec3;
+use vestra_physics::engine::config::PhysicsConfig;
+
+#[test]
+fn test_config_from_json_preset() {
+    // Partial preset: unspecified fields keep their defaults
+    let json = r#"{ "substeps": 12, "distance_compliance": 1e-5, "gravity": [0.0, -3.0, 0.0] }"#;
+    let config = PhysicsConfig::from_json(json).expect("valid preset");
+
+    assert_eq!(config.substeps, 12);
+    assert_eq!(config.distance_compliance, 1e-5);
+    assert_eq!(config.gravity, Vec3::new(0.0, -3.0, 0.0));
+    assert_eq!(config.solver_iterations, PhysicsConfig::default().solver_iterations);
+
+    // Round trip
+    let again = PhysicsConfig::from_json(&config.to_json()).unwrap();
+    assert_eq!(again.substeps, 12);
+}
+
+#[test]
+fn test_config_rejects_invalid_values() {
+    let err = PhysicsConfig::from_json(r#"{ "substeps": 0 }"#).unwrap_err();
+    assert!(err.contains("substeps"), "{}", err);
+
+    assert!(PhysicsConfig::from_json("{ not json").is_err());
+    assert!(PhysicsConfig::builder().damping(1.5).build().is_err());
+
+    // Same bounds as `set_aero_coefficients`
+    let err = PhysicsConfig::from_json(r#"{ "lift_coeff": -0.1 }"#).unwrap_err();
+    assert!(err.contains("lift_coeff"), "{}", err);
+    assert!(PhysicsConfig::builder().drag_coeff(-1.0).build().is_err());
+}
+
+#[test]
+fn test_config_builder() {
+    let config = PhysicsConfig::builder()
+        .substeps(4)
+        .solver_iterations(6)
+        .wind(Vec3::X)
+        .self_collision_enabled(false)
+        .build()
+        .unwrap();
+
+    assert_eq!(config.substeps, 4);
+    assert_eq!(config.solver_iterations, 6);
+    assert_eq!(config.wind, Vec3::X);
+    assert!(!config.self_collision_enabled);
+    assert!(PhysicsConfig::default().validate().is_ok());
+}
+
+#[test]
+fn test_discrete_contact_radius() {
+    let mut config = PhysicsConfig::default();
+    // Defaults keep the historical 5cm query
+    assert!((config.discrete_contact_radius(0.0) - 0.05).abs() < 1e-6);
+
+    // Tracks thickness, cloth radius and inflation
+    config.contact_thickness = 0.01;
+    config.cloth_radius = 0.002;
+    assert!((config.discrete_contact_radius(0.003) - (0.015 + 0.045)).abs() < 1e-6);
+
+    // Explicit override wins
+    config.contact_query_radius = 0.02;
+    assert_eq!(config.discrete_contact_radius(0.003), 0.02);
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_distance_stiffness_overrides_compliance() {
+    let config = PhysicsConfig::default();
+    assert_eq!(config.effective_distance_compliance(), config.distance_compliance);
+
+    let config = PhysicsConfig::builder().distance_stiffness(2.0e4).build().unwrap();
+    assert_eq!(config.effective_distance_compliance(), 5.0e-5);
+    assert!(PhysicsConfig::builder().distance_stiffness(-1.0).build().is_err());
+}
+
+#[test]
+fn test_dihedral_limits() {
+    assert_eq!(PhysicsConfig::default().dihedral_limits(), None);
+
+    let config = PhysicsConfig::builder()
+        .dihedral_limits_enabled(true)
+        .dihedral_min_angle(-0.5)
+        .dihedral_max_angle(1.0)
+        .build()
+        .unwrap();
+    assert_eq!(config.dihedral_limits(), Some((-0.5, 1.0)));
+
+    assert!(PhysicsConfig::builder().dihedral_min_angle(1.0).dihedral_max_angle(0.5).build().is_err());
+    assert!(PhysicsConfig::builder().dihedral_max_angle(4.0).build().is_err());
+}
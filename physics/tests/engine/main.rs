@@ -1 +1,8 @@
+mod config;
+mod memory;
+mod multi;
+mod native;
 mod simulation;
+mod state;
+mod topology;
+mod uv_lookup;
@@ -0,0 +1,29 @@
+use vestra_physics::engine::{MemoryReport, NativeEngine, PhysicsConfig};
+
+#[test]
+fn test_memory_report_counts_buffers() {
+    // Unit quad resting just above a floor
+    let mut engine = NativeEngine::new(
+        vec![0.0, 0.005, 0.0, 0.1, 0.005, 0.0, 0.1, 0.005, 0.1, 0.0, 0.005, 0.1],
+        vec![0, 2, 1, 0, 3, 2],
+        vec![0.0; 8],
+        vec![-1.0, 0.0, -1.0, 1.0, 0.0, -1.0, 0.0, 0.0, 1.0],
+        vec![0, 2, 1],
+        PhysicsConfig::default(),
+    )
+    .unwrap();
+    engine.run(2, 1.0 / 60.0);
+
+    let report = MemoryReport::new(&engine.sim);
+    assert_eq!(report.particles, 4);
+    assert_eq!(report.distance_constraints, 5);
+    assert_eq!(report.area_constraints, 2);
+    assert!(report.candidate_indices.len > 0);
+    assert!(report.candidate_indices.capacity >= report.candidate_indices.len);
+    assert!(report.collider_grid_cells > 0);
+    assert!(report.collider_grid_entries >= 1);
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+    assert_eq!(json["particles"], 4);
+    assert_eq!(json["candidate_indices"]["len"], report.candidate_indices.len);
+}
@@ -0,0 +1,102 @@
+use vestra_physics::engine::config::PhysicsConfig;
+use vestra_physics::engine::multi::MultiSimulation;
+use vestra_physics::engine::simulation::Simulation;
+
+const FLOOR_POS: [f32; 9] = [-5.0, 0.0, -5.0, 15.0, 0.0, -5.0, -5.0, 0.0, 15.0];
+const FLOOR_NORMALS: [f32; 9] = [0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0];
+const FLOOR_INDICES: [u32; 3] = [0, 2, 1];
+
+fn triangle_at(y: f32, x: f32) -> Vec<f32> {
+    vec![x, y, 0.0, x + 0.2, y, 0.0, x, y, 0.2]
+}
+
+#[test]
+fn test_garments_share_collider() {
+    let mut multi = MultiSimulation::new(FLOOR_POS.to_vec(), FLOOR_NORMALS.to_vec(), FLOOR_INDICES.to_vec(), 0, 0.5, 0.0);
+    let uvs = vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0];
+    let a = multi
+        .add_garment(triangle_at(0.1, 0.0), vec![0, 1, 2], uvs.clone(), 1.0, PhysicsConfig::default())
+        .unwrap();
+    let b = multi
+        .add_garment(triangle_at(0.3, 1.0), vec![0, 1, 2], uvs.clone(), 1.0, PhysicsConfig::default())
+        .unwrap();
+    assert_eq!((a, b, multi.garment_count()), (0, 1, 2));
+
+    // Same garment stepped on its own against an identical collider
+    let mut single = Simulation::new(
+        triangle_at(0.1, 0.0),
+        vec![0, 1, 2],
+        uvs,
+        FLOOR_POS.to_vec(),
+        FLOOR_NORMALS.to_vec(),
+        FLOOR_INDICES.to_vec(),
+        0,
+        0.0,
+        1.0,
+    );
+
+    for _ in 0..120 {
+        multi.step(0.016);
+        single.step(0.016);
+    }
+
+    for garment in &multi.garments {
+        for p in &garment.state.positions {
+            assert!(p.y > -1e-3, "Garment fell through the shared floor: {}", p.y);
+        }
+    }
+    assert_eq!(multi.garments[0].state.positions, single.state.positions);
+}
+
+#[test]
+fn test_add_garment_rejects_malformed_buffers() {
+    let mut multi = MultiSimulation::new(FLOOR_POS.to_vec(), FLOOR_NORMALS.to_vec(), FLOOR_INDICES.to_vec(), 0, 0.5, 0.0);
+    let err = multi
+        .add_garment(triangle_at(0.1, 0.0), vec![0, 1, 7], vec![0.0; 6], 1.0, PhysicsConfig::default())
+        .unwrap_err();
+    assert!(err.contains("garment_indices"), "{}", err);
+    assert_eq!(multi.garment_count(), 0);
+}
+
+#[test]
+fn test_attachments_bind_to_the_shared_collider() {
+    let mut multi = MultiSimulation::new(FLOOR_POS.to_vec(), FLOOR_NORMALS.to_vec(), FLOOR_INDICES.to_vec(), 0, 0.5, 0.0);
+    let uvs = vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0];
+    for x in [0.0, 1.0] {
+        multi.add_garment(triangle_at(0.01, x), vec![0, 1, 2], uvs.clone(), 1.0, PhysicsConfig::default()).unwrap();
+    }
+
+    // A garment's own collider is empty, so binding to it is an error
+    assert!(multi.garments[1].attach_to_collider(&[0]).is_err());
+    assert!(multi.attach_to_collider(2, &[0]).is_err());
+    assert!(multi.attach_to_collider(1, &[3]).is_err());
+    multi.attach_to_collider(1, &[0, 1, 2]).unwrap();
+    assert_eq!(multi.garments[1].attachments.len(), 3);
+
+    // Lift the body: the attached garment rides it, the free one stays put
+    let lifted: Vec<f32> = FLOOR_POS.iter().enumerate().map(|(k, &v)| if k % 3 == 1 { v + 0.5 } else { v }).collect();
+    multi.update_collider(&lifted);
+    for _ in 0..10 {
+        multi.step(0.016);
+    }
+    assert!(multi.garments[1].state.positions.iter().all(|p| (p.y - 0.51).abs() < 0.01));
+    assert!(multi.garments[0].state.positions.iter().all(|p| p.y < 0.1));
+}
+
+#[test]
+fn test_try_new_and_garment_index_are_checked() {
+    let err = MultiSimulation::try_new(FLOOR_POS.to_vec(), vec![0.0; 3], FLOOR_INDICES.to_vec(), 0, 0.5, 0.0)
+        .err()
+        .unwrap();
+    assert!(err.contains("collider_normals"), "{}", err);
+    assert!(MultiSimulation::try_new(FLOOR_POS.to_vec(), FLOOR_NORMALS.to_vec(), vec![0, 1, 9], 0, 0.5, 0.0).is_err());
+
+    assert!(MultiSimulation::try_new(FLOOR_POS.to_vec(), FLOOR_NORMALS.to_vec(), FLOOR_INDICES.to_vec(), 0, 1.5, 0.0).is_err());
+
+    let mut multi = MultiSimulation::try_new(FLOOR_POS.to_vec(), FLOOR_NORMALS.to_vec(), FLOOR_INDICES.to_vec(), 0, 0.25, 0.0)
+        .unwrap();
+    assert_eq!(multi.collider.smoothing_lambda, 0.25);
+    multi.add_garment(triangle_at(0.1, 0.0), vec![0, 1, 2], vec![0.0; 6], 1.0, PhysicsConfig::default()).unwrap();
+    assert_eq!(multi.garment(0).unwrap().state.count, 3);
+    assert!(multi.garment(1).is_err());
+}
@@ -0,0 +1,51 @@
+use vestra_physics::engine::{NativeEngine, PhysicsConfig};
+
+fn triangle_over_floor() -> NativeEngine {
+    NativeEngine::new(
+        vec![0.0, 0.5, 0.0, 0.1, 0.5, 0.0, 0.0, 0.5, 0.1],
+        vec![0, 1, 2],
+        vec![0.0; 6],
+        vec![-1.0, 0.0, -1.0, 1.0, 0.0, -1.0, 0.0, 0.0, 1.0],
+        vec![0, 2, 1],
+        PhysicsConfig::default(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_native_engine_steps_headless() {
+    let mut engine = triangle_over_floor();
+    let elapsed = engine.run(5, 1.0 / 60.0);
+    assert!(elapsed >= 0.0);
+    assert_eq!(engine.sim.frame_count(), 5);
+
+    // Falling under gravity, reported in input order
+    let positions = engine.positions();
+    assert_eq!(positions.len(), 9);
+    assert!(positions[1] < 0.5);
+    assert_eq!(positions[3], engine.sim.state.positions[engine.sim.state.internal_index[1] as usize].x);
+}
+
+#[test]
+fn test_native_engine_rejects_bad_buffers() {
+    let result = NativeEngine::new(vec![0.0; 9], vec![0, 1, 3], vec![0.0; 6], Vec::new(), Vec::new(), PhysicsConfig::default());
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "profiling")]
+#[test]
+fn test_native_profiler_measures_time() {
+    use vestra_physics::utils::profiler::{ProfileCategory, Profiler};
+
+    let mut engine = triangle_over_floor();
+    let was_enabled = Profiler::is_enabled();
+    Profiler::set_enabled(true);
+    engine.reset_profiler();
+    engine.run(3, 1.0 / 60.0);
+
+    let frame = Profiler::get_timing(ProfileCategory::Frame);
+    assert_eq!(frame.count, 3);
+    assert!(frame.max_ms > 0.0, "Native timings should come from Instant, not read as zero");
+    assert!(engine.profile_report().contains("\"Frame\""));
+    Profiler::set_enabled(was_enabled);
+}
@@ -1,3 +1,4 @@
+use vestra_physics::collision::SdfCollider;
 use vestra_physics::engine::simulation::Simulation;
 // use glam::Vec4;
 
@@ -67,3 +68,1051 @@ fn test_simulation_step() {
     // Should fall due to gravity
     assert!(sim.state.positions[0].y < initial_y, "Particle should fall due to gravity");
 }
+
+#[test]
+fn test_reorder_particles_preserves_geometry() {
+    // 3x3 vertex grid with scrambled input order
+    let n = 3;
+    let mut garment_pos = Vec::new();
+    for k in 0..n * n {
+        let s = (k * 5) % (n * n); // permuted placement
+        garment_pos.extend_from_slice(&[(s % n) as f32 * 0.1, 1.0 + (s / n) as f32 * 0.1, 0.0]);
+    }
+    let garment_indices = vec![0, 1, 2, 2, 3, 4, 4, 5, 6, 6, 7, 8];
+    let garment_uvs = vec![0.0; n * n * 2];
+
+    let mut sim = Simulation::new(
+        garment_pos.clone(),
+        garment_indices.clone(),
+        garment_uvs,
+        vec![0.0, -1.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, 1.0],
+        vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+        vec![0, 1, 2],
+        0,
+        0.01,
+        1.0,
+    );
+
+    sim.reorder_particles();
+
+    for k in 0..n * n {
+        let internal = sim.state.internal_index[k] as usize;
+        assert_eq!(sim.state.original_index[internal] as usize, k);
+        let p = sim.state.positions[internal];
+        assert_eq!(p.x, garment_pos[k * 3]);
+        assert_eq!(p.y, garment_pos[k * 3 + 1]);
+    }
+
+    // Triangles still reference the same physical vertices
+    for (t, &orig) in garment_indices.iter().enumerate() {
+        assert_eq!(sim.state.original_index[sim.state.indices[t] as usize], orig);
+    }
+
+    sim.step(0.016);
+    assert!(sim.state.positions.iter().all(|p| p.is_finite()));
+}
+
+#[test]
+fn test_adaptive_substeps_track_velocity() {
+    let mut sim = triangle_sim();
+    sim.config.adaptive_substeps = true;
+    sim.config.max_substeps = 12;
+    sim.config.self_collision_enabled = false;
+
+    // First frame has no velocity history: falls back to config.substeps
+    sim.step(0.016);
+    assert_eq!(sim.last_substeps(), sim.config.substeps);
+
+    // Near-rest cloth: a single substep is enough
+    sim.step(0.016);
+    assert_eq!(sim.last_substeps(), 1);
+
+    // Fast flick: capped at max_substeps
+    for i in 0..sim.state.count {
+        sim.state.prev_positions[i] = sim.state.positions[i] - glam::Vec4::new(0.5, 0.0, 0.0, 0.0);
+    }
+    sim.step(0.016);
+    assert_eq!(sim.last_substeps(), 12);
+}
+
+#[test]
+fn test_particles_sleep_and_wake() {
+    let mut sim = triangle_sim();
+    sim.config.gravity = glam::Vec3::ZERO;
+    sim.config.sleep_frames = 3;
+    sim.set_sleep_enabled(true);
+
+    for _ in 0..4 {
+        sim.step(0.016);
+    }
+    assert_eq!(sim.sleeping_count(), 3, "Motionless cloth should fall asleep");
+
+    // Dragging a particle wakes it, and its motion wakes the neighbors
+    sim.mouse.grab(0, glam::Vec3::new(0.0, 1.5, 0.0));
+    sim.step(0.016);
+    assert!(sim.state.awake[0]);
+    assert_eq!(sim.sleeping_count(), 0);
+    assert!(sim.state.positions[0].y > 1.0);
+}
+
+#[test]
+fn test_sleeping_particles_wake_on_setting_change_and_penetration() {
+    let mut sim = triangle_sim();
+    sim.config.gravity = glam::Vec3::ZERO;
+    sim.config.sleep_frames = 3;
+    sim.set_sleep_enabled(true);
+    for _ in 0..4 {
+        sim.step(0.016);
+    }
+    assert_eq!(sim.sleeping_count(), 3);
+
+    // Turning gravity back on must reach the resting cloth
+    sim.config.gravity = glam::Vec3::new(0.0, -9.81, 0.0);
+    sim.step(0.016);
+    assert_eq!(sim.sleeping_count(), 0);
+    assert!(sim.state.positions[0].y < 1.0);
+
+    sim.config.gravity = glam::Vec3::ZERO;
+    sim.state.prev_positions.clone_from(&sim.state.positions);
+    for _ in 0..4 {
+        sim.step(0.016);
+    }
+    assert_eq!(sim.sleeping_count(), 3);
+
+    // A distance field dropped over a resting particle pushes it out
+    let p2 = sim.state.positions[2].truncate();
+    let (n, extent) = (11, 0.5);
+    let min = p2 - glam::Vec3::splat(extent);
+    let step = 2.0 * extent / (n - 1) as f32;
+    let mut data = Vec::with_capacity(n * n * n);
+    for z in 0..n {
+        for y in 0..n {
+            for x in 0..n {
+                let p = glam::Vec3::new(x as f32, y as f32, z as f32) * step - glam::Vec3::splat(extent);
+                data.push(p.length() - 0.3);
+            }
+        }
+    }
+    sim.set_sdf_collider(SdfCollider::new(data, [n, n, n], min, p2 + glam::Vec3::splat(extent)));
+    sim.step(0.016);
+    assert!(sim.state.awake[2], "Penetrated particle should wake");
+    assert!(sim.state.positions[2].truncate().distance(p2) > 0.1);
+}
+
+#[test]
+fn test_kinetic_energy() {
+    let mut sim = triangle_sim();
+    assert_eq!(sim.kinetic_energy(), 0.0);
+
+    sim.step(0.016);
+    let falling = sim.kinetic_energy();
+    assert!(falling > 0.0, "Falling cloth has kinetic energy");
+
+    // Pinned particles contribute nothing
+    for w in sim.state.inv_mass.iter_mut() {
+        *w = 0.0;
+    }
+    assert_eq!(sim.kinetic_energy(), 0.0);
+}
+
+#[test]
+fn test_contacts_readable_after_step() {
+    // Cloth triangle resting just above a large floor triangle; the corner at the
+    // origin comes last so reordering actually permutes the particles
+    let mut sim = Simulation::new(
+        vec![0.2, 0.02, 0.0, 0.0, 0.02, 0.2, 0.0, 0.02, 0.0],
+        vec![0, 1, 2],
+        vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+        vec![-5.0, 0.0, -5.0, 15.0, 0.0, -5.0, -5.0, 0.0, 15.0],
+        vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+        vec![0, 2, 1],
+        0,
+        0.0,
+        1.0,
+    );
+    sim.reorder_particles();
+    sim.step(0.016);
+
+    let contacts = sim.resolver.contacts();
+    assert_eq!(contacts.len(), 3);
+    for (k, c) in contacts.iter().enumerate() {
+        assert_eq!(c.particle_index, k, "Contacts are ordered by particle");
+    }
+    assert_eq!(sim.resolver.contact_mask(), &[1, 1, 1]);
+
+    // The flat export reports original vertex indices, 7 floats per contact
+    let flat = sim.contacts_flat();
+    assert_eq!(flat.len(), 3 * 7);
+    let mut originals: Vec<u32> = flat.chunks_exact(7).map(|c| c[0] as u32).collect();
+    for c in flat.chunks_exact(7) {
+        let i = sim.state.internal_index[c[0] as usize] as usize;
+        assert!((c[1] - sim.state.positions[i].x).abs() < 1e-4, "Surface point sits under its particle");
+        assert!(c[2].abs() < 1e-4);
+        assert!(c[5].abs() > 0.99);
+    }
+    assert_ne!(sim.state.original_index, vec![0, 1, 2]);
+    originals.sort();
+    assert_eq!(originals, vec![0, 1, 2]);
+
+    // Lifted well clear of the floor: no contacts left
+    for p in sim.state.positions.iter_mut().chain(sim.state.prev_positions.iter_mut()) {
+        p.y = 1.0;
+    }
+    sim.step(0.016);
+    assert_eq!(sim.resolver.contact_mask(), &[0, 0, 0]);
+}
+
+#[test]
+fn test_try_with_config_reports_malformed_buffers() {
+    use vestra_physics::engine::config::PhysicsConfig;
+
+    let build = |pos: Vec<f32>, indices: Vec<u32>, uvs: Vec<f32>, normals: Vec<f32>| {
+        Simulation::try_with_config(
+            pos,
+            indices,
+            uvs,
+            vec![0.0, -1.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, 1.0],
+            normals,
+            vec![0, 1, 2],
+            0,
+            0.01,
+            1.0,
+            PhysicsConfig::default(),
+        )
+    };
+    let pos = vec![0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+    let uvs = vec![0.0; 6];
+    let normals = vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0];
+
+    assert!(build(pos.clone(), vec![0, 1, 2], uvs.clone(), normals.clone()).is_ok());
+
+    let err = build(pos[..8].to_vec(), vec![0, 1, 2], uvs.clone(), normals.clone()).err().unwrap();
+    assert!(err.starts_with("garment_pos"), "{}", err);
+
+    let err = build(pos.clone(), vec![0, 1, 3], uvs.clone(), normals.clone()).err().unwrap();
+    assert!(err.starts_with("garment_indices"), "{}", err);
+
+    let err = build(pos.clone(), vec![0, 1, 2], vec![0.0; 4], normals.clone()).err().unwrap();
+    assert!(err.starts_with("garment_uvs"), "{}", err);
+
+    let err = build(pos, vec![0, 1, 2], uvs, normals[..6].to_vec()).err().unwrap();
+    assert!(err.starts_with("collider_normals"), "{}", err);
+}
+
+#[test]
+fn test_serialize_roundtrip() {
+    let mut draped = triangle_sim();
+    draped.state.inv_mass[1] = 0.0;
+    for _ in 0..5 {
+        draped.step(0.016);
+    }
+    let bytes = draped.serialize();
+
+    // Restore into a fresh (and reordered) simulation of the same mesh
+    let mut restored = triangle_sim();
+    restored.reorder_particles();
+    restored.deserialize(&bytes).expect("snapshot should load");
+    for k in 0..3 {
+        let i = restored.state.internal_index[k] as usize;
+        assert_eq!(restored.state.positions[i], draped.state.positions[k]);
+        assert_eq!(restored.state.prev_positions[i], draped.state.prev_positions[k]);
+        assert_eq!(restored.state.inv_mass[i], draped.state.inv_mass[k]);
+    }
+
+    // Both continue identically
+    draped.step(0.016);
+    restored.step(0.016);
+    for k in 0..3 {
+        let i = restored.state.internal_index[k] as usize;
+        assert!((restored.state.positions[i] - draped.state.positions[k]).length() < 1e-6);
+    }
+
+    // Wrong mesh / corrupt data is rejected without touching the state
+    let before = restored.state.positions.clone();
+    assert!(restored.deserialize(&bytes[..bytes.len() - 4]).is_err());
+    assert!(restored.deserialize(b"nope").is_err());
+    // A NaN first position (after the 16-byte header) or an infinite last inverse mass
+    let mut nan_position = bytes.clone();
+    nan_position[16..20].copy_from_slice(&f32::NAN.to_le_bytes());
+    let err = restored.deserialize(&nan_position).unwrap_err();
+    assert!(err.contains("position"), "{}", err);
+    let mut inf_mass = bytes.clone();
+    let end = inf_mass.len();
+    inf_mass[end - 4..].copy_from_slice(&f32::INFINITY.to_le_bytes());
+    assert!(restored.deserialize(&inf_mass).is_err());
+    assert_eq!(restored.state.positions, before);
+    assert!(restored.state.inv_mass.iter().all(|w| w.is_finite()));
+}
+
+#[test]
+fn test_stiffness_map_sets_edge_compliance() {
+    let mut sim = triangle_sim();
+    let scale = sim.config.stiffness_map_scale;
+    let floor = sim.config.distance_compliance;
+
+    assert!(sim.set_stiffness_map(vec![1.0, 1.0]).is_err());
+
+    // Vertex 2 soft, 0 and 1 stiff
+    sim.set_stiffness_map(vec![1.0, 1.0, 0.0]).unwrap();
+    let dc = &sim.solver.distance_constraint;
+    for (k, &[a, b]) in dc.constraints.iter().enumerate() {
+        let expected = if a == 2 || b == 2 { 0.5 * scale } else { floor };
+        assert!((dc.compliances[k] - expected).abs() < 1e-12, "edge {:?}", [a, b]);
+    }
+
+    // Survives a particle reorder, and can be cleared
+    sim.reorder_particles();
+    let soft = sim.state.internal_index[2] as usize;
+    let dc = &sim.solver.distance_constraint;
+    for (k, &[a, b]) in dc.constraints.iter().enumerate() {
+        assert_eq!(dc.compliances[k] > floor, a == soft || b == soft);
+    }
+    sim.clear_stiffness_map();
+    assert!(sim.solver.distance_constraint.compliances.iter().all(|&c| c == floor));
+}
+
+#[test]
+fn test_non_finite_positions_are_recovered() {
+    let mut sim = triangle_sim();
+    sim.step(0.016);
+    assert_eq!(sim.nan_count(), 0);
+
+    // Simulate a blow-up: the NaN spreads to the neighbors through the constraints
+    sim.state.positions[0] = glam::Vec4::splat(f32::NAN);
+    sim.step(0.016);
+
+    assert!(sim.nan_count() >= 1);
+    for p in &sim.state.positions {
+        assert!(p.is_finite(), "Position should be repaired: {:?}", p);
+    }
+    for p in &sim.state.prev_positions {
+        assert!(p.is_finite());
+    }
+
+    sim.step(0.016);
+    assert_eq!(sim.nan_count(), 0);
+
+    // With no usable history or neighbors, particles return to their rest position
+    sim.state.positions.fill(glam::Vec4::splat(f32::NAN));
+    sim.state.prev_positions.fill(glam::Vec4::splat(f32::NAN));
+    sim.step(0.016);
+    for i in 0..3 {
+        let d = (sim.state.positions[i] - sim.state.rest_positions[i]).length();
+        assert!(d < 0.1, "Particle {} should restart near rest, off by {}", i, d);
+    }
+}
+
+#[test]
+fn test_contact_thickness_sets_rest_gap() {
+    let rest_height = |thickness: f32| {
+        let mut sim = Simulation::new(
+            vec![0.0, 0.03, 0.0, 0.2, 0.03, 0.0, 0.0, 0.03, 0.2],
+            vec![0, 1, 2],
+            vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+            vec![-5.0, 0.0, -5.0, 15.0, 0.0, -5.0, -5.0, 0.0, 15.0],
+            vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+            vec![0, 2, 1],
+            0,
+            0.0,
+            1.0,
+        );
+        sim.set_contact_thickness(thickness);
+        for _ in 0..120 {
+            sim.step(0.016);
+        }
+        sim.state.positions.iter().map(|p| p.y).sum::<f32>() / 3.0
+    };
+
+    let thin = rest_height(0.005);
+    let thick = rest_height(0.02);
+    assert!((thin - 0.005).abs() < 2e-3, "thin gap {}", thin);
+    assert!((thick - 0.02).abs() < 2e-3, "thick gap {}", thick);
+}
+
+#[test]
+fn test_convergence_tracking() {
+    let mut sim = triangle_sim();
+    sim.step(0.016);
+    assert!(sim.convergence().is_empty(), "Tracking is off by default");
+
+    sim.config.track_convergence = true;
+    // Stretch an edge so the solver has work to do
+    sim.state.positions[1].x = 1.5;
+    sim.state.prev_positions[1].x = 1.5;
+    sim.step(0.016);
+
+    let curve = sim.convergence();
+    assert_eq!(curve.len(), sim.config.solver_iterations);
+    assert!(curve[curve.len() - 1] < curve[0], "Residual should drop: {:?}", curve);
+}
+
+#[test]
+fn test_set_aero_coefficients_clamps() {
+    let mut sim = triangle_sim();
+    sim.set_aero_coefficients(3.0, -1.0);
+    assert_eq!(sim.config.drag_coeff, 3.0);
+    assert_eq!(sim.config.lift_coeff, 0.0);
+    assert!(sim.config.validate().is_ok());
+}
+
+#[test]
+fn test_position_w_stays_zero() {
+    // 3x3 grid falling onto a floor, with every optional solver path enabled
+    let mut positions = Vec::new();
+    let mut uvs = Vec::new();
+    for z in 0..3 {
+        for x in 0..3 {
+            positions.extend_from_slice(&[x as f32 * 0.1, 0.05, z as f32 * 0.1]);
+            uvs.extend_from_slice(&[x as f32 * 0.5, z as f32 * 0.5]);
+        }
+    }
+    let mut indices = Vec::new();
+    for z in 0..2u32 {
+        for x in 0..2u32 {
+            let i = z * 3 + x;
+            indices.extend_from_slice(&[i, i + 3, i + 1, i + 1, i + 3, i + 4]);
+        }
+    }
+    let mut sim = Simulation::new(
+        positions,
+        indices,
+        uvs,
+        vec![-5.0, 0.0, -5.0, 15.0, 0.0, -5.0, -5.0, 0.0, 15.0],
+        vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+        vec![0, 2, 1],
+        0,
+        0.01,
+        1.0,
+    );
+    sim.config.dihedral_enabled = true;
+    sim.rebuild_systems();
+    sim.mouse.grab(4, glam::Vec3::new(0.1, 0.2, 0.1));
+
+    for frame in 0..120 {
+        if frame == 60 {
+            sim.mouse.release();
+        }
+        sim.step(0.016);
+    }
+
+    for i in 0..sim.state.count {
+        assert_eq!(sim.state.positions[i].w, 0.0, "positions[{}].w drifted", i);
+        assert_eq!(sim.state.prev_positions[i].w, 0.0, "prev_positions[{}].w drifted", i);
+    }
+}
+
+#[test]
+fn test_cloth_radius_widens_body_and_self_gaps() {
+    let mut sim = Simulation::new(
+        vec![0.0, 0.03, 0.0, 0.2, 0.03, 0.0, 0.0, 0.03, 0.2],
+        vec![0, 1, 2],
+        vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+        vec![-5.0, 0.0, -5.0, 15.0, 0.0, -5.0, -5.0, 0.0, 15.0],
+        vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+        vec![0, 2, 1],
+        0,
+        0.0,
+        1.0,
+    );
+    sim.set_cloth_radius(0.01);
+    assert_eq!(sim.self_collision.config.thickness, sim.config.self_collision_thickness + 0.02);
+
+    for _ in 0..120 {
+        sim.step(0.016);
+    }
+    let rest = sim.state.positions.iter().map(|p| p.y).sum::<f32>() / 3.0;
+    assert!((rest - 0.015).abs() < 2e-3, "rest height {}", rest);
+
+    // Changing the self-collision thickness keeps the radius on top
+    sim.set_self_collision_thickness(0.004);
+    assert!((sim.self_collision.config.thickness - 0.024).abs() < 1e-7);
+}
+
+#[test]
+fn test_constraint_toggles_skip_solve() {
+    let stretched_length = |distance_enabled: bool| {
+        let mut sim = triangle_sim();
+        sim.config.gravity = glam::Vec3::ZERO;
+        sim.config.aerodynamics_enabled = false;
+        sim.config.self_collision_enabled = false;
+        sim.config.distance_enabled = distance_enabled;
+        sim.config.bending_enabled = false;
+        sim.config.tether_enabled = false;
+        sim.config.area_enabled = false;
+
+        sim.state.positions[1].x = 1.5;
+        sim.state.prev_positions[1].x = 1.5;
+        sim.step(0.016);
+        (sim.state.positions[1] - sim.state.positions[0]).truncate().length()
+    };
+
+    // Nothing pulls the stretched edge back with every constraint off
+    assert!((stretched_length(false) - 1.5).abs() < 1e-4);
+    assert!(stretched_length(true) < 1.1);
+}
+
+/// 10x10 sheet (5cm spacing) hanging from its pinned top row, aerodynamics and
+/// self-collision off, 4 iterations, convergence tracking on.
+/// One triangle (x/y unit legs at the origin) with a small floor far below it.
+fn triangle_sim() -> Simulation {
+    Simulation::new(
+        vec![0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0],
+        vec![0, 1, 2],
+        vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+        vec![0.0, -5.0, 0.0, 1.0, -5.0, 0.0, 0.0, -5.0, 1.0],
+        vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+        vec![0, 1, 2],
+        0,
+        0.01,
+        1.0,
+    )
+}
+
+/// Unit quad in the XY plane as 2 triangles, no collider.
+fn quad_sim() -> Simulation {
+    Simulation::new(
+        vec![0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+        vec![0, 1, 2, 0, 2, 3],
+        vec![0.0; 8],
+        vec![],
+        vec![],
+        vec![],
+        0,
+        0.0,
+        1.0,
+    )
+}
+
+fn hanging_sheet() -> Simulation {
+    let n = 10;
+    let mut pos = Vec::new();
+    let mut uvs = Vec::new();
+    for j in 0..n {
+        for i in 0..n {
+            pos.extend([i as f32 * 0.05, 1.0 - j as f32 * 0.05, 0.0]);
+            uvs.extend([i as f32 / n as f32, j as f32 / n as f32]);
+        }
+    }
+    let mut indices = Vec::new();
+    for j in 0..n - 1 {
+        for i in 0..n - 1 {
+            let a = (j * n + i) as u32;
+            let c = a + n as u32;
+            indices.extend([a, c, a + 1, a + 1, c, c + 1]);
+        }
+    }
+    let mut sim = Simulation::new(
+        pos,
+        indices,
+        uvs,
+        vec![0.0, -5.0, 0.0, 1.0, -5.0, 0.0, 0.0, -5.0, 1.0],
+        vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+        vec![0, 1, 2],
+        0,
+        0.01,
+        1.0,
+    );
+    sim.config.aerodynamics_enabled = false;
+    sim.config.self_collision_enabled = false;
+    sim.config.solver_iterations = 4;
+    sim.config.track_convergence = true;
+    for i in 0..sim.state.count {
+        if sim.state.positions[i].y > 0.999 {
+            sim.state.inv_mass[i] = 0.0;
+        }
+    }
+    sim
+}
+
+#[test]
+fn test_warm_start_converges_faster_on_settled_drape() {
+    let residual = |warm_start: bool| {
+        let mut sim = hanging_sheet();
+        sim.config.warm_start = warm_start;
+        for _ in 0..60 {
+            sim.step(1.0 / 60.0);
+        }
+        assert!(sim.state.positions.iter().all(|p| p.is_finite()));
+        *sim.convergence().last().unwrap()
+    };
+
+    let cold = residual(false);
+    let warm = residual(true);
+    assert!(warm < 0.8 * cold, "warm {} vs cold {}", warm, cold);
+}
+
+#[test]
+fn test_chebyshev_off_is_plain_gauss_seidel() {
+    let run = |use_chebyshev: bool, spectral_radius: f32| {
+        let mut sim = triangle_sim();
+        sim.config.use_chebyshev = use_chebyshev;
+        sim.config.spectral_radius = spectral_radius;
+        sim.state.positions[1].x = 1.5;
+        sim.state.prev_positions[1].x = 1.5;
+        sim.step(0.016);
+        sim.state.positions.clone()
+    };
+
+    // A zero spectral radius keeps omega at 1.0, same as switching the schedule off
+    assert_eq!(run(false, 0.9), run(true, 0.0));
+    assert_ne!(run(true, 0.9), run(true, 0.0));
+}
+
+#[test]
+fn test_external_forces_last_one_frame() {
+    let run = |pushes: usize| {
+        let mut sim = triangle_sim();
+        sim.config.gravity = glam::Vec3::ZERO;
+        sim.config.aerodynamics_enabled = false;
+        for frame in 0..3 {
+            if frame < pushes {
+                sim.add_force(0, glam::Vec3::new(0.0, 0.0, 1.0)).unwrap();
+            }
+            sim.step(0.016);
+        }
+        assert!(sim.add_force(3, glam::Vec3::X).is_err());
+        sim.state.positions.iter().map(|p| p.z).sum::<f32>()
+    };
+
+    assert_eq!(run(0), 0.0);
+    let once = run(1);
+    assert!(once > 0.0);
+    // A force not re-added is cleared after its frame; re-adding keeps accelerating
+    assert!(run(3) > 1.5 * once, "{} vs {}", run(3), once);
+}
+
+#[test]
+fn test_grab_maps_original_index_and_rejects_bad_ones() {
+    let mut sim = hanging_sheet();
+    assert!(sim.grab(100, glam::Vec3::ZERO).is_err());
+    assert_eq!(sim.mouse.grabbed_index, None);
+
+    // Original vertex 95 sits on the free bottom edge
+    sim.grab(95, glam::Vec3::new(0.25, 0.3, 0.5)).unwrap();
+    let i = sim.state.internal_index[95] as usize;
+    assert_eq!(sim.mouse.grabbed_index, Some(i));
+    let before = sim.state.positions[i].z;
+    sim.step(0.016);
+    assert!(sim.state.positions[i].z > before);
+}
+
+#[test]
+fn test_grab_stiffness_is_applied_only_when_changed() {
+    let mut sim = hanging_sheet();
+    assert_eq!(sim.mouse.compliance, 0.0, "Default grab is rigid");
+
+    sim.config.grab_stiffness = 0.5;
+    sim.step(0.016);
+    let soft = sim.mouse.compliance;
+    assert!(soft > 0.0);
+    assert_eq!(sim.group_grab.compliance, soft);
+
+    // A compliance set on the constraint itself survives unrelated setting changes
+    sim.mouse.compliance = 1.0;
+    sim.config.wind = glam::Vec3::X;
+    sim.step(0.016);
+    assert_eq!(sim.mouse.compliance, 1.0);
+}
+
+#[test]
+fn test_collider_smoothing_lambda_applies_on_next_collider_update() {
+    let raw = [0.0, -5.0, 0.0, 1.0, -5.0, 0.0, 0.0, -5.0, 1.0];
+    let mut sim = Simulation::new(
+        vec![0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0],
+        vec![0, 1, 2],
+        vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+        raw.to_vec(),
+        vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+        vec![0, 1, 2],
+        1,
+        0.0,
+        1.0,
+    );
+
+    sim.config.collider_smoothing_lambda = 0.0;
+    sim.step(0.016);
+    sim.update_collider(&raw);
+    assert_eq!(sim.collider.vertices[0], glam::Vec3::new(0.0, -5.0, 0.0), "lambda 0 leaves the mesh as is");
+
+    // Lambda 1 snaps each vertex to its neighbor average
+    sim.config.collider_smoothing_lambda = 1.0;
+    sim.step(0.016);
+    sim.update_collider(&raw);
+    assert!(sim.collider.vertices[0].distance(glam::Vec3::new(0.5, -5.0, 0.5)) < 1e-6);
+}
+
+#[test]
+fn test_tether_edits_survive_rebuilds() {
+    use vestra_physics::systems::constraints::{TetherGroup, TetherMode};
+
+    let mut sim = hanging_sheet();
+    assert!(sim.add_tether(0, 100, TetherGroup::Vertical, TetherMode::Bilateral).is_err());
+    assert!(sim.add_tether(5, 5, TetherGroup::Vertical, TetherMode::Bilateral).is_err());
+    assert!(sim.set_tether_mode(0, 1, TetherMode::Bilateral).is_err(), "No tether between neighbors");
+
+    // A bilateral strut across the bottom edge (original vertices 90 and 99)
+    sim.add_tether(90, 99, TetherGroup::Horizontal, TetherMode::Bilateral).unwrap();
+    sim.set_tether_group_mode(TetherGroup::Vertical, TetherMode::Compression);
+    sim.subdivide();
+
+    let tether = &sim.solver.tether_constraint;
+    let (a, b) = (sim.state.internal_index[90] as usize, sim.state.internal_index[99] as usize);
+    let k = tether
+        .constraints
+        .iter()
+        .position(|&[i, j]| (i, j) == (a, b))
+        .expect("Added tether should be rebuilt");
+    assert_eq!(tether.modes[k], TetherMode::Bilateral);
+    assert_eq!(tether.groups[k], TetherGroup::Horizontal);
+    assert!((tether.rest_lengths[k] - 0.45).abs() < 1e-5);
+    for (g, m) in tether.groups.iter().zip(&tether.modes) {
+        if *g == TetherGroup::Vertical {
+            assert_eq!(*m, TetherMode::Compression);
+        }
+    }
+}
+
+#[test]
+fn test_rest_positions_drive_rest_lengths() {
+    // Starts stretched to twice the rest pattern
+    let mut sim = Simulation::new(
+        vec![0.0, 2.0, 0.0, 2.0, 2.0, 0.0, 0.0, 0.0, 0.0],
+        vec![0, 1, 2],
+        vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+        vec![],
+        vec![],
+        vec![],
+        0,
+        0.0,
+        1.0,
+    );
+    sim.reorder_particles();
+    let start = sim.state.positions.clone();
+    assert!(sim.set_rest_positions(&[0.0, 1.0]).is_err());
+    sim.set_rest_positions(&[0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0]).unwrap();
+
+    assert_eq!(sim.state.positions, start);
+    let mut lengths = sim.solver.distance_constraint.rest_lengths.clone();
+    lengths.sort_by(f32::total_cmp);
+    assert_eq!(lengths, vec![1.0, 1.0, 2.0f32.sqrt()]);
+    assert_eq!(sim.solver.mesh_stats().max_triangle_area, 0.5);
+
+    // Released with no gravity, the stretched triangle contracts toward the rest shape
+    sim.config.gravity = glam::Vec3::ZERO;
+    sim.config.aerodynamics_enabled = false;
+    for _ in 0..30 {
+        sim.step(0.016);
+    }
+    let edge = |a: usize, b: usize| sim.state.positions[a].distance(sim.state.positions[b]);
+    let i = |orig: usize| sim.state.internal_index[orig] as usize;
+    assert!(edge(i(0), i(1)) < 1.5, "edge {}", edge(i(0), i(1)));
+}
+
+#[test]
+fn test_set_distance_stiffness_updates_edges() {
+    let mut sim = Simulation::new(
+        vec![0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0],
+        vec![0, 1, 2],
+        vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+        vec![],
+        vec![],
+        vec![],
+        0,
+        0.0,
+        1.0,
+    );
+    sim.set_distance_stiffness(1.0e3);
+    assert!(sim.solver.distance_constraint.compliances.iter().all(|&c| c == 1.0e-3));
+
+    // The raw compliance only applies once the stiffness is cleared
+    sim.set_distance_compliance(2.0e-4);
+    assert!(sim.solver.distance_constraint.compliances.iter().all(|&c| c == 1.0e-3));
+    sim.set_distance_stiffness(0.0);
+    assert!(sim.solver.distance_constraint.compliances.iter().all(|&c| c == 2.0e-4));
+}
+
+#[test]
+fn test_region_iterations_tighten_tagged_region() {
+    let residual = |multiplier: u32| {
+        let mut sim = hanging_sheet();
+        // Bottom half of the sheet is the "hem" region
+        let regions: Vec<u8> = (0..sim.state.count).map(|i| (i >= 50) as u8).collect();
+        sim.set_particle_regions(&regions);
+        sim.set_region_iterations(1, multiplier);
+        for _ in 0..60 {
+            sim.step(1.0 / 60.0);
+        }
+        assert!(sim.state.positions.iter().all(|p| p.is_finite()));
+        *sim.convergence().last().unwrap()
+    };
+
+    let uniform = residual(1);
+    let boosted = residual(4);
+    assert!(boosted < 0.8 * uniform, "boosted {} vs uniform {}", boosted, uniform);
+}
+
+#[test]
+fn test_step_is_reproducible() {
+    // Golden-test guarantee: identical inputs give bit-identical results, including the
+    // order in which constraints (tethers, bending, self-collision pairs) are built and solved
+    let run = || {
+        let mut sim = hanging_sheet();
+        sim.config.self_collision_enabled = true;
+        sim.config.aerodynamics_enabled = true;
+        sim.config.wind = glam::Vec3::new(0.0, 0.0, 2.0);
+        for _ in 0..30 {
+            sim.step(1.0 / 60.0);
+        }
+        (sim.solver.tether_constraint.constraints.clone(), sim.state.positions.clone())
+    };
+
+    let (tethers, positions) = run();
+    assert!(!tethers.is_empty());
+    for _ in 0..3 {
+        let (t, p) = run();
+        assert_eq!(t, tethers);
+        assert!(
+            p.iter().zip(&positions).all(|(a, b)| a.to_array().map(f32::to_bits) == b.to_array().map(f32::to_bits)),
+            "step() must be bit-for-bit reproducible"
+        );
+    }
+}
+
+#[test]
+fn test_frame_count_and_sim_time() {
+    let mut sim = hanging_sheet();
+    assert_eq!(sim.frame_count(), 0);
+    assert_eq!(sim.sim_time(), 0.0);
+
+    for _ in 0..3 {
+        sim.step(1.0 / 60.0);
+    }
+    sim.step(1.0 / 30.0);
+    assert_eq!(sim.frame_count(), 4);
+    assert!((sim.sim_time() - 5.0 / 60.0).abs() < 1e-6, "sim_time: {}", sim.sim_time());
+}
+
+#[test]
+fn test_fixed_dt_accumulates_partial_frames() {
+    let mut sim = hanging_sheet();
+    sim.config.fixed_dt = 1.0 / 60.0;
+
+    // Half a frame: nothing runs yet
+    sim.step(1.0 / 120.0);
+    assert_eq!(sim.frame_count(), 0);
+    // The carried half plus this call make one frame
+    sim.step(1.0 / 120.0);
+    assert_eq!(sim.frame_count(), 1);
+    // A 30 FPS render frame runs two physics frames
+    sim.step(1.0 / 30.0);
+    assert_eq!(sim.frame_count(), 3);
+    assert!((sim.sim_time() - 3.0 / 60.0).abs() < 1e-6);
+
+    // A long hitch is capped instead of replayed
+    sim.step(10.0);
+    assert_eq!(sim.frame_count(), 3 + 8);
+}
+
+#[test]
+fn test_orphan_vertices_are_reported_and_pinned() {
+    use vestra_physics::engine::config::PhysicsConfig;
+
+    // Vertex 1 is a stray point no triangle uses
+    let build = |pin: bool| {
+        let config = PhysicsConfig { pin_orphan_vertices: pin, ..Default::default() };
+        Simulation::with_config(
+            vec![0.0, 1.0, 0.0, 5.0, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0],
+            vec![0, 2, 3],
+            vec![0.0; 8],
+            vec![],
+            vec![],
+            vec![],
+            0,
+            0.0,
+            1.0,
+            config,
+        )
+    };
+
+    let mut sim = build(true);
+    sim.reorder_particles();
+    assert_eq!(sim.orphan_vertices(), vec![1]);
+    let stray = sim.state.internal_index[1] as usize;
+    assert_eq!(sim.state.inv_mass[stray], 0.0);
+    for _ in 0..10 {
+        sim.step(1.0 / 60.0);
+    }
+    assert_eq!(sim.state.positions[stray].y, 1.0);
+
+    // Opt-out: reported, but left free
+    let sim = build(false);
+    assert_eq!(sim.orphan_vertices(), vec![1]);
+    assert_eq!(sim.state.inv_mass[1], 1.0);
+}
+
+#[test]
+fn test_subdivide_rebuilds_constraints() {
+    let mut sim = quad_sim();
+    sim.state.inv_mass[0] = 0.0;
+    sim.state.inv_mass[1] = 0.0;
+    let edges_before = sim.solver.distance_constraint.compliances.len();
+
+    sim.subdivide();
+
+    // 4 vertices + 5 edge midpoints, 8 triangles
+    assert_eq!(sim.state.count, 9);
+    assert_eq!(sim.state.indices.len(), 24);
+    assert!(sim.solver.distance_constraint.compliances.len() > edges_before);
+
+    // The top edge stays pinned across its new midpoint
+    let mid = (4..9)
+        .find(|&i| (sim.state.positions[i].truncate() - glam::Vec3::new(0.5, 1.0, 0.0)).length() < 1e-6)
+        .expect("midpoint of the top edge");
+    assert_eq!(sim.state.inv_mass[mid], 0.0);
+    for _ in 0..10 {
+        sim.step(0.016);
+    }
+    assert_eq!(sim.state.positions[mid].y, 1.0);
+    assert!(sim.state.positions.iter().all(|p| p.is_finite()));
+}
+
+#[test]
+fn test_rebuild_keeps_turbulence_clock() {
+    let mut sim = quad_sim();
+    for _ in 0..3 {
+        sim.step(0.016);
+    }
+    let time = sim.aerodynamics.time();
+    assert!(time > 0.0);
+
+    sim.subdivide();
+    assert_eq!(sim.aerodynamics.time(), time);
+}
+
+#[test]
+fn test_nearest_vertex() {
+    let mut sim = quad_sim();
+    // Before the first step: linear scan
+    assert_eq!(sim.nearest_vertex(glam::Vec3::new(0.9, 0.1, 0.3)), Some(2));
+
+    sim.step(0.016);
+    let target = sim.state.positions[3].truncate() + glam::Vec3::new(0.01, 0.0, 0.0);
+    assert_eq!(sim.nearest_vertex(target), Some(3));
+}
+
+#[test]
+fn test_gravity_ramp_and_reset() {
+    // Unpinned, so the whole sheet free-falls
+    let falling_sheet = || {
+        let mut sim = hanging_sheet();
+        sim.state.inv_mass.fill(1.0);
+        sim
+    };
+    let bottom_y = |sim: &Simulation| sim.state.positions[sim.state.internal_index[99] as usize].y;
+    let start = bottom_y(&falling_sheet());
+
+    let mut full = falling_sheet();
+    let mut ramped = falling_sheet();
+    ramped.config.gravity_ramp_frames = 10;
+
+    // First frame of the ramp: no gravity at all
+    ramped.step(1.0 / 60.0);
+    assert!((bottom_y(&ramped) - start).abs() < 1e-6);
+
+    ramped.step(1.0 / 60.0);
+    ramped.step(1.0 / 60.0);
+    for _ in 0..3 {
+        full.step(1.0 / 60.0);
+    }
+    assert!(bottom_y(&ramped) < start);
+    assert!(bottom_y(&ramped) > bottom_y(&full), "ramped cloth should fall slower");
+
+    // Reset restores the pose and restarts the ramp
+    ramped.reset();
+    assert_eq!(ramped.frame_count(), 0);
+    assert_eq!(ramped.sim_time(), 0.0);
+    assert_eq!(bottom_y(&ramped), start);
+    ramped.step(1.0 / 60.0);
+    assert!((bottom_y(&ramped) - start).abs() < 1e-6);
+}
+
+#[test]
+fn test_uv_to_world_follows_the_drape() {
+    let mut sim = hanging_sheet();
+    for _ in 0..5 {
+        sim.step(1.0 / 60.0);
+    }
+
+    // UV (0.3, 0.4) is vertex 4 * 10 + 3 of the sheet
+    let vertex = sim.state.positions[sim.state.internal_index[43] as usize].truncate();
+    let p = sim.uv_to_world(glam::Vec2::new(0.3, 0.4)).unwrap();
+    assert!((p - vertex).length() < 1e-5, "{} vs {}", p, vertex);
+
+    // Halfway along an edge interpolates its endpoints
+    let next = sim.state.positions[sim.state.internal_index[44] as usize].truncate();
+    let mid = sim.uv_to_world(glam::Vec2::new(0.35, 0.4)).unwrap();
+    assert!((mid - (vertex + next) * 0.5).length() < 1e-5);
+
+    assert!(sim.uv_to_world(glam::Vec2::new(2.0, 2.0)).is_none());
+}
+
+#[test]
+fn test_settle_stops_at_rest() {
+    let mut sim = hanging_sheet();
+    let frames = sim.settle(1.0 / 60.0, 600, 1.0e-4);
+    assert!(frames > 1 && frames < 600, "settled after {} frames", frames);
+    assert!(sim.kinetic_energy() < 1.0e-4);
+    assert_eq!(sim.frame_count(), frames);
+
+    // An unreachable threshold runs the full budget
+    let mut sim = hanging_sheet();
+    assert_eq!(sim.settle(1.0 / 60.0, 5, 0.0), 5);
+}
+
+#[test]
+fn test_set_self_collision_frequency_at_runtime() {
+    let mut sim = hanging_sheet();
+    sim.config.self_collision_enabled = true;
+    sim.step(1.0 / 60.0);
+
+    sim.set_self_collision_frequency(1);
+    assert_eq!(sim.config.self_collision_frequency, 1);
+    assert_eq!(sim.self_collision.config.frequency, 1);
+
+    // 0 means every substep, same as 1
+    sim.set_self_collision_frequency(0);
+    assert_eq!(sim.self_collision.config.frequency, 1);
+    sim.self_collision.config.frequency = 0;
+    sim.step(1.0 / 60.0);
+    assert!(sim.state.positions.iter().all(|p| p.is_finite()));
+}
+
+#[cfg(feature = "profiling")]
+#[test]
+fn test_self_collision_frequency_sets_the_pass_rate() {
+    use vestra_physics::utils::profiler::{ProfileCategory, Profiler};
+
+    let mut sim = hanging_sheet();
+    sim.config.self_collision_enabled = true;
+    sim.config.adaptive_substeps = false;
+    sim.config.substeps = 4;
+    sim.set_self_collision_frequency(2);
+    // Passes run over 3 frames of 4 substeps
+    let passes = |sim: &mut Simulation| {
+        Profiler::reset();
+        for _ in 0..3 {
+            sim.step(1.0 / 60.0);
+        }
+        Profiler::get_timing(ProfileCategory::SelfCollision).count
+    };
+
+    let was_enabled = Profiler::is_enabled();
+    Profiler::set_enabled(true);
+    let every_other = passes(&mut sim);
+    sim.set_self_collision_frequency(1);
+    let every = passes(&mut sim);
+    Profiler::set_enabled(was_enabled);
+
+    assert_eq!(every_other, 6);
+    assert_eq!(every, 12);
+}
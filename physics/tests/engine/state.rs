@@ -0,0 +1,118 @@
+use glam::{Vec3, Vec4};
+use vestra_physics::engine::state::PhysicsState;
+
+#[test]
+fn test_state_aabb_tracks_positions() {
+    let mut state = PhysicsState::new(
+        &[0.0, 1.0, 0.0, 1.0, 1.0, -0.5, 0.0, 0.0, 0.25],
+        &[0, 1, 2],
+        &[0.0; 6],
+    );
+    assert_eq!(state.aabb(), (Vec3::new(0.0, 0.0, -0.5), Vec3::new(1.0, 1.0, 0.25)));
+
+    state.positions[2] = Vec4::new(-2.0, 3.0, 0.0, 0.0);
+    assert_eq!(state.aabb(), (Vec3::new(-2.0, 1.0, -0.5), Vec3::new(1.0, 3.0, 0.0)));
+
+    let empty = PhysicsState::new(&[], &[], &[]);
+    assert_eq!(empty.aabb(), (Vec3::ZERO, Vec3::ZERO));
+}
+
+#[test]
+fn test_new_with_quads_triangulates_and_keeps_quads() {
+    let positions = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0];
+    let state = PhysicsState::new_with_quads(&positions, &[0, 1, 2, 3], &[0.0; 8]).unwrap();
+
+    assert_eq!(state.indices, vec![0, 1, 2, 0, 2, 3]);
+    assert_eq!(state.quads, vec![[0, 1, 2, 3]]);
+
+    // Quads follow a permutation like the triangles do
+    let mut permuted = state.clone();
+    permuted.apply_permutation(&[3, 2, 1, 0]);
+    assert_eq!(permuted.quads, vec![[3, 2, 1, 0]]);
+
+    // Partial quads and out-of-range indices are rejected
+    assert!(PhysicsState::new_with_quads(&positions, &[0, 1, 2], &[0.0; 8]).is_err());
+    assert!(PhysicsState::new_with_quads(&positions, &[0, 1, 2, 4], &[0.0; 8]).is_err());
+}
+
+#[test]
+fn test_new_with_rest_separates_start_and_rest_shape() {
+    // Flat pattern, started folded up along the 0-1 edge
+    let rest = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    let draped = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0];
+    let state = PhysicsState::new_with_rest(&draped, &rest, &[0, 1, 2], &[0.0; 6]).unwrap();
+    assert_eq!(state.positions[2], Vec4::new(0.0, 0.0, 1.0, 0.0));
+    assert_eq!(state.rest_positions[2], Vec4::new(0.0, 1.0, 0.0, 0.0));
+
+    assert!(PhysicsState::new_with_rest(&draped, &rest[..6], &[0, 1, 2], &[0.0; 6]).is_err());
+    assert!(PhysicsState::new_with_rest(&draped, &[f32::NAN; 9], &[0, 1, 2], &[0.0; 6]).is_err());
+}
+
+#[test]
+fn test_area_density_masses() {
+    // Two triangles sharing the 1-2 edge; the second is three times as large
+    let mut state = PhysicsState::new(
+        &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 2.0, 2.0, 0.0, 5.0, 5.0, 5.0],
+        &[0, 1, 2, 1, 3, 2],
+        &[0.0; 10],
+    );
+    state.inv_mass[0] = 0.0;
+    state.apply_area_density(0.3);
+
+    let mass = |i: usize| 1.0 / state.inv_mass[i];
+    // Pinned and orphan particles are left alone
+    assert_eq!(state.inv_mass[0], 0.0);
+    assert_eq!(state.inv_mass[4], 1.0);
+    // Areas 0.5 and 1.5, a third of each per corner
+    assert!((mass(1) - 0.3 * 2.0 / 3.0).abs() < 1e-6);
+    assert!((mass(3) - 0.3 * 1.5 / 3.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_copy_positions_into_matches_buffer() {
+    let positions = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    let state = PhysicsState::new(&positions, &[0, 1, 2], &[0.0; 6]);
+
+    let mut out = vec![-1.0; 12];
+    state.copy_positions_into(&mut out).unwrap();
+    assert_eq!(out, vec![0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+    let mut short = vec![0.0; 9];
+    assert!(state.copy_positions_into(&mut short).is_err());
+}
+
+#[test]
+fn test_subdivide_splits_triangles_and_inherits_pins() {
+    let positions = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    let mut state = PhysicsState::new(&positions, &[0, 1, 2], &[0.0, 0.0, 1.0, 0.0, 0.0, 1.0]);
+    state.inv_mass[0] = 0.0;
+    state.inv_mass[1] = 0.0;
+
+    let parents = state.subdivide();
+
+    assert_eq!(state.count, 6);
+    assert_eq!(parents, vec![[0, 1], [0, 2], [1, 2]]);
+    assert_eq!(state.indices.len(), 12);
+    assert_eq!(state.original_index.len(), 6);
+    assert_eq!(state.internal_index.len(), 6);
+
+    // Midpoint of the pinned edge stays pinned, the others are free
+    assert_eq!(state.positions[3], Vec4::new(0.5, 0.0, 0.0, 0.0));
+    assert_eq!(state.inv_mass[3], 0.0);
+    assert_eq!(state.inv_mass[4], 1.0);
+    assert_eq!(state.inv_mass[5], 1.0);
+    assert_eq!(state.uvs[5], glam::Vec2::new(0.5, 0.5));
+
+    // Total area is preserved and every child keeps the parent's winding
+    let area = |s: &PhysicsState| -> Vec3 {
+        s.indices.chunks_exact(3).map(|t| {
+            let [a, b, c] = [t[0], t[1], t[2]].map(|i| s.positions[i as usize].truncate());
+            (b - a).cross(c - a) * 0.5
+        }).sum()
+    };
+    assert!((area(&state) - Vec3::new(0.0, 0.0, 0.5)).length() < 1e-6);
+    for t in state.indices.chunks_exact(3) {
+        let [a, b, c] = [t[0], t[1], t[2]].map(|i| state.positions[i as usize].truncate());
+        assert!((b - a).cross(c - a).z > 0.0);
+    }
+}
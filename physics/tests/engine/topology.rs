@@ -0,0 +1,69 @@
+use vestra_physics::engine::state::PhysicsState;
+use vestra_physics::engine::topology::{MeshTopology, NO_FACE};
+
+#[test]
+fn test_topology_csr_adjacency() {
+    // Two triangles sharing edge 1-2: (0,1,2) and (1,3,2)
+    let indices = vec![0u32, 1, 2, 1, 3, 2];
+    let topology = MeshTopology::new(&indices, 4);
+
+    assert_eq!(topology.vertex_count(), 4);
+    assert_eq!(topology.neighbors(0), &[1, 2]);
+    assert_eq!(topology.neighbors(1), &[0, 2, 3]);
+    assert_eq!(topology.neighbors(3), &[1, 2]);
+
+    assert!(topology.are_adjacent(1, 2));
+    assert!(!topology.are_adjacent(0, 3));
+
+    // 5 unique edges, sorted by (min, max)
+    assert_eq!(topology.edges, vec![[0, 1], [0, 2], [1, 2], [1, 3], [2, 3]]);
+}
+
+#[test]
+fn test_topology_built_with_state() {
+    let positions = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    // Degenerate triangle should not produce self-edges
+    let indices = vec![0u32, 1, 2, 2, 2, 1];
+    let uvs = vec![0.0; 6];
+    let state = PhysicsState::new(&positions, &indices, &uvs);
+
+    assert_eq!(state.topology.edges.len(), 3);
+    assert!(!state.topology.neighbors(2).contains(&2));
+}
+
+#[test]
+fn test_topology_edge_faces() {
+    // Two triangles sharing edge 1-2: (0,1,2) and (1,3,2)
+    let indices = vec![0u32, 1, 2, 1, 3, 2];
+    let topology = MeshTopology::new(&indices, 4);
+
+    let shared = topology.edge_index(2, 1).unwrap();
+    assert_eq!(topology.edge_face_counts[shared], 2);
+    assert_eq!(topology.edge_faces[shared], [0, 1]);
+
+    let boundary = topology.edge_index(0, 1).unwrap();
+    assert_eq!(topology.edge_face_counts[boundary], 1);
+    assert_eq!(topology.edge_faces[boundary], [0, NO_FACE]);
+
+    assert_eq!(topology.edge_index(0, 3), None);
+}
+
+#[test]
+fn test_topology_boundary_vertices() {
+    // 3x3 grid: only the center vertex (4) is interior
+    let mut indices = Vec::new();
+    for z in 0..2u32 {
+        for x in 0..2u32 {
+            let i = z * 3 + x;
+            indices.extend_from_slice(&[i, i + 3, i + 1, i + 1, i + 3, i + 4]);
+        }
+    }
+    let topo = MeshTopology::new(&indices, 9);
+    for i in 0..9 {
+        assert_eq!(topo.is_boundary(i), i != 4, "vertex {}", i);
+    }
+
+    // Closed tetrahedron: no boundary at all
+    let tet = MeshTopology::new(&[0, 1, 2, 0, 3, 1, 1, 3, 2, 2, 3, 0], 4);
+    assert!(tet.boundary_vertices.iter().all(|&b| !b));
+}
@@ -0,0 +1,25 @@
+use glam::Vec2;
+use vestra_physics::engine::UvLookup;
+
+#[test]
+fn test_uv_lookup_locates_triangle_and_weights() {
+    // Unit UV square split along its diagonal
+    let uvs = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(0.0, 1.0)];
+    let indices = [0, 1, 2, 0, 2, 3];
+    let lookup = UvLookup::new(&uvs, &indices);
+
+    let (t, bary) = lookup.locate(&uvs, &indices, Vec2::new(0.75, 0.25)).unwrap();
+    assert_eq!(t, 0);
+    let expected = [0.25, 0.5, 0.25];
+    for k in 0..3 {
+        assert!((bary[k] - expected[k]).abs() < 1e-5, "{:?}", bary);
+    }
+
+    let (t, _) = lookup.locate(&uvs, &indices, Vec2::new(0.1, 0.9)).unwrap();
+    assert_eq!(t, 1);
+    // Corners and edges are found despite rounding
+    assert!(lookup.locate(&uvs, &indices, Vec2::new(1.0, 1.0)).is_some());
+
+    assert!(lookup.locate(&uvs, &indices, Vec2::new(1.5, 0.5)).is_none());
+    assert!(UvLookup::new(&[], &[]).locate(&[], &[], Vec2::ZERO).is_none());
+}
@@ -0,0 +1,108 @@
+use vestra_physics::io::parse_obj;
+
+#[test]
+fn test_parse_quad_with_uvs() {
+    let text = "\
+# unit quad
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+vt 0 0
+vt 1 0
+vt 1 1
+vt 0 1
+f 1/1 2/2 3/3 4/4
+";
+    let mesh = parse_obj(text).unwrap();
+    assert_eq!(mesh.positions.len(), 12);
+    // Quad fan-triangulated into (0,1,2) and (0,2,3)
+    assert_eq!(mesh.indices, vec![0, 1, 2, 0, 2, 3]);
+    assert_eq!(mesh.uvs, vec![0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0]);
+}
+
+#[test]
+fn test_parse_normals_negative_indices_and_missing_uvs() {
+    let text = "\
+v 0 0 0
+v 1 0 0
+v 0 1 0
+vn 0 0 1
+mtllib cloth.mtl
+f -3//1 -2//1 -1//1
+";
+    let mesh = parse_obj(text).unwrap();
+    assert_eq!(mesh.indices, vec![0, 1, 2]);
+    assert_eq!(mesh.uvs, vec![0.0; 6]);
+}
+
+#[test]
+fn test_uv_seam_keeps_shared_vertex() {
+    // Vertex 2 is referenced with two different UVs; it stays one particle
+    let text = "\
+v 0 0 0
+v 1 0 0
+v 0 1 0
+v 1 1 0
+vt 0 0
+vt 1 0
+vt 0 1
+vt 0.5 0.5
+f 1/1 2/2 3/3
+f 2/4 4/2 3/3
+";
+    let mesh = parse_obj(text).unwrap();
+    assert_eq!(mesh.positions.len(), 12);
+    assert_eq!(mesh.indices, vec![0, 1, 2, 1, 3, 2]);
+    assert_eq!(&mesh.uvs[2..4], &[1.0, 0.0]);
+}
+
+#[test]
+fn test_parse_errors_name_the_line() {
+    let err = parse_obj("v 0 0 0\nv 1 0\n").unwrap_err();
+    assert!(err.contains("line 2"), "{}", err);
+
+    let err = parse_obj("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 4\n").unwrap_err();
+    assert!(err.contains("out of range"), "{}", err);
+
+    let err = parse_obj("v 0 0 0\nv 1 0 0\nf 1 2\n").unwrap_err();
+    assert!(err.contains("at least 3"), "{}", err);
+}
+
+#[test]
+fn test_write_obj_roundtrips_in_original_order() {
+    use vestra_physics::engine::simulation::Simulation;
+    use vestra_physics::io::write_obj;
+
+    // 3x3 grid with scrambled input order
+    let n = 3;
+    let mut garment_pos = Vec::new();
+    let mut garment_uvs = Vec::new();
+    for k in 0..n * n {
+        let s = (k * 5) % (n * n);
+        garment_pos.extend_from_slice(&[(s % n) as f32 * 0.1, 1.0 + (s / n) as f32 * 0.1, 0.0]);
+        garment_uvs.extend_from_slice(&[(s % n) as f32 * 0.5, (s / n) as f32 * 0.5]);
+    }
+    let garment_indices = vec![0, 1, 2, 2, 3, 4, 4, 5, 6, 6, 7, 8];
+
+    let mut sim = Simulation::new(
+        garment_pos.clone(),
+        garment_indices.clone(),
+        garment_uvs.clone(),
+        vec![0.0, -1.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, 1.0],
+        vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+        vec![0, 1, 2],
+        0,
+        0.01,
+        1.0,
+    );
+    sim.reorder_particles();
+
+    let text = write_obj(&sim.state);
+    assert_eq!(text.lines().filter(|l| l.starts_with("vn ")).count(), n * n);
+
+    let mesh = parse_obj(&text).unwrap();
+    assert_eq!(mesh.positions, garment_pos);
+    assert_eq!(mesh.uvs, garment_uvs);
+    assert_eq!(mesh.indices, garment_indices);
+}
@@ -41,3 +41,78 @@ fn test_area_constraint_restoration() {
     // Should be significantly less than 1.0
     assert!(area < 0.99, "Area should decrease towards rest area. Current: {}", area);
 }
+
+#[test]
+fn test_area_simd_matches_unrolled() {
+    // 4x4 grid of quads -> 32 triangles, enough to fill several SIMD chunks per batch
+    let n = 4;
+    let mut positions = Vec::new();
+    let mut uvs = Vec::new();
+    for y in 0..=n {
+        for x in 0..=n {
+            positions.extend_from_slice(&[x as f32 * 0.1, y as f32 * 0.1, 0.0]);
+            uvs.extend_from_slice(&[x as f32 / n as f32, y as f32 / n as f32]);
+        }
+    }
+    let mut indices = Vec::new();
+    for y in 0..n {
+        for x in 0..n {
+            let i = (y * (n + 1) + x) as u32;
+            let row = (n + 1) as u32;
+            indices.extend_from_slice(&[i, i + 1, i + row, i + 1, i + row + 1, i + row]);
+        }
+    }
+
+    let mut base = PhysicsState::new(&positions, &indices, &uvs);
+    let constraint = AreaConstraint::new(&base);
+
+    // Distort out of plane and in plane
+    for (i, p) in base.positions.iter_mut().enumerate() {
+        p.x *= 1.2;
+        p.z = (i % 3) as f32 * 0.02;
+    }
+    base.inv_mass[0] = 0.0; // Pinned vertex exercises the mass mask
+
+    let mut a = base.clone();
+    let mut b = base.clone();
+    constraint.solve_unrolled(&mut a, 2e-4, 1.0, 0.002);
+    constraint.solve_simd(&mut b, 2e-4, 1.0, 0.002);
+
+    for i in 0..base.count {
+        let d = (a.positions[i] - b.positions[i]).length();
+        assert!(d < 1e-5, "Kernels diverge at particle {}: {}", i, d);
+        assert_eq!(b.positions[i].w, 0.0);
+    }
+    assert!((a.positions[5] - base.positions[5]).length() > 0.0, "Constraint should move particles");
+}
+
+#[test]
+fn test_area_recovers_collapsed_triangle() {
+    let positions = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    let uvs = vec![0.0; 6];
+    let area_of = |s: &PhysicsState| {
+        let u = (s.positions[1] - s.positions[0]).truncate();
+        let v = (s.positions[2] - s.positions[0]).truncate();
+        0.5 * u.cross(v).length()
+    };
+
+    // Collapsed onto a line, and all the way to a point
+    for collapsed in [
+        [Vec4::ZERO, Vec4::new(1.0, 0.0, 0.0, 0.0), Vec4::new(0.5, 0.0, 0.0, 0.0)],
+        [Vec4::splat(0.0); 3],
+    ] {
+        let mut state = PhysicsState::new(&positions, &[0, 1, 2], &uvs);
+        let constraint = AreaConstraint::new(&state);
+        state.positions[..3].copy_from_slice(&collapsed);
+
+        constraint.solve(&mut state, 0.0, 1.0, 0.016);
+        let reopened = area_of(&state);
+        assert!(reopened > 1e-3, "Collapsed triangle should re-open, area {}", reopened);
+        assert!(state.positions.iter().all(|p| p.is_finite()));
+
+        for _ in 0..20 {
+            constraint.solve(&mut state, 0.0, 1.0, 0.016);
+        }
+        assert!(area_of(&state) > 0.25, "Area should head back to rest (0.5)");
+    }
+}
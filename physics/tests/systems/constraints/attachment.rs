@@ -0,0 +1,40 @@
+use vestra_physics::systems::constraints::AttachmentConstraint;
+use vestra_physics::collision::MeshCollider;
+use vestra_physics::engine::state::PhysicsState;
+use glam::Vec3;
+
+fn floor(y: f32) -> Vec<f32> {
+    vec![-1.0, y, -1.0, 1.0, y, -1.0, 0.0, y, 1.0]
+}
+
+fn floor_collider() -> MeshCollider {
+    MeshCollider::new(floor(0.0), vec![0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0], vec![0, 2, 1], 0, 0.0)
+}
+
+#[test]
+fn test_attachment_follows_moving_collider() {
+    let mut state = PhysicsState::new(&[0.1, 0.01, 0.0], &[], &[0.0, 0.0]);
+    let mut collider = floor_collider();
+    let attachment = AttachmentConstraint::new(&state, &collider, &[0]);
+    assert_eq!(attachment.len(), 1);
+
+    // The body moves up 20cm; the particle keeps its 1cm gap above the surface point
+    collider.update(&floor(0.2));
+    attachment.solve(&mut state, &collider, 0.002);
+    let p = state.positions[0].truncate();
+    assert!(p.distance(Vec3::new(0.1, 0.21, 0.0)) < 1e-5, "Attached particle should ride the body: {}", p);
+}
+
+#[test]
+fn test_soft_attachment_pulls_partially() {
+    let mut state = PhysicsState::new(&[0.1, 0.0, 0.0], &[], &[0.0, 0.0]);
+    let collider = floor_collider();
+    let mut attachment = AttachmentConstraint::new(&state, &collider, &[0]);
+    attachment.set_stiffness(0.5);
+
+    // Dragged 10cm off the surface
+    state.positions[0].y = 0.1;
+    attachment.solve(&mut state, &collider, 0.002);
+    let y = state.positions[0].y;
+    assert!(y > 0.0 && y < 0.1, "Soft attachment should not snap: {}", y);
+}
@@ -1,6 +1,7 @@
 use vestra_physics::systems::constraints::bending::BendingConstraint;
 use vestra_physics::engine::state::PhysicsState;
 use glam::Vec4;
+use super::fixtures::quad_grid;
 
 #[test]
 fn test_bending_constraint_flatness() {
@@ -37,3 +38,37 @@ fn test_bending_constraint_flatness() {
     // Check if 3 moved back towards z=0 (restoring flatness)
     assert!(state.positions[3].z < 1.0, "Bending constraint should restore flatness");
 }
+
+#[test]
+fn test_quad_bending_pairs_are_axis_aligned() {
+    let state = quad_grid();
+    let constraint = BendingConstraint::new(&state, 1.0);
+
+    // Only straight 2-ring pairs: 3 rows + 3 columns, nothing along the split diagonal
+    assert_eq!(constraint.constraints.len(), 6);
+    for &[i, j] in &constraint.constraints {
+        let d = (state.positions[i] - state.positions[j]).abs();
+        assert!(
+            (d.x < 1e-6 && (d.y - 0.2).abs() < 1e-6) || (d.y < 1e-6 && (d.x - 0.2).abs() < 1e-6),
+            "Pair {}-{} is not axis-aligned",
+            i,
+            j
+        );
+    }
+}
+
+#[test]
+fn test_bending_only_across_interior_edges() {
+    // Two triangles sharing edge 1-2: the only bending pair is 0-3
+    let positions = vec![0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+    let state = PhysicsState::new(&positions, &[0, 2, 1, 1, 2, 3], &[0.0; 8]);
+    assert_eq!(BendingConstraint::new(&state, 1.0).constraints, vec![[0, 3]]);
+
+    // Three flaps on one edge (non-manifold): no phantom pairs between unrelated flaps
+    let positions = vec![
+        0.0, 0.0, 0.0, 0.0, 1.0, 0.0,
+        1.0, 0.5, 0.0, -1.0, 0.5, 0.0, 0.0, 0.5, 1.0,
+    ];
+    let state = PhysicsState::new(&positions, &[0, 1, 2, 1, 0, 3, 0, 1, 4], &[0.0; 10]);
+    assert!(BendingConstraint::new(&state, 1.0).constraints.is_empty());
+}
@@ -0,0 +1,102 @@
+use vestra_physics::systems::constraints::dihedral::{
+    dihedral_angle_and_gradients, DihedralConstraint,
+};
+use vestra_physics::engine::state::PhysicsState;
+use glam::{Vec3, Vec4};
+
+/// Two triangles (0,1,2) and (1,3,2) hinged on edge 1-2, with vertex 3 lifted to `fold_z`.
+fn hinge_state(fold_z: f32) -> PhysicsState {
+    let positions = vec![
+        0.0, 0.0, 0.0,
+        1.0, 0.0, 0.0,
+        0.0, 1.0, 0.0,
+        1.0, 1.0, fold_z,
+    ];
+    let indices = vec![0, 1, 2, 1, 3, 2];
+    let uvs = vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+    PhysicsState::new(&positions, &indices, &uvs)
+}
+
+#[test]
+fn test_dihedral_gradient_matches_finite_difference() {
+    let p = [
+        Vec3::new(0.1, -0.2, 0.05),
+        Vec3::new(1.0, 0.1, -0.1),
+        Vec3::new(0.4, 0.9, 0.2),
+        Vec3::new(0.6, -0.8, 0.3),
+    ];
+    let (_, grads) = dihedral_angle_and_gradients(p).unwrap();
+
+    let h = 1e-3;
+    for j in 0..4 {
+        for axis in 0..3 {
+            let mut plus = p;
+            let mut minus = p;
+            plus[j][axis] += h;
+            minus[j][axis] -= h;
+            let a_plus = dihedral_angle_and_gradients(plus).unwrap().0;
+            let a_minus = dihedral_angle_and_gradients(minus).unwrap().0;
+            let numeric = (a_plus - a_minus) / (2.0 * h);
+            assert!(
+                (numeric - grads[j][axis]).abs() < 1e-2,
+                "point {} axis {}: analytic {} vs numeric {}",
+                j, axis, grads[j][axis], numeric
+            );
+        }
+    }
+}
+
+#[test]
+fn test_dihedral_rest_angle_from_initial_mesh() {
+    let flat = DihedralConstraint::new(&hinge_state(0.0));
+    assert_eq!(flat.constraints.len(), 1); // Only the interior edge 1-2
+    assert!(flat.rest_angles[0].abs() < 1e-5);
+
+    let folded = DihedralConstraint::new(&hinge_state(0.5));
+    assert!(folded.rest_angles[0].abs() > 0.3);
+}
+
+#[test]
+fn test_dihedral_restores_pre_folded_shape() {
+    let mut state = hinge_state(0.5);
+    let constraint = DihedralConstraint::new(&state);
+    let rest = constraint.rest_angles[0];
+
+    // Flatten the fold; the constraint should pull it back instead of leaving it flat
+    state.positions[3] = Vec4::new(1.0, 1.0, 0.0, 0.0);
+
+    for _ in 0..50 {
+        constraint.solve(&mut state, 0.0, None, 1.0, 0.016);
+    }
+
+    let hinge = constraint.constraints[0].map(|i| state.positions[i].truncate());
+    let angle = dihedral_angle_and_gradients(hinge).unwrap().0;
+    assert!((angle - rest).abs() < 1e-2, "angle {} should return to rest {}", angle, rest);
+}
+
+#[test]
+fn test_dihedral_limits_only_correct_outside_the_stops() {
+    let angle_of = |state: &PhysicsState, constraint: &DihedralConstraint| {
+        let hinge = constraint.constraints[0].map(|i| state.positions[i].truncate());
+        dihedral_angle_and_gradients(hinge).unwrap().0
+    };
+    // Flat rest shape: without limits any fold would be pulled back to 0
+    let mut state = hinge_state(0.0);
+    let constraint = DihedralConstraint::new(&state);
+    let limits = Some((-0.3, 0.3));
+
+    // A small fold inside the stops is left alone
+    state.positions[3] = Vec4::new(1.0, 1.0, 0.1, 0.0);
+    let before = angle_of(&state, &constraint);
+    assert!(before.abs() < 0.3);
+    constraint.solve(&mut state, 0.0, limits, 1.0, 0.016);
+    assert_eq!(angle_of(&state, &constraint), before);
+
+    // A sharp fold is pushed back to the nearest stop, not to flat
+    state.positions[3] = Vec4::new(1.0, 1.0, 1.0, 0.0);
+    for _ in 0..50 {
+        constraint.solve(&mut state, 0.0, limits, 1.0, 0.016);
+    }
+    let angle = angle_of(&state, &constraint);
+    assert!((angle.abs() - 0.3).abs() < 1e-2, "angle {} should stop at 0.3", angle);
+}
@@ -1,6 +1,7 @@
 use glam::Vec4;
 use vestra_physics::engine::state::PhysicsState;
 use vestra_physics::systems::constraints::distance::DistanceConstraint;
+use super::fixtures::quad_grid;
 
 fn make_state() -> PhysicsState {
     let positions = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0];
@@ -39,3 +40,55 @@ fn test_distance_constraint_solve() {
     // Should stay > 1.0 (doesn't solve perfectly in 1 iteration usually, or might, depends on stiffness)
     assert!(state.positions[1].x > 1.0);
 }
+
+#[cfg(feature = "f64")]
+#[test]
+fn test_f64_kernels_solve_in_double_and_store_f32() {
+    use vestra_physics::utils::real::{Real, SIMD_ENABLED};
+    // SIMD kernels are f32-only and must be bypassed
+    const { assert!(std::mem::size_of::<Real>() == 8 && !SIMD_ENABLED) };
+
+    // Particle state stays f32; the rigid edge still lands on its rest length
+    // to the f32 resolution of the stored positions.
+    let mut state = make_state();
+    let constraint_system = DistanceConstraint::new(&state, 0.0);
+    state.positions[1] = Vec4::new(2.0, 0.0, 0.0, 0.0);
+    constraint_system.solve(&mut state, 1.0, 0.016);
+    let len: f32 = (state.positions[1] - state.positions[0]).length();
+    assert!((len - 1.0).abs() < 1e-6, "len = {}", len);
+}
+
+#[test]
+fn test_quad_mesh_constrains_both_diagonals() {
+    let state = quad_grid();
+    let constraint = DistanceConstraint::new(&state, 0.0);
+
+    // 12 quad sides + 4 triangulation diagonals + 4 opposite diagonals
+    assert_eq!(constraint.constraints.len(), 20);
+    for quad in &state.quads {
+        for [a, b] in [[quad[0], quad[2]], [quad[1], quad[3]]] {
+            assert!(constraint
+                .constraints
+                .iter()
+                .any(|&[i, j]| (i, j) == (a.min(b), a.max(b))));
+        }
+    }
+}
+
+#[test]
+fn test_edge_order_is_sorted_within_batches() {
+    // Shuffled triangle order must not change the constraints or their order
+    let positions: Vec<f32> = (0..9).flat_map(|i| [(i % 3) as f32, (i / 3) as f32, 0.0]).collect();
+    let a = PhysicsState::new(&positions, &[0, 3, 1, 1, 3, 4, 4, 5, 1, 4, 7, 5], &[0.0; 18]);
+    let b = PhysicsState::new(&positions, &[4, 7, 5, 1, 3, 4, 4, 5, 1, 0, 3, 1], &[0.0; 18]);
+    let ca = DistanceConstraint::new(&a, 0.0);
+    let cb = DistanceConstraint::new(&b, 0.0);
+    assert_eq!(ca.constraints, cb.constraints);
+    assert_eq!(ca.batch_offsets, cb.batch_offsets);
+
+    // Greedy coloring keeps the (min, max) input order inside every batch
+    for batch in ca.batch_offsets.windows(2) {
+        let edges = &ca.constraints[batch[0]..batch[1]];
+        assert!(edges.windows(2).all(|w| w[0] < w[1]), "{:?}", edges);
+    }
+}
@@ -0,0 +1,19 @@
+use vestra_physics::engine::state::PhysicsState;
+
+/// 3x3 vertex grid (2x2 quads) in the XY plane, 0.1 spacing.
+pub fn quad_grid() -> PhysicsState {
+    let mut positions = Vec::new();
+    for y in 0..3 {
+        for x in 0..3 {
+            positions.extend_from_slice(&[x as f32 * 0.1, y as f32 * 0.1, 0.0]);
+        }
+    }
+    let mut quads = Vec::new();
+    for y in 0..2u32 {
+        for x in 0..2u32 {
+            let i = y * 3 + x;
+            quads.extend_from_slice(&[i, i + 1, i + 4, i + 3]);
+        }
+    }
+    PhysicsState::new_with_quads(&positions, &quads, &[0.0; 18]).unwrap()
+}
@@ -1,4 +1,8 @@
 pub mod area;
+pub mod attachment;
 pub mod bending;
+pub mod dihedral;
 pub mod distance;
+pub mod fixtures;
+pub mod mouse;
 pub mod tether;
@@ -0,0 +1,101 @@
+use vestra_physics::systems::constraints::{GroupGrabConstraint, MouseConstraint};
+use vestra_physics::systems::constraints::mouse::stiffness_to_compliance;
+use vestra_physics::collision::MeshCollider;
+use vestra_physics::engine::state::PhysicsState;
+use glam::Vec3;
+
+fn single_particle() -> PhysicsState {
+    PhysicsState::new(&[0.0, 0.0, 0.0], &[], &[0.0, 0.0])
+}
+
+#[test]
+fn test_rigid_grab_snaps_to_target() {
+    let mut state = single_particle();
+    let mut mouse = MouseConstraint::new();
+    mouse.grab(0, Vec3::new(1.0, 0.0, 0.0));
+    mouse.solve(&mut state, 0.002);
+    assert_eq!(state.positions[0].truncate(), Vec3::new(1.0, 0.0, 0.0));
+}
+
+#[test]
+fn test_spring_grab_pulls_partially() {
+    let mut state = single_particle();
+    let mut mouse = MouseConstraint::new();
+    mouse.set_stiffness(0.5);
+    mouse.grab(0, Vec3::new(1.0, 0.0, 0.0));
+
+    mouse.solve(&mut state, 0.002);
+    let first = state.positions[0].x;
+    assert!(first > 0.0 && first < 0.5, "Spring should not snap: {}", first);
+
+    // Keeps converging towards the cursor over substeps
+    for _ in 0..200 {
+        mouse.solve(&mut state, 0.002);
+    }
+    assert!(state.positions[0].x > 0.99);
+}
+
+#[test]
+fn test_grab_pull_grows_with_stiffness() {
+    // One substep's pull must rise monotonically from nothing (0) to a snap (1)
+    let mut last = -1.0;
+    for k in 0..=20 {
+        let mut state = single_particle();
+        let mut mouse = MouseConstraint::new();
+        mouse.set_stiffness(k as f32 / 20.0);
+        mouse.grab(0, Vec3::new(1.0, 0.0, 0.0));
+        mouse.solve(&mut state, 0.002);
+        let x = state.positions[0].x;
+        assert!(x.is_finite() && x > last, "stiffness {}: {} <= {}", k as f32 / 20.0, x, last);
+        last = x;
+    }
+    assert_eq!(last, 1.0);
+    assert_eq!(stiffness_to_compliance(1.0), 0.0);
+    assert!(stiffness_to_compliance(0.999) < 1.0e-6, "No jump near rigid");
+}
+
+#[test]
+fn test_sweep_stops_grab_at_collider() {
+    let collider = MeshCollider::new(
+        vec![-5.0, 0.0, -5.0, 15.0, 0.0, -5.0, -5.0, 0.0, 15.0],
+        vec![],
+        vec![0, 2, 1],
+        0,
+        0.0,
+    );
+    let mut state = PhysicsState::new(&[0.0, 0.1, 0.0], &[], &[0.0, 0.0]);
+    let mut mouse = MouseConstraint::new();
+
+    // A fast drag straight through the floor
+    mouse.grab(0, Vec3::new(0.0, -1.0, 0.0));
+    let from = state.positions[0].truncate();
+    mouse.solve(&mut state, 0.002);
+    mouse.sweep(&mut state, &collider, from, 0.005);
+    assert!((state.positions[0].y - 0.005).abs() < 1e-5, "y = {}", state.positions[0].y);
+
+    // A drag that stays above the surface is untouched
+    mouse.update_target(Vec3::new(0.5, 0.2, 0.0));
+    let from = state.positions[0].truncate();
+    mouse.solve(&mut state, 0.002);
+    mouse.sweep(&mut state, &collider, from, 0.005);
+    assert_eq!(state.positions[0].truncate(), Vec3::new(0.5, 0.2, 0.0));
+}
+
+#[test]
+fn test_group_grab_moves_cluster_rigidly() {
+    let mut state = PhysicsState::new(&[0.0, 0.0, 0.0, 0.1, 0.0, 0.0, 0.0, 0.1, 0.0], &[0, 1, 2], &[0.0; 6]);
+    let mut group = GroupGrabConstraint::new();
+    group.grab(&state, &[0, 1]);
+
+    group.translate(Vec3::new(0.0, 0.5, 0.0));
+    group.translate(Vec3::new(0.2, 0.0, 0.0));
+    group.solve(&mut state, 0.002);
+
+    assert!(state.positions[0].truncate().distance(Vec3::new(0.2, 0.5, 0.0)) < 1e-6);
+    assert!(state.positions[1].truncate().distance(Vec3::new(0.3, 0.5, 0.0)) < 1e-6);
+    // Not in the group
+    assert_eq!(state.positions[2].truncate(), Vec3::new(0.0, 0.1, 0.0));
+
+    group.release();
+    assert!(!group.is_active());
+}
@@ -1,6 +1,6 @@
 use glam::Vec4;
 use vestra_physics::engine::state::PhysicsState;
-use vestra_physics::systems::constraints::tether::TetherConstraint;
+use vestra_physics::systems::constraints::tether::{TetherConstraint, TetherGroup, TetherMode};
 
 fn make_state() -> PhysicsState {
     let positions = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0]; // 1.0 apart
@@ -23,7 +23,7 @@ fn test_tether_constraint_preserves_length() {
 
     // Solve multiple times to ensure convergence
     for _ in 0..5 {
-        constraint.solve(&mut state, omega, dt);
+        constraint.solve(&mut state, [0.0, 0.0], omega, dt);
     }
 
     // Should be pulled back. Tether usually clamps to max_len.
@@ -33,3 +33,170 @@ fn test_tether_constraint_preserves_length() {
     assert!(state.positions[1].x < 2.0);
     assert!(state.positions[1].x > 0.5);
 }
+
+#[test]
+fn test_tether_groups_use_their_own_compliance() {
+    // One horizontal tether stretched to 2.0 (rest 1.0)
+    let tether = TetherConstraint {
+        constraints: vec![[0, 1]],
+        rest_lengths: vec![1.0],
+        groups: vec![TetherGroup::Horizontal],
+        modes: vec![TetherMode::Extension],
+        batch_offsets: vec![0, 1],
+    };
+    let dt = 0.016;
+
+    let mut rigid = make_state();
+    rigid.positions[1] = Vec4::new(2.0, 0.0, 0.0, 0.0);
+    // A soft vertical group must not affect a horizontal tether
+    tether.solve(&mut rigid, [1.0, 0.0], 1.0, dt);
+
+    let mut soft = make_state();
+    soft.positions[1] = Vec4::new(2.0, 0.0, 0.0, 0.0);
+    tether.solve(&mut soft, [0.0, 1.0e-3], 1.0, dt);
+
+    let rigid_len = (rigid.positions[1] - rigid.positions[0]).truncate().length();
+    let soft_len = (soft.positions[1] - soft.positions[0]).truncate().length();
+    assert!((rigid_len - 1.0).abs() < 1e-4, "rigid tether should snap to rest, got {}", rigid_len);
+    assert!(soft_len > rigid_len + 0.1, "soft tether should correct less: {} vs {}", soft_len, rigid_len);
+}
+
+/// Triangulated 6x6 vertex grid in the XY plane, 0.05 spacing (0.25 m square).
+fn make_grid_state() -> PhysicsState {
+    let n = 6;
+    let mut positions = Vec::new();
+    for y in 0..n {
+        for x in 0..n {
+            positions.extend([x as f32 * 0.05, y as f32 * 0.05, 0.0]);
+        }
+    }
+    let mut indices = Vec::new();
+    for y in 0..n - 1 {
+        for x in 0..n - 1 {
+            let i = (y * n + x) as u32;
+            let up = i + n as u32;
+            indices.extend([i, i + 1, up, i + 1, up + 1, up]);
+        }
+    }
+    PhysicsState::new(&positions, &indices, &vec![0.0; 2 * n * n])
+}
+
+#[test]
+fn test_generated_tethers_are_tagged() {
+    let state = make_grid_state();
+    let tether = TetherConstraint::new(&state);
+    assert_eq!(tether.groups.len(), tether.constraints.len());
+    assert_eq!(tether.modes.len(), tether.constraints.len());
+    assert!(tether.modes.iter().all(|&m| m == TetherMode::Extension));
+    assert!(tether.groups.contains(&TetherGroup::Vertical));
+    assert!(tether.groups.contains(&TetherGroup::Horizontal));
+
+    // Tags follow the constraints through the batch reorder
+    for (&[a, b], &group) in tether.constraints.iter().zip(&tether.groups) {
+        let (pa, pb) = (state.rest_positions[a], state.rest_positions[b]);
+        match group {
+            TetherGroup::Vertical => assert_eq!(pa.x, pb.x, "vertical tether spans one column"),
+            TetherGroup::Horizontal => {
+                assert!(pa.y.min(pb.y) >= 0.1 - 1e-6, "horizontal tethers stay in the top band");
+                assert_ne!(pa.x, pb.x, "horizontal tether crosses the width");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_open_boundary_borrows_interior_normals() {
+    // Thin open edges: the rim normals collapsed to zero, the interior faces +Z
+    let mut state = make_grid_state();
+    for i in 0..state.count {
+        state.normals[i] = if state.topology.is_boundary(i) { Vec4::ZERO } else { Vec4::Z };
+    }
+    let tether = TetherConstraint::new(&state);
+    assert!(tether.groups.contains(&TetherGroup::Vertical));
+    assert!(tether.groups.contains(&TetherGroup::Horizontal));
+}
+
+#[test]
+fn test_tether_modes_limit_extension_or_compression() {
+    // Vertical tethers, rest 1.0, solved at `length` under `mode`
+    let solved_length = |mode: TetherMode, length: f32| {
+        // Several lanes so both the SIMD and the scalar kernel run
+        let tether = TetherConstraint {
+            constraints: vec![[0, 1], [2, 3], [4, 5], [6, 7], [8, 9]],
+            rest_lengths: vec![1.0; 5],
+            groups: vec![TetherGroup::Vertical; 5],
+            modes: vec![mode; 5],
+            batch_offsets: vec![0, 5],
+        };
+        let mut positions = Vec::new();
+        for k in 0..5 {
+            let z = k as f32;
+            positions.extend([0.0, 0.0, z, length, 0.0, z]);
+        }
+        let mut state = PhysicsState::new(&positions, &[0, 1, 2], &[0.0; 20]);
+        tether.solve(&mut state, [0.0, 0.0], 1.0, 0.016);
+        (0..5)
+            .map(|k| {
+                let (a, b) = (state.internal_index[2 * k] as usize, state.internal_index[2 * k + 1] as usize);
+                (state.positions[b] - state.positions[a]).truncate().length()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let close = |lengths: Vec<f32>, expected: f32| {
+        assert!(lengths.iter().all(|l| (l - expected).abs() < 1e-4), "{:?} vs {}", lengths, expected);
+    };
+    close(solved_length(TetherMode::Extension, 2.0), 1.0);
+    close(solved_length(TetherMode::Extension, 0.5), 0.5);
+    close(solved_length(TetherMode::Compression, 2.0), 2.0);
+    close(solved_length(TetherMode::Compression, 0.5), 1.0);
+    close(solved_length(TetherMode::Bilateral, 2.0), 1.0);
+    close(solved_length(TetherMode::Bilateral, 0.5), 1.0);
+
+    assert_eq!(TetherMode::from_index(2), Some(TetherMode::Bilateral));
+    assert_eq!(TetherMode::from_index(3), None);
+}
+
+#[test]
+fn test_tether_modes_are_per_constraint() {
+    // Five compressed tethers (rest 1.0, length 0.5) in one batch, same group:
+    // only the ones tagged bilateral push apart
+    let mut positions = Vec::new();
+    for k in 0..5 {
+        let z = k as f32;
+        positions.extend([0.0, 0.0, z, 1.0, 0.0, z]);
+    }
+    let mut state = PhysicsState::new(&positions, &[0, 1, 2], &[0.0; 20]);
+    let pairs: Vec<[usize; 2]> = (0..5)
+        .map(|k| [state.internal_index[2 * k] as usize, state.internal_index[2 * k + 1] as usize])
+        .collect();
+    let mut tether = TetherConstraint {
+        constraints: Vec::new(),
+        rest_lengths: Vec::new(),
+        groups: Vec::new(),
+        modes: Vec::new(),
+        batch_offsets: vec![0],
+    };
+    tether.add(&state, &pairs, TetherGroup::Vertical, TetherMode::Extension);
+    assert_eq!(tether.constraints.len(), 5);
+    assert!(tether.rest_lengths.iter().all(|&l| (l - 1.0).abs() < 1e-6));
+
+    let [a, b] = pairs[1];
+    assert_eq!(tether.set_mode(b, a, TetherMode::Bilateral), 1);
+    assert_eq!(tether.set_mode(a, pairs[2][1], TetherMode::Bilateral), 0);
+
+    for &[_, b] in &pairs {
+        state.positions[b].x = 0.5;
+    }
+    tether.solve(&mut state, [0.0, 0.0], 1.0, 0.016);
+    for (k, &[a, b]) in pairs.iter().enumerate() {
+        let len = (state.positions[b] - state.positions[a]).truncate().length();
+        let expected = if k == 1 { 1.0 } else { 0.5 };
+        assert!((len - expected).abs() < 1e-4, "tether {}: {} vs {}", k, len, expected);
+    }
+
+    tether.set_group_mode(TetherGroup::Vertical, TetherMode::Compression);
+    assert!(tether.modes.iter().all(|&m| m == TetherMode::Compression));
+    assert_eq!(TetherGroup::from_index(1), Some(TetherGroup::Horizontal));
+    assert_eq!(TetherGroup::from_index(2), None);
+}
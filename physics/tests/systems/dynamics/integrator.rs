@@ -4,6 +4,7 @@ use vestra_physics::engine::config::PhysicsConfig;
 use glam::Vec3;
 
 #[test]
+#[allow(clippy::field_reassign_with_default)]
 fn test_integrator_gravity() {
     let positions = vec![0.0, 0.0, 0.0];
     let indices = vec![0, 0, 0]; // unused
@@ -29,3 +30,22 @@ fn test_integrator_gravity() {
     // Prev position becomes old pos (0.0)
     assert_eq!(state.prev_positions[0].y, 0.0);
 }
+
+#[test]
+fn test_integrator_clamps_displacement() {
+    let mut state = PhysicsState::new(&[0.0, 0.0, 0.0], &[0, 0, 0], &[0.0; 2]);
+    let config = PhysicsConfig {
+        gravity: Vec3::ZERO,
+        damping: 1.0,
+        max_displacement: 0.01,
+        ..Default::default()
+    };
+
+    // A huge impulse: unclamped, the particle would jump 100m in one substep
+    let forces = vec![Vec3::new(100.0, 0.0, 0.0)];
+    Integrator::integrate(&mut state, &config, &forces, 1.0);
+
+    let step = state.positions[0] - state.prev_positions[0];
+    assert!((step.length() - 0.01).abs() < 1e-6);
+    assert!(step.x > 0.0);
+}
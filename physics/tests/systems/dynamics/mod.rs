@@ -1 +1,2 @@
 pub mod integrator;
+pub mod solver;
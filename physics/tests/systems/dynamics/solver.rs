@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use vestra_physics::collision::CollisionResolver;
+use vestra_physics::engine::config::PhysicsConfig;
+use vestra_physics::engine::state::PhysicsState;
+use vestra_physics::systems::dynamics::{ConstraintPlugin, MeshStats, Solver};
+
+#[test]
+fn test_mesh_stats_report_rest_shape() {
+    // Right triangle (legs 1 and 2) plus a sliver whose third vertex lies on an edge
+    let positions = vec![
+        0.0, 0.0, 0.0,
+        1.0, 0.0, 0.0,
+        0.0, 2.0, 0.0,
+        0.5, 0.0, 0.0,
+    ];
+    let indices = vec![0, 1, 2, 0, 1, 3];
+    let state = PhysicsState::new(&positions, &indices, &[0.0; 8]);
+    let stats = Solver::new(&state, 1.0, 0.0).mesh_stats();
+
+    // Edges: 0-1 (1), 1-2 (sqrt 5), 0-2 (2), 1-3 (0.5), 0-3 (0.5)
+    let mean = (1.0 + 5.0f32.sqrt() + 2.0 + 0.5 + 0.5) / 5.0;
+    assert_eq!(stats.min_edge_length, 0.5);
+    assert!((stats.max_edge_length - 5.0f32.sqrt()).abs() < 1e-6);
+    assert!((stats.mean_edge_length - mean).abs() < 1e-6);
+    assert_eq!(stats.min_triangle_area, 1.0);
+    assert_eq!(stats.max_triangle_area, 1.0);
+    assert_eq!(stats.degenerate_triangles, 1);
+}
+
+#[test]
+fn test_mesh_stats_empty_mesh() {
+    let state = PhysicsState::new(&[], &[], &[]);
+    assert_eq!(Solver::new(&state, 1.0, 0.0).mesh_stats(), MeshStats::default());
+}
+
+/// Keeps every particle above `height`, counting its calls.
+struct Floor {
+    height: f32,
+    calls: Arc<AtomicUsize>,
+}
+
+impl ConstraintPlugin for Floor {
+    fn solve(&mut self, state: &mut PhysicsState, _dt: f32, iteration: usize) {
+        assert_eq!(iteration, self.calls.fetch_add(1, Ordering::Relaxed));
+        for p in state.positions.iter_mut() {
+            p.y = p.y.max(self.height);
+        }
+    }
+}
+
+#[test]
+fn test_plugins_run_every_iteration() {
+    let mut state = PhysicsState::new(&[0.0, -1.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0, 1.0], &[0, 1, 2], &[0.0; 6]);
+    let mut solver = Solver::new(&state, 1.0, 0.0);
+    let calls = Arc::new(AtomicUsize::new(0));
+    solver.plugins.push(Box::new(Floor { height: 0.0, calls: calls.clone() }));
+
+    let config = PhysicsConfig { solver_iterations: 3, ..Default::default() };
+    let resolver = CollisionResolver::new(state.count);
+    solver.solve(&mut state, &resolver, &config, 1.0 / 240.0);
+
+    assert_eq!(calls.load(Ordering::Relaxed), 3);
+    assert!(state.positions.iter().all(|p| p.y >= 0.0));
+}
+
+#[test]
+fn test_distance_relaxation_scales_correction() {
+    let correction = |relaxation: f32| {
+        let mut state = PhysicsState::new(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0], &[0, 1, 2], &[0.0; 6]);
+        let mut solver = Solver::new(&state, 1.0, 0.0);
+        let i = state.internal_index[1] as usize;
+        let start = state.positions[i].x + 0.5;
+        state.positions[i].x = start;
+
+        let config = PhysicsConfig {
+            solver_iterations: 1,
+            use_chebyshev: false,
+            bending_enabled: false,
+            tether_enabled: false,
+            area_enabled: false,
+            distance_relaxation: relaxation,
+            ..Default::default()
+        };
+        let resolver = CollisionResolver::new(state.count);
+        solver.solve(&mut state, &resolver, &config, 1.0 / 240.0);
+        start - state.positions[i].x
+    };
+
+    let full = correction(1.0);
+    let half = correction(0.5);
+    assert!(full > 0.0);
+    assert!(half > 0.0 && half < full, "{} vs {}", half, full);
+    assert!(PhysicsConfig::builder().distance_relaxation(2.0).build().is_err());
+    assert!(PhysicsConfig::builder().bending_relaxation(0.0).build().is_err());
+}
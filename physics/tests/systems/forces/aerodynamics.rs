@@ -4,6 +4,7 @@ use vestra_physics::systems::forces::Aerodynamics;
 use glam::Vec4;
 
 #[test]
+#[allow(clippy::field_reassign_with_default)]
 fn test_aerodynamics_wind_force() {
     let positions = vec![0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
     let indices = vec![0, 1, 2]; // One triangle
@@ -33,3 +34,88 @@ fn test_aerodynamics_wind_force() {
     assert!(forces[1].z < 0.0);
     assert!(forces[2].z < 0.0);
 }
+
+#[test]
+fn test_wind_turbulence() {
+    let steady = PhysicsConfig {
+        wind: glam::Vec3::new(2.0, 0.0, 0.0),
+        ..Default::default()
+    };
+    let gusty = PhysicsConfig {
+        wind_turbulence: 1.0,
+        wind_seed: 3,
+        ..steady.clone()
+    };
+
+    let mut a = Aerodynamics::new(0);
+    let mut b = Aerodynamics::new(0);
+    let p = glam::Vec3::new(0.3, 1.2, 0.1);
+
+    // No turbulence: the base wind everywhere
+    assert_eq!(a.wind_at(p, &steady), steady.wind);
+
+    // Reproducible for the same seed and time, varying over time
+    a.advance_time(0.5);
+    b.advance_time(0.5);
+    let w0 = a.wind_at(p, &gusty);
+    assert_eq!(w0, b.wind_at(p, &gusty));
+    assert!((w0 - gusty.wind).length() <= 3.0);
+
+    a.advance_time(0.37);
+    assert_ne!(a.wind_at(p, &gusty), w0, "Gusts should evolve over time");
+}
+
+#[test]
+fn test_aerodynamics_disabled_produces_no_force() {
+    let positions = vec![0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+    let state = PhysicsState::new(&positions, &[0, 1, 2], &[0.0; 6]);
+    let mut a = Aerodynamics::new(state.count);
+
+    let config = PhysicsConfig {
+        wind: glam::Vec3::new(0.0, 0.0, -5.0),
+        aerodynamics_enabled: false,
+        ..Default::default()
+    };
+    let forces = a.apply(&state, &config, 0.016);
+    assert!(forces.iter().all(|f| *f == glam::Vec3::ZERO));
+}
+
+#[test]
+fn test_wind_occlusion_shades_leeward_cloth() {
+    use vestra_physics::collision::MeshCollider;
+
+    // A wall at z = 0; the wind blows toward -Z, so z > 0 is windward
+    let collider = MeshCollider::new(
+        vec![-1.0, -1.0, 0.0, 1.0, -1.0, 0.0, 1.0, 1.0, 0.0, -1.0, 1.0, 0.0],
+        vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+        vec![0, 1, 2, 0, 2, 3],
+        0,
+        0.0,
+    );
+    // One triangle in front of the wall, one behind it
+    let positions = vec![
+        0.0, 0.2, 0.5, 0.2, 0.2, 0.5, 0.0, 0.0, 0.5,
+        0.0, 0.2, -0.5, 0.2, 0.2, -0.5, 0.0, 0.0, -0.5,
+    ];
+    let state = PhysicsState::new(&positions, &[0, 1, 2, 3, 4, 5], &[0.0; 12]);
+
+    let force_on = |wind_occlusion: bool| {
+        let config = PhysicsConfig {
+            wind: glam::Vec3::new(0.0, 0.0, -5.0),
+            wind_occlusion,
+            ..Default::default()
+        };
+        let mut aero = Aerodynamics::new(state.count);
+        aero.update_occlusion(&state, &config, &collider);
+        let forces = aero.apply(&state, &config, 0.016);
+        (forces[0].length(), forces[3].length())
+    };
+
+    let (front, back) = force_on(false);
+    assert!((front - back).abs() < 1e-6);
+
+    // Force scales with the wind speed squared: a 20% draft leaves 4% of the force
+    let (front, back) = force_on(true);
+    assert!(front > 0.0);
+    assert!((back / front - 0.04).abs() < 1e-3, "ratio {}", back / front);
+}
@@ -0,0 +1,62 @@
+use vestra_physics::utils::coloring::{
+    color_constraints, color_constraints_3, color_constraints_4, validate_batches,
+};
+
+/// Checks that every constraint appears once and no batch touches a particle twice.
+fn assert_valid<const N: usize>(constraints: &[[usize; N]], sorted: &[usize], offsets: &[usize]) {
+    let mut seen = sorted.to_vec();
+    seen.sort_unstable();
+    assert_eq!(seen, (0..constraints.len()).collect::<Vec<_>>());
+
+    for batch in offsets.windows(2) {
+        let mut particles: Vec<usize> = sorted[batch[0]..batch[1]]
+            .iter()
+            .flat_map(|&c| constraints[c])
+            .collect();
+        let len = particles.len();
+        particles.sort_unstable();
+        particles.dedup();
+        assert_eq!(particles.len(), len, "Batch shares a particle");
+    }
+}
+
+#[test]
+fn test_coloring_star_beyond_64_colors() {
+    // Every edge touches the hub, so each needs its own color
+    let edges: Vec<[usize; 2]> = (1..=100).map(|i| [0, i]).collect();
+    let (sorted, offsets) = color_constraints(&edges, 101);
+    assert_eq!(offsets.len() - 1, 100);
+    assert_valid(&edges, &sorted, &offsets);
+
+    let triangles: Vec<[usize; 3]> = (1..=80).map(|i| [0, i, i + 1]).collect();
+    let (sorted, offsets) = color_constraints_3(&triangles, 82);
+    assert_eq!(offsets.len() - 1, 80);
+    assert_valid(&triangles, &sorted, &offsets);
+
+    let hinges: Vec<[usize; 4]> = (1..=70).map(|i| [0, 1, i + 1, i + 2]).collect();
+    let (sorted, offsets) = color_constraints_4(&hinges, 73);
+    assert_eq!(offsets.len() - 1, 70);
+    assert_valid(&hinges, &sorted, &offsets);
+}
+
+#[test]
+fn test_coloring_chain_uses_two_colors() {
+    let edges: Vec<[usize; 2]> = (0..10).map(|i| [i, i + 1]).collect();
+    let (sorted, offsets) = color_constraints(&edges, 11);
+    assert_eq!(offsets, vec![0, 5, 10]);
+    assert_valid(&edges, &sorted, &offsets);
+}
+
+#[test]
+fn test_validate_batches_rejects_bad_colorings() {
+    let edges = [[0, 1], [1, 2], [2, 3]];
+    assert!(validate_batches(&edges, &[0, 2, 1], &[0, 2, 3], 4).is_ok());
+
+    // Edges 0 and 1 share particle 1
+    let err = validate_batches(&edges, &[0, 1, 2], &[0, 2, 3], 4).unwrap_err();
+    assert!(err.contains("particle 1"), "{}", err);
+
+    // Missing and duplicated constraints
+    assert!(validate_batches(&edges, &[0, 2], &[0, 2], 4).is_err());
+    assert!(validate_batches(&edges, &[0, 2, 2], &[0, 2, 3], 4).is_err());
+}
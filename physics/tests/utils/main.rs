@@ -1 +1,3 @@
+mod coloring;
+mod noise;
 mod normals;
@@ -0,0 +1,17 @@
+use glam::Vec3;
+use vestra_physics::utils::noise::perlin3;
+
+#[test]
+fn test_perlin_deterministic_and_bounded() {
+    let p = Vec3::new(0.37, 1.91, -2.4);
+    assert_eq!(perlin3(p, 7), perlin3(p, 7));
+    assert_ne!(perlin3(p, 7), perlin3(p, 8), "Seed should change the field");
+
+    // Zero on lattice points, bounded elsewhere
+    assert_eq!(perlin3(Vec3::new(3.0, -1.0, 2.0), 1), 0.0);
+    for k in 0..1000 {
+        let q = Vec3::new(k as f32 * 0.173, k as f32 * 0.071, k as f32 * -0.113);
+        let n = perlin3(q, 42);
+        assert!(n.is_finite() && n.abs() <= 1.5, "Noise out of range: {}", n);
+    }
+}
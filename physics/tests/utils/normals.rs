@@ -1,4 +1,4 @@
-use vestra_physics::utils::normals::compute_vertex_normals;
+use vestra_physics::utils::normals::{blend_normals, compute_vertex_normals, compute_vertex_normals_weighted};
 use glam::Vec4;
 
 #[test]
@@ -38,3 +38,70 @@ fn test_degenerate_triangle() {
         assert_eq!(*n, Vec4::new(0.0, 1.0, 0.0, 0.0));
     }
 }
+
+#[test]
+fn test_blend_normals() {
+    let previous = vec![Vec4::new(0.0, 1.0, 0.0, 0.0), Vec4::new(0.0, 0.0, -1.0, 0.0)];
+
+    // 0 = off: fresh normals are kept as-is
+    let mut normals = vec![Vec4::new(1.0, 0.0, 0.0, 0.0), Vec4::new(0.0, 0.0, 1.0, 0.0)];
+    blend_normals(&mut normals, &previous, 0.0);
+    assert_eq!(normals[0], Vec4::new(1.0, 0.0, 0.0, 0.0));
+
+    blend_normals(&mut normals, &previous, 0.5);
+    let expected = std::f32::consts::FRAC_1_SQRT_2;
+    assert!((normals[0].x - expected).abs() < 1e-6 && (normals[0].y - expected).abs() < 1e-6);
+    assert!((normals[0].truncate().length() - 1.0).abs() < 1e-6);
+    assert_eq!(normals[0].w, 0.0);
+    // Exactly opposing normals cancel; the fresh one wins
+    assert_eq!(normals[1], Vec4::new(0.0, 0.0, 1.0, 0.0));
+}
+
+#[test]
+fn test_orient_indices_fixes_mixed_winding() {
+    use glam::Vec3;
+    use vestra_physics::engine::topology::MeshTopology;
+    use vestra_physics::utils::normals::orient_indices;
+
+    // Flat 3x2 strip in the XZ plane; the second quad is authored with flipped winding
+    let positions: Vec<Vec4> = (0..6)
+        .map(|i| Vec4::new((i % 3) as f32, 0.0, (i / 3) as f32, 0.0))
+        .collect();
+    let indices = vec![0, 3, 1, 1, 3, 4, 1, 2, 4, 2, 5, 4];
+    let topology = MeshTopology::new(&indices, 6);
+
+    for up in [Vec3::Y, -Vec3::Y] {
+        let oriented = orient_indices(&positions, &indices, &topology, up);
+        let mut normals = vec![Vec4::ZERO; 6];
+        compute_vertex_normals(&positions, &oriented, &mut normals);
+        for n in &normals {
+            assert!((n.truncate() - up).length() < 1e-5, "{:?} should face {:?}", n, up);
+        }
+    }
+}
+
+#[test]
+fn test_angle_weighting_ignores_face_size() {
+    // Fan around vertex 0: a tiny +Y face and a huge +X face, both with a 90 degree corner
+    let positions = vec![
+        Vec4::new(0.0, 0.0, 0.0, 0.0),
+        Vec4::new(0.0, 0.0, 0.1, 0.0),
+        Vec4::new(0.1, 0.0, 0.0, 0.0),
+        Vec4::new(0.0, 10.0, 0.0, 0.0),
+        Vec4::new(0.0, 0.0, 10.0, 0.0),
+    ];
+    let indices = vec![0, 1, 2, 0, 3, 4];
+
+    let mut area = vec![Vec4::ZERO; 5];
+    compute_vertex_normals_weighted(&positions, &indices, &mut area, false);
+    let mut angle = vec![Vec4::ZERO; 5];
+    compute_vertex_normals_weighted(&positions, &indices, &mut angle, true);
+
+    // Area weighting is dominated by the large face
+    assert!(area[0].x > 0.99, "Area-weighted: {}", area[0]);
+    // Angle weighting splits the two equal corners evenly
+    let half = std::f32::consts::FRAC_1_SQRT_2;
+    assert!((angle[0].x - half).abs() < 1e-5 && (angle[0].y - half).abs() < 1e-5, "Angle-weighted: {}", angle[0]);
+    // Vertices with a single face get that face's normal either way
+    assert!((angle[1].y - 1.0).abs() < 1e-6 && (angle[3].x - 1.0).abs() < 1e-6);
+}
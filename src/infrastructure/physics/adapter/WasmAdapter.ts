@@ -7,7 +7,9 @@ import type { InitPayload, MainToWorkerMessage, WorkerToMainMessage } from '../w
 
 export interface ProfileReport {
     frameCount: number;
-    categories: { [key: string]: { avg: number; min: number; max: number; last: number; count: number; }; };
+    categories: { [key: string]: { avg: number; min: number | null; max: number; last: number; count: number; p95: number | null; p99: number | null; }; };
+    counters: { CollisionPairs: number; Contacts: number; SelfCollisionBatches: number; };
+    substeps: { avg: number; min: number | null; max: number; last: number; count: number; p95: number | null; p99: number | null; }[];
 }
 
 /**